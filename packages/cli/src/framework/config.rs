@@ -39,6 +39,10 @@ pub enum NetworkVariant {
 
     /// Beaker's state of the network will be shared with collaborator via vcs
     Shared,
+
+    /// In-process chain backed by cw-multi-test, no running node required.
+    /// Chain data is kept in `.beaker/simulated` and beaker's state is not shared via vcs
+    Simulated,
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, Getters, GetDataDocs)]
@@ -50,10 +54,12 @@ pub struct Network {
     /// Network variant used to specify whether state file of the network should be tracked in vcs or not
     network_variant: NetworkVariant,
 
-    /// Endpoint for grpc
+    /// Endpoint for grpc, not required for `Simulated` network
+    #[serde(default)]
     grpc_endpoint: String,
 
-    /// Endpoint for rpc
+    /// Endpoint for rpc, not required for `Simulated` network
+    #[serde(default)]
     rpc_endpoint: String,
 }
 
@@ -81,6 +87,15 @@ impl Default for GlobalConfig {
             account_prefix: "osmo".to_string(),
             derivation_path: "m/44'/118'/0'/0/0".to_string(),
             networks: Map::from([
+                (
+                    "simulated".into(),
+                    Network {
+                        chain_id: "beaker-simulated".into(),
+                        network_variant: NetworkVariant::Simulated,
+                        grpc_endpoint: "".into(),
+                        rpc_endpoint: "".into(),
+                    }
+                ),
                 (
                     "local".into(),
                     Network {
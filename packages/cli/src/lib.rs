@@ -32,8 +32,11 @@ use clap::{Parser, Subcommand};
 use config::Config;
 use data_doc_derive::GetDataDocs;
 use modules::{
+    authz::entrypoint::{AuthzCmd, AuthzModule},
     key::entrypoint::{KeyCmd, KeyModule},
+    state::entrypoint::{StateCmd, StateModule},
     task::entrypoint::{TaskCmd, TaskModule},
+    tx::entrypoint::{TxCmd, TxModule},
 };
 use serde::{Deserialize, Serialize};
 use support::node::run_npx;
@@ -47,10 +50,13 @@ pub use modules::workspace::{WorkspaceCmd, WorkspaceConfig, WorkspaceModule};
 pub use support::cosmos::{Client, SigningClient};
 pub use support::gas::{Gas, GasPrice};
 pub use support::state::{
-    Proposal, State, WasmRef, STATE_DIR, STATE_FILE_LOCAL, STATE_FILE_SHARED,
+    Proposal, State, WasmRef, STATE_DIR, STATE_FILE_LOCAL, STATE_FILE_SHARED, STATE_VERSION,
 };
 
-use crate::modules::{key::config::KeyConfig, task::config::TaskConfig};
+use crate::modules::{
+    authz::config::AuthzConfig, key::config::KeyConfig, state::config::StateConfig,
+    task::config::TaskConfig, tx::config::TxConfig,
+};
 
 #[derive(Parser)]
 #[clap(author, version,about, long_about = None)]
@@ -88,6 +94,21 @@ pub enum Commands {
         #[clap(subcommand)]
         cmd: TaskCmd,
     },
+    /// Signing and broadcasting transactions generated by `--generate-only`
+    Tx {
+        #[clap(subcommand)]
+        cmd: TxCmd,
+    },
+    /// Managing authz grants for executing contracts on behalf of another account
+    Authz {
+        #[clap(subcommand)]
+        cmd: AuthzCmd,
+    },
+    /// Inspecting the deployments recorded in beaker's state
+    State {
+        #[clap(subcommand)]
+        cmd: StateCmd,
+    },
 }
 
 #[derive(Serialize, Deserialize, GetDataDocs)]
@@ -149,7 +170,10 @@ context!(
     WorkspaceContext, config = { workspace: WorkspaceConfig };
     ConsoleContext, config = { console: ConsoleConfig };
     KeyContext, config = { key: KeyConfig };
-    TaskContext, config = { task: TaskConfig }
+    TaskContext, config = { task: TaskConfig };
+    TxContext, config = { tx: TxConfig };
+    AuthzContext, config = { authz: AuthzConfig };
+    StateContext, config = { state: StateConfig }
 );
 
 pub fn execute(cmd: &Commands) -> Result<()> {
@@ -159,6 +183,9 @@ pub fn execute(cmd: &Commands) -> Result<()> {
         Commands::Console { network } => console(network),
         Commands::Key { cmd } => KeyModule::execute(KeyContext::new(), cmd),
         Commands::Task { cmd } => TaskModule::execute(TaskContext::new(), cmd),
+        Commands::Tx { cmd } => TxModule::execute(TxContext::new(), cmd),
+        Commands::Authz { cmd } => AuthzModule::execute(AuthzContext::new(), cmd),
+        Commands::State { cmd } => StateModule::execute(StateContext::new(), cmd),
     }
 }
 
@@ -0,0 +1,269 @@
+use anyhow::Result;
+use clap::Subcommand;
+use cosmrs::{AccountId, Any};
+
+use super::config::AuthzConfig;
+use super::ops;
+use crate::framework::{Context, Module};
+use crate::modules::tx;
+use crate::modules::tx::ops::{DryRunResponse, GenerateOnlyResponse};
+use crate::modules::wasm::args::BaseTxArgs;
+use crate::support::gas::Gas;
+
+#[derive(Subcommand, Debug)]
+pub enum AuthzCmd {
+    /// Grant an address to execute a contract on behalf of the signer
+    Grant {
+        /// Address allowed to execute the contract
+        grantee: String,
+
+        /// Name of the contract that can be executed
+        contract_name: String,
+
+        /// Label of the instantiated contract
+        #[clap(short, long, default_value = "default")]
+        label: String,
+
+        /// Maximum number of executions allowed by the grant
+        #[clap(long)]
+        max_calls: Option<u64>,
+
+        /// Maximum funds that can be sent along with the executions, eg. `1000uosmo`
+        #[clap(long)]
+        max_funds: Option<String>,
+
+        /// Only allow messages with these top level keys, eg. `increment,reset`. All messages are allowed by default
+        #[clap(long, value_delimiter = ',')]
+        accepted_keys: Vec<String>,
+
+        /// Number of days until the grant expires, it never expires by default
+        #[clap(long)]
+        expires_in_days: Option<u64>,
+
+        #[clap(flatten)]
+        base_tx_args: BaseTxArgs,
+    },
+    /// Revoke the contract execution grant given by the signer to an address
+    Revoke {
+        /// Address whose grant is revoked
+        grantee: String,
+
+        #[clap(flatten)]
+        base_tx_args: BaseTxArgs,
+    },
+    /// List grants given by and / or to an address
+    List {
+        /// Address that gave the grants
+        #[clap(long, required_unless_present = "grantee")]
+        granter: Option<String>,
+
+        /// Address that received the grants
+        #[clap(long)]
+        grantee: Option<String>,
+
+        /// Name of the network to query, the actual endpoint / chain-id are defined in config
+        #[clap(short, long, default_value = "local")]
+        network: String,
+    },
+}
+
+impl AuthzCmd {
+    /// Common tx args of the commands that produce a transaction
+    fn tx_args(&self) -> Option<&BaseTxArgs> {
+        match self {
+            AuthzCmd::Grant { base_tx_args, .. } | AuthzCmd::Revoke { base_tx_args, .. } => {
+                Some(base_tx_args)
+            }
+            AuthzCmd::List { .. } => None,
+        }
+    }
+
+    fn is_generate_only(&self) -> bool {
+        self.tx_args()
+            .is_some_and(|args| args.generate_only.is_some())
+    }
+
+    fn is_dry_run(&self) -> bool {
+        self.tx_args().is_some_and(|args| args.dry_run)
+    }
+}
+
+pub struct AuthzModule {}
+
+impl<'a> Module<'a, AuthzConfig, AuthzCmd, anyhow::Error> for AuthzModule {
+    fn execute<Ctx: Context<'a, AuthzConfig>>(
+        ctx: Ctx,
+        cmd: &AuthzCmd,
+    ) -> Result<(), anyhow::Error> {
+        match cmd {
+            cmd if cmd.is_generate_only() => generate_only(ctx, cmd).map(|_| ()),
+            cmd if cmd.is_dry_run() => dry_run(ctx, cmd).map(|_| ()),
+            AuthzCmd::Grant {
+                grantee,
+                contract_name,
+                label,
+                max_calls,
+                max_funds,
+                accepted_keys,
+                expires_in_days,
+                base_tx_args,
+            } => {
+                let BaseTxArgs {
+                    network,
+                    signer_args,
+                    gas_args,
+                    account_sequence,
+                    ..
+                }: &BaseTxArgs = base_tx_args;
+                let global_conf = ctx.global_config()?;
+                ops::grant(
+                    &ctx,
+                    contract_name,
+                    label,
+                    grantee,
+                    *max_calls,
+                    max_funds.as_deref().try_into()?,
+                    accepted_keys,
+                    *expires_in_days,
+                    network,
+                    &base_tx_args.tx_options()?,
+                    &Gas::from_args(
+                        gas_args,
+                        global_conf.gas_price(),
+                        global_conf.gas_adjustment(),
+                    )?,
+                    signer_args.private_key(&global_conf)?,
+                    account_sequence,
+                )
+                .map(|_| ())
+            }
+            AuthzCmd::Revoke {
+                grantee,
+                base_tx_args,
+            } => {
+                let BaseTxArgs {
+                    network,
+                    signer_args,
+                    gas_args,
+                    account_sequence,
+                    ..
+                }: &BaseTxArgs = base_tx_args;
+                let global_conf = ctx.global_config()?;
+                ops::revoke(
+                    &ctx,
+                    grantee,
+                    network,
+                    &base_tx_args.tx_options()?,
+                    &Gas::from_args(
+                        gas_args,
+                        global_conf.gas_price(),
+                        global_conf.gas_adjustment(),
+                    )?,
+                    signer_args.private_key(&global_conf)?,
+                    account_sequence,
+                )
+                .map(|_| ())
+            }
+            AuthzCmd::List {
+                granter,
+                grantee,
+                network,
+            } => ops::list(&ctx, granter.as_deref(), grantee.as_deref(), network).map(|_| ()),
+        }
+    }
+}
+
+fn generate_only<'a>(
+    ctx: impl Context<'a, AuthzConfig>,
+    cmd: &AuthzCmd,
+) -> Result<GenerateOnlyResponse> {
+    let base_tx_args = cmd.tx_args().unwrap_or_else(|| unimplemented!());
+    let BaseTxArgs {
+        network,
+        signer_args,
+        gas_args,
+        account_sequence,
+        generate_only,
+        ..
+    }: &BaseTxArgs = base_tx_args;
+
+    let global_conf = ctx.global_config()?;
+    let granter = signer_args.account_id(&global_conf)?;
+
+    tx::ops::generate_only(
+        &ctx,
+        vec![cmd_msg(&ctx, cmd, &granter)?],
+        &granter,
+        network,
+        &Gas::from_args(
+            gas_args,
+            global_conf.gas_price(),
+            global_conf.gas_adjustment(),
+        )?,
+        &base_tx_args.tx_options()?,
+        account_sequence,
+        None,
+        generate_only.as_ref().unwrap(),
+    )
+}
+
+fn dry_run<'a>(ctx: impl Context<'a, AuthzConfig>, cmd: &AuthzCmd) -> Result<DryRunResponse> {
+    let base_tx_args = cmd.tx_args().unwrap_or_else(|| unimplemented!());
+    let BaseTxArgs {
+        network,
+        signer_args,
+        gas_args,
+        account_sequence,
+        ..
+    }: &BaseTxArgs = base_tx_args;
+
+    let global_conf = ctx.global_config()?;
+    let granter = signer_args.account_id(&global_conf)?;
+
+    tx::ops::dry_run(
+        &ctx,
+        vec![cmd_msg(&ctx, cmd, &granter)?],
+        &granter,
+        network,
+        &Gas::from_args(
+            gas_args,
+            global_conf.gas_price(),
+            global_conf.gas_adjustment(),
+        )?,
+        &base_tx_args.tx_options()?,
+        account_sequence,
+    )
+}
+
+/// Message of the command sent by `granter`, for `--generate-only` and `--dry-run`.
+fn cmd_msg<'a, Ctx: Context<'a, AuthzConfig>>(
+    ctx: &Ctx,
+    cmd: &AuthzCmd,
+    granter: &AccountId,
+) -> Result<Any> {
+    match cmd {
+        AuthzCmd::Grant {
+            grantee,
+            contract_name,
+            label,
+            max_calls,
+            max_funds,
+            accepted_keys,
+            expires_in_days,
+            base_tx_args,
+        } => ops::grant_msg(
+            ctx,
+            contract_name,
+            label,
+            grantee,
+            *max_calls,
+            max_funds.as_deref().try_into()?,
+            accepted_keys,
+            *expires_in_days,
+            &base_tx_args.network,
+            granter,
+        ),
+        AuthzCmd::Revoke { grantee, .. } => ops::revoke_msg(grantee, granter),
+        AuthzCmd::List { .. } => unimplemented!(),
+    }
+}
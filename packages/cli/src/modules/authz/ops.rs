@@ -0,0 +1,412 @@
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use anyhow::{anyhow, bail, Context as _, Result};
+use cosmos_sdk_proto::cosmos::authz::v1beta1::{
+    GenericAuthorization, Grant, GrantAuthorization, MsgGrant, MsgRevoke,
+};
+use cosmos_sdk_proto::traits::Message;
+use cosmrs::crypto::secp256k1::SigningKey;
+use cosmrs::{AccountId, Any};
+use serde::Serialize;
+
+use crate::attrs_format;
+use crate::framework::Context;
+use crate::support::authz::{
+    AcceptedMessageKeysFilter, AllowAllMessagesFilter, CombinedLimit,
+    ContractExecutionAuthorization, ContractGrant, MaxCallsLimit, MaxFundsLimit,
+    ACCEPTED_MESSAGE_KEYS_FILTER_TYPE_URL, ALLOW_ALL_MESSAGES_FILTER_TYPE_URL,
+    COMBINED_LIMIT_TYPE_URL, CONTRACT_EXECUTION_AUTHORIZATION_TYPE_URL, MAX_CALLS_LIMIT_TYPE_URL,
+    MAX_FUNDS_LIMIT_TYPE_URL, MSG_EXECUTE_CONTRACT_TYPE_URL,
+};
+use crate::support::coin::Coins;
+use crate::support::cosmos::{Client, TxOptions};
+use crate::support::future::block;
+use crate::support::gas::Gas;
+use crate::support::ops_response::OpResponseDisplay;
+use crate::support::proto::MessageExt;
+use crate::support::state::State;
+use crate::vars_format;
+
+use super::config::AuthzConfig;
+
+const GENERIC_AUTHORIZATION_TYPE_URL: &str = "/cosmos.authz.v1beta1.GenericAuthorization";
+
+#[allow(clippy::too_many_arguments)]
+pub fn grant<'a, Ctx: Context<'a, AuthzConfig>>(
+    ctx: &Ctx,
+    contract_name: &str,
+    label: &str,
+    grantee: &str,
+    max_calls: Option<u64>,
+    max_funds: Coins,
+    accepted_keys: &[String],
+    expires_in_days: Option<u64>,
+    network: &str,
+    tx_options: &TxOptions,
+    gas: &Gas,
+    signing_key: SigningKey,
+    account_sequence: &Option<u64>,
+) -> Result<GrantResponse> {
+    let global_config = ctx.global_config()?;
+
+    let network_info = global_config
+        .networks()
+        .get(network)
+        .with_context(|| format!("Unable to find network config: {network}"))?
+        .to_owned();
+
+    let client =
+        Client::new(network_info, &ctx.root()?, &global_config).to_signing_client(signing_key);
+    let granter = client.signer_account_id();
+
+    let msg_grant = grant_msg(
+        ctx,
+        contract_name,
+        label,
+        grantee,
+        max_calls,
+        max_funds,
+        accepted_keys,
+        expires_in_days,
+        network,
+        &granter,
+    )?;
+    let contract_address = contract_address(ctx, contract_name, label, network)?;
+
+    block(async {
+        client
+            .sign_and_broadcast(vec![msg_grant], gas, tx_options, account_sequence)
+            .await?;
+
+        let grant_response = GrantResponse {
+            granter: granter.to_string(),
+            grantee: grantee.to_string(),
+            contract_address,
+        };
+
+        grant_response.log();
+
+        Ok(grant_response)
+    })
+}
+
+/// Build the `MsgGrant` allowing `grantee` to execute the labeled contract on behalf of `granter`.
+/// Either `max_calls` or `max_funds` must be set since the chain requires the grant to be limited.
+#[allow(clippy::too_many_arguments)]
+pub fn grant_msg<'a, Ctx: Context<'a, AuthzConfig>>(
+    ctx: &Ctx,
+    contract_name: &str,
+    label: &str,
+    grantee: &str,
+    max_calls: Option<u64>,
+    max_funds: Coins,
+    accepted_keys: &[String],
+    expires_in_days: Option<u64>,
+    network: &str,
+    granter: &AccountId,
+) -> Result<Any> {
+    let amounts: Vec<cosmos_sdk_proto::cosmos::base::v1beta1::Coin> = max_funds.into();
+    let limit = match (max_calls, amounts.is_empty()) {
+        (None, true) => {
+            bail!("Either `--max-calls` or `--max-funds` is required to limit the grant")
+        }
+        (Some(remaining), true) => Any {
+            type_url: MAX_CALLS_LIMIT_TYPE_URL.to_owned(),
+            value: MaxCallsLimit { remaining }.to_bytes()?,
+        },
+        (None, false) => Any {
+            type_url: MAX_FUNDS_LIMIT_TYPE_URL.to_owned(),
+            value: MaxFundsLimit { amounts }.to_bytes()?,
+        },
+        (Some(calls_remaining), false) => Any {
+            type_url: COMBINED_LIMIT_TYPE_URL.to_owned(),
+            value: CombinedLimit {
+                calls_remaining,
+                amounts,
+            }
+            .to_bytes()?,
+        },
+    };
+
+    let filter = if accepted_keys.is_empty() {
+        Any {
+            type_url: ALLOW_ALL_MESSAGES_FILTER_TYPE_URL.to_owned(),
+            value: AllowAllMessagesFilter {}.to_bytes()?,
+        }
+    } else {
+        Any {
+            type_url: ACCEPTED_MESSAGE_KEYS_FILTER_TYPE_URL.to_owned(),
+            value: AcceptedMessageKeysFilter {
+                keys: accepted_keys.to_vec(),
+            }
+            .to_bytes()?,
+        }
+    };
+
+    let authorization = ContractExecutionAuthorization {
+        grants: vec![ContractGrant {
+            contract: contract_address(ctx, contract_name, label, network)?,
+            limit: Some(limit),
+            filter: Some(filter),
+        }],
+    };
+
+    let expiration = expires_in_days
+        .map(|days| -> Result<prost_types::Timestamp> {
+            let now = SystemTime::now().duration_since(UNIX_EPOCH)?.as_secs();
+            Ok(prost_types::Timestamp {
+                seconds: (now + days * 24 * 60 * 60).try_into()?,
+                nanos: 0,
+            })
+        })
+        .transpose()?;
+
+    let msg_grant = MsgGrant {
+        granter: granter.to_string(),
+        grantee: parse_address(grantee)?.to_string(),
+        grant: Some(Grant {
+            authorization: Some(Any {
+                type_url: CONTRACT_EXECUTION_AUTHORIZATION_TYPE_URL.to_owned(),
+                value: authorization.to_bytes()?,
+            }),
+            expiration,
+        }),
+    };
+
+    Ok(Any {
+        type_url: "/cosmos.authz.v1beta1.MsgGrant".to_owned(),
+        value: msg_grant.to_bytes()?,
+    })
+}
+
+pub fn revoke<'a, Ctx: Context<'a, AuthzConfig>>(
+    ctx: &Ctx,
+    grantee: &str,
+    network: &str,
+    tx_options: &TxOptions,
+    gas: &Gas,
+    signing_key: SigningKey,
+    account_sequence: &Option<u64>,
+) -> Result<RevokeResponse> {
+    let global_config = ctx.global_config()?;
+
+    let network_info = global_config
+        .networks()
+        .get(network)
+        .with_context(|| format!("Unable to find network config: {network}"))?
+        .to_owned();
+
+    let client =
+        Client::new(network_info, &ctx.root()?, &global_config).to_signing_client(signing_key);
+    let granter = client.signer_account_id();
+
+    let msg_revoke = revoke_msg(grantee, &granter)?;
+
+    block(async {
+        client
+            .sign_and_broadcast(vec![msg_revoke], gas, tx_options, account_sequence)
+            .await?;
+
+        let revoke_response = RevokeResponse {
+            granter: granter.to_string(),
+            grantee: grantee.to_string(),
+            msg_type_url: MSG_EXECUTE_CONTRACT_TYPE_URL.to_string(),
+        };
+
+        revoke_response.log();
+
+        Ok(revoke_response)
+    })
+}
+
+/// Build the `MsgRevoke` of the contract execution grant given by `granter` to `grantee`.
+pub fn revoke_msg(grantee: &str, granter: &AccountId) -> Result<Any> {
+    let msg_revoke = MsgRevoke {
+        granter: granter.to_string(),
+        grantee: parse_address(grantee)?.to_string(),
+        msg_type_url: MSG_EXECUTE_CONTRACT_TYPE_URL.to_owned(),
+    };
+
+    Ok(Any {
+        type_url: "/cosmos.authz.v1beta1.MsgRevoke".to_owned(),
+        value: msg_revoke.to_bytes()?,
+    })
+}
+
+pub fn list<'a, Ctx: Context<'a, AuthzConfig>>(
+    ctx: &Ctx,
+    granter: Option<&str>,
+    grantee: Option<&str>,
+    network: &str,
+) -> Result<ListResponse> {
+    let global_config = ctx.global_config()?;
+
+    let network_info = global_config
+        .networks()
+        .get(network)
+        .with_context(|| format!("Unable to find network config: {network}"))?
+        .to_owned();
+
+    let client = Client::new(network_info, &ctx.root()?, &global_config);
+
+    block(async {
+        let grants = client.authz_grants(granter, grantee).await?;
+
+        let list_response = ListResponse {
+            grants: grants.iter().map(format_grant).collect(),
+        };
+
+        list_response.log();
+
+        Ok(list_response)
+    })
+}
+
+fn contract_address<'a, Ctx: Context<'a, AuthzConfig>>(
+    ctx: &Ctx,
+    contract_name: &str,
+    label: &str,
+    network: &str,
+) -> Result<String> {
+    let network_info = ctx
+        .global_config()?
+        .networks()
+        .get(network)
+        .with_context(|| format!("Unable to find network config: {network}"))?
+        .to_owned();
+
+    let state = State::load_by_network(network_info, ctx.root()?)?;
+    state
+        .get_ref(network, contract_name)?
+        .addresses()
+        .get(label)
+        .cloned()
+        .with_context(|| format!("Unable to retrieve contract for {contract_name}:{label}"))
+}
+
+fn parse_address(address: &str) -> Result<AccountId> {
+    address
+        .parse::<AccountId>()
+        .map_err(|e| anyhow!(e))
+        .with_context(|| format!("Invalid address: {address}"))
+}
+
+/// Granter, grantee and authorization of the grant, along with the contracts for wasm authorizations.
+fn format_grant(grant: &GrantAuthorization) -> String {
+    let authorization = grant.authorization.as_ref().map(|authorization| {
+        let value = &authorization.value[..];
+        let content = match authorization.type_url.as_str() {
+            CONTRACT_EXECUTION_AUTHORIZATION_TYPE_URL => {
+                ContractExecutionAuthorization::decode(value).ok().map(|a| {
+                    let contracts = a
+                        .grants
+                        .iter()
+                        .map(|g| g.contract.as_str())
+                        .collect::<Vec<_>>()
+                        .join(",");
+                    format!("contracts={contracts}")
+                })
+            }
+            GENERIC_AUTHORIZATION_TYPE_URL => GenericAuthorization::decode(value)
+                .ok()
+                .map(|a| format!("msg={}", a.msg)),
+            _ => None,
+        };
+
+        match content {
+            Some(content) => format!("{}: {content}", authorization.type_url),
+            None => authorization.type_url.clone(),
+        }
+    });
+
+    let expiration = grant
+        .expiration
+        .as_ref()
+        .map(|t| t.seconds.to_string())
+        .unwrap_or_else(|| "-".to_string());
+
+    format!(
+        "{} -> {}, {}, expiration={expiration}",
+        grant.granter,
+        grant.grantee,
+        authorization.unwrap_or_else(|| "-".to_string())
+    )
+}
+
+#[derive(Serialize)]
+pub struct GrantResponse {
+    pub granter: String,
+    pub grantee: String,
+    pub contract_address: String,
+}
+
+impl OpResponseDisplay for GrantResponse {
+    fn headline() -> &'static str {
+        "Contract execution has been granted successfully!! 🎉"
+    }
+    fn attrs(&self) -> Vec<String> {
+        attrs_format! { self | granter, grantee, contract_address }
+    }
+}
+
+#[derive(Serialize)]
+pub struct RevokeResponse {
+    pub granter: String,
+    pub grantee: String,
+    pub msg_type_url: String,
+}
+
+impl OpResponseDisplay for RevokeResponse {
+    fn headline() -> &'static str {
+        "Contract execution grant has been revoked successfully!! 🎉"
+    }
+    fn attrs(&self) -> Vec<String> {
+        attrs_format! { self | granter, grantee, msg_type_url }
+    }
+}
+
+#[derive(Serialize)]
+pub struct ListResponse {
+    pub grants: Vec<String>,
+}
+
+impl OpResponseDisplay for ListResponse {
+    fn headline() -> &'static str {
+        "Grants have been retrieved successfully!! 🎉"
+    }
+    fn attrs(&self) -> Vec<String> {
+        let grants = self.grants.join("\n");
+        let count = self.grants.len();
+        vars_format!(grants, count)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn format_contract_execution_grant() {
+        let authorization = ContractExecutionAuthorization {
+            grants: vec![ContractGrant {
+                contract: "osmo1contract".to_string(),
+                limit: None,
+                filter: None,
+            }],
+        };
+        let grant = GrantAuthorization {
+            granter: "osmo1granter".to_string(),
+            grantee: "osmo1grantee".to_string(),
+            authorization: Some(Any {
+                type_url: CONTRACT_EXECUTION_AUTHORIZATION_TYPE_URL.to_owned(),
+                value: authorization.to_bytes().unwrap(),
+            }),
+            expiration: None,
+        };
+
+        assert_eq!(
+            format_grant(&grant),
+            "osmo1granter -> osmo1grantee, /cosmwasm.wasm.v1.ContractExecutionAuthorization: contracts=osmo1contract, expiration=-"
+        );
+    }
+}
@@ -1,4 +1,7 @@
+pub mod authz;
 pub mod key;
+pub mod state;
 pub mod task;
+pub mod tx;
 pub mod wasm;
 pub mod workspace;
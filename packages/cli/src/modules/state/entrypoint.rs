@@ -0,0 +1,57 @@
+use anyhow::Result;
+use clap::Subcommand;
+use serde::Deserialize;
+
+use super::config::StateConfig;
+use super::ops::{self, HistoryResponse};
+use crate::framework::{Context, Module};
+
+#[derive(Subcommand, Debug, Deserialize)]
+pub enum StateCmd {
+    /// Show the recorded deployments of each label of a contract, oldest first
+    History {
+        contract_name: String,
+
+        /// Only show the history of this label
+        #[clap(short, long)]
+        #[serde(default)]
+        label: Option<String>,
+
+        /// Name of the network whose state is shown, the actual endpoint / chain-id are defined in config
+        #[clap(short, long, default_value = "local")]
+        #[serde(default = "default_value::network")]
+        network: String,
+    },
+}
+
+mod default_value {
+    pub(crate) fn network() -> String {
+        "local".to_string()
+    }
+}
+
+pub struct StateModule {}
+
+impl<'a> Module<'a, StateConfig, StateCmd, anyhow::Error> for StateModule {
+    fn execute<Ctx: Context<'a, StateConfig>>(
+        ctx: Ctx,
+        cmd: &StateCmd,
+    ) -> Result<(), anyhow::Error> {
+        match cmd {
+            cmd @ StateCmd::History { .. } => history(ctx, cmd).map(|_| ()),
+        }
+    }
+}
+
+pub(crate) fn history<'a>(
+    ctx: impl Context<'a, StateConfig>,
+    cmd: &StateCmd,
+) -> Result<HistoryResponse> {
+    match cmd {
+        StateCmd::History {
+            contract_name,
+            label,
+            network,
+        } => ops::history(&ctx, contract_name, label.as_deref(), network),
+    }
+}
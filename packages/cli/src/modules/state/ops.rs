@@ -0,0 +1,110 @@
+use anyhow::{Context as _, Result};
+use config::Map;
+use serde::Serialize;
+
+use super::config::StateConfig;
+use crate::framework::Context;
+use crate::support::state::{Deployment, DeploymentKind, State};
+use crate::vars_format;
+
+/// Show the deployments recorded for the labels of a contract, or only for `label` if given.
+pub fn history<'a, Ctx: Context<'a, StateConfig>>(
+    ctx: &Ctx,
+    contract_name: &str,
+    label: Option<&str>,
+    network: &str,
+) -> Result<HistoryResponse> {
+    let network_info = ctx
+        .global_config()?
+        .networks()
+        .get(network)
+        .with_context(|| format!("Unable to find network config: {network}"))?
+        .to_owned();
+
+    let state = State::load_by_network(network_info, ctx.root()?)?;
+    let wasm_ref = state.get_ref(network, contract_name)?;
+
+    let history = match label {
+        Some(label) => {
+            let deployments = wasm_ref.history().get(label).cloned().with_context(|| {
+                format!("No history found for {contract_name}:{label} on network `{network}`")
+            })?;
+            Map::from([(label.to_string(), deployments)])
+        }
+        None => wasm_ref.history().clone(),
+    };
+
+    let history_response = HistoryResponse {
+        contract_name: contract_name.to_string(),
+        network: network.to_string(),
+        history,
+    };
+    history_response.log();
+
+    Ok(history_response)
+}
+
+/// Single line summary of a deployment, its msg is left to the json output.
+fn deployment_str(deployment: &Deployment) -> String {
+    let kind = match deployment.kind() {
+        DeploymentKind::Instantiate => "instantiate",
+        DeploymentKind::Migrate => "migrate",
+        DeploymentKind::Rollback => "rollback",
+    };
+    let or_dash = |s: &str| {
+        if s.is_empty() {
+            "-".to_string()
+        } else {
+            s.to_string()
+        }
+    };
+
+    format!(
+        "{kind} · code_id: {} · checksum: {} · height: {} · tx: {} · at: {} · by: {} · msg_file: {} · beaker: {}",
+        deployment.code_id(),
+        or_dash(deployment.checksum().as_deref().unwrap_or_default()),
+        deployment.height(),
+        or_dash(deployment.tx_hash()),
+        or_dash(deployment.timestamp()),
+        or_dash(deployment.signer()),
+        or_dash(deployment.msg_file().as_deref().unwrap_or("raw")),
+        or_dash(deployment.beaker_version()),
+    )
+}
+
+#[derive(Serialize)]
+pub struct HistoryResponse {
+    pub contract_name: String,
+    pub network: String,
+    pub history: Map<String, Vec<Deployment>>,
+}
+
+impl HistoryResponse {
+    fn log(&self) {
+        if self.history.is_empty() {
+            println!(
+                "\n  No deployment recorded for {} on {}\n",
+                self.contract_name, self.network
+            );
+        }
+
+        for (label, deployments) in &self.history {
+            let deployments = deployments
+                .iter()
+                .map(deployment_str)
+                .collect::<Vec<_>>()
+                .join("\n");
+            println!(
+                "{}",
+                vars_format!(
+                    format!(
+                        "History of {}:{label} on {}",
+                        self.contract_name, self.network
+                    ),
+                    deployments
+                )
+                .join("\n")
+            );
+        }
+    }
+}
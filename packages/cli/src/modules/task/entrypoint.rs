@@ -6,7 +6,7 @@ use std::{
 
 use super::{
     config::TaskConfig,
-    script_mod::{wasm, wasm_proposal},
+    script_mod::{state, wasm, wasm_proposal},
 };
 use crate::framework::{Context, Module};
 use anyhow::{Context as _, Result};
@@ -58,12 +58,14 @@ impl<'a> Module<'a, TaskConfig, TaskCmd, anyhow::Error> for TaskModule {
                 let package = FilesystemPackage::new();
                 package.register_into_engine_as(&mut engine, "fs");
 
-                // register wasm & wasm_proposal module
+                // register wasm, wasm_proposal & state module
                 let wasm = exported_module!(wasm::commands);
                 let wasm_proposal = exported_module!(wasm_proposal::commands);
+                let state = exported_module!(state::commands);
 
                 engine.register_static_module("wasm", wasm.into());
                 engine.register_static_module("wasm::proposal", wasm_proposal.into());
+                engine.register_static_module("state", state.into());
 
                 let script_path = task_path.join(format!("{}.rhai", script));
 
@@ -103,7 +105,7 @@ impl<'a> Module<'a, TaskConfig, TaskCmd, anyhow::Error> for TaskModule {
                                 }))
                                 .try_get_matches_from(
                                     // emulate calling cli command with args
-                                    vec![prog_name].iter().chain(args.to_owned().iter()),
+                                    [prog_name].iter().chain(args.to_owned().iter()),
                                 )
                                 .map_err(|e| <Box<EvalAltResult>>::from(e.to_string()))?;
 
@@ -1,2 +1,16 @@
+use rhai::{EvalAltResult, Map};
+
+pub mod state;
 pub mod wasm;
 pub mod wasm_proposal;
+
+/// Tasks broadcast their txs as they go, so generating or simulating them is only supported from the command line.
+pub(crate) fn reject_offline_args(cmd_args: &Map) -> Result<(), Box<EvalAltResult>> {
+    let dry_run = cmd_args
+        .get("dry_run")
+        .is_some_and(|v| v.as_bool().unwrap_or(true));
+    if dry_run || cmd_args.contains_key("generate_only") {
+        return Err("`dry_run` and `generate_only` are not supported in tasks".into());
+    }
+    Ok(())
+}
@@ -0,0 +1,26 @@
+use crate::modules::state;
+
+use rhai::plugin::*;
+use rhai::Map;
+
+#[export_module]
+pub(crate) mod commands {
+    use rhai::{
+        serde::{from_dynamic, to_dynamic},
+        Dynamic, EvalAltResult,
+    };
+
+    use crate::StateContext;
+
+    const CONTEXT: StateContext = StateContext {};
+
+    #[rhai_fn(return_raw)]
+    pub fn history(cmd_args: Map) -> Result<Dynamic, Box<EvalAltResult>> {
+        let mut cmd = Map::new();
+        cmd.insert("History".into(), cmd_args.into());
+
+        state::entrypoint::history(CONTEXT, &from_dynamic(&to_dynamic(cmd)?)?)
+            .map_err(|e| e.to_string().into())
+            .and_then(to_dynamic)
+    }
+}
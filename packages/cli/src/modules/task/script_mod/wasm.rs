@@ -1,5 +1,7 @@
 use crate::modules::wasm;
 
+use super::reject_offline_args;
+
 use rhai::plugin::*;
 use rhai::Map;
 
@@ -7,16 +9,20 @@ use rhai::Map;
 pub(crate) mod commands {
     use rhai::{
         serde::{from_dynamic, to_dynamic},
-        Dynamic, EvalAltResult,
+        Array, Dynamic, EvalAltResult,
     };
-    use serde::Serialize;
+    use serde::{Deserialize, Serialize};
 
+    use crate::modules::wasm::args::BaseTxArgs;
+    use crate::modules::wasm::ops::batch::BatchOp;
     use crate::{Context, WasmContext};
 
     const CONTEXT: WasmContext = WasmContext {};
 
     #[rhai_fn(return_raw)]
     pub fn deploy(mut cmd_args: Map) -> Result<Dynamic, Box<EvalAltResult>> {
+        reject_offline_args(&cmd_args)?;
+
         if cmd_args.contains_key("msg") {
             let msg = from_dynamic::<Map>(&cmd_args["msg"])?;
             let msg_json = serde_json::to_string(&msg).map_err(|e| e.to_string())?;
@@ -73,6 +79,8 @@ pub(crate) mod commands {
 
     #[rhai_fn(return_raw)]
     pub fn store_code(cmd_args: Map) -> Result<Dynamic, Box<EvalAltResult>> {
+        reject_offline_args(&cmd_args)?;
+
         let mut cmd = Map::new();
         cmd.insert("StoreCode".into(), cmd_args.into());
 
@@ -83,6 +91,8 @@ pub(crate) mod commands {
 
     #[rhai_fn(return_raw)]
     pub fn update_admin(cmd_args: Map) -> Result<Dynamic, Box<EvalAltResult>> {
+        reject_offline_args(&cmd_args)?;
+
         let mut cmd = Map::new();
         cmd.insert("UpdateAdmin".into(), cmd_args.into());
 
@@ -93,6 +103,8 @@ pub(crate) mod commands {
 
     #[rhai_fn(return_raw)]
     pub fn clear_admin(cmd_args: Map) -> Result<Dynamic, Box<EvalAltResult>> {
+        reject_offline_args(&cmd_args)?;
+
         let mut cmd = Map::new();
         cmd.insert("ClearAdmin".into(), cmd_args.into());
 
@@ -103,6 +115,8 @@ pub(crate) mod commands {
 
     #[rhai_fn(return_raw)]
     pub fn instantiate(mut cmd_args: Map) -> Result<Dynamic, Box<EvalAltResult>> {
+        reject_offline_args(&cmd_args)?;
+
         if cmd_args.contains_key("msg") {
             let msg = from_dynamic::<Map>(&cmd_args["msg"])?;
             let msg_json = serde_json::to_string(&msg).map_err(|e| e.to_string())?;
@@ -119,6 +133,8 @@ pub(crate) mod commands {
 
     #[rhai_fn(return_raw)]
     pub fn migrate(mut cmd_args: Map) -> Result<Dynamic, Box<EvalAltResult>> {
+        reject_offline_args(&cmd_args)?;
+
         if cmd_args.contains_key("msg") {
             let msg = from_dynamic::<Map>(&cmd_args["msg"])?;
             let msg_json = serde_json::to_string(&msg).map_err(|e| e.to_string())?;
@@ -135,6 +151,8 @@ pub(crate) mod commands {
 
     #[rhai_fn(return_raw)]
     pub fn upgrade(mut cmd_args: Map) -> Result<Dynamic, Box<EvalAltResult>> {
+        reject_offline_args(&cmd_args)?;
+
         if cmd_args.contains_key("msg") {
             let msg = from_dynamic::<Map>(&cmd_args["msg"])?;
             let msg_json = serde_json::to_string(&msg).map_err(|e| e.to_string())?;
@@ -149,8 +167,28 @@ pub(crate) mod commands {
             .and_then(to_dynamic)
     }
 
+    #[rhai_fn(return_raw)]
+    pub fn rollback(mut cmd_args: Map) -> Result<Dynamic, Box<EvalAltResult>> {
+        reject_offline_args(&cmd_args)?;
+
+        if cmd_args.contains_key("msg") {
+            let msg = from_dynamic::<Map>(&cmd_args["msg"])?;
+            let msg_json = serde_json::to_string(&msg).map_err(|e| e.to_string())?;
+            cmd_args.insert("raw".into(), msg_json.into());
+        }
+
+        let mut cmd = Map::new();
+        cmd.insert("Rollback".into(), cmd_args.into());
+
+        wasm::entrypoint::rollback(CONTEXT, &from_dynamic(&to_dynamic(cmd)?)?)
+            .map_err(|e| e.to_string().into())
+            .and_then(to_dynamic)
+    }
+
     #[rhai_fn(return_raw)]
     pub fn execute(mut cmd_args: Map) -> Result<Dynamic, Box<EvalAltResult>> {
+        reject_offline_args(&cmd_args)?;
+
         if cmd_args.contains_key("msg") {
             let msg = from_dynamic::<Map>(&cmd_args["msg"])?;
             let msg_json = serde_json::to_string(&msg).map_err(|e| e.to_string())?;
@@ -165,6 +203,39 @@ pub(crate) mod commands {
             .and_then(to_dynamic)
     }
 
+    #[derive(Deserialize)]
+    struct BatchArgs {
+        #[serde(default)]
+        no_proposal_sync: bool,
+        #[serde(default)]
+        yes: bool,
+        #[serde(flatten)]
+        base_tx_args: BaseTxArgs,
+    }
+
+    #[rhai_fn(name = "batch", return_raw)]
+    pub fn batch_with_args(ops: Array, cmd_args: Map) -> Result<Dynamic, Box<EvalAltResult>> {
+        reject_offline_args(&cmd_args)?;
+
+        let batch_ops = from_dynamic::<Vec<BatchOp>>(&ops.into())?;
+        let args = from_dynamic::<BatchArgs>(&cmd_args.into())?;
+
+        wasm::entrypoint::batch_ops(
+            CONTEXT,
+            &batch_ops,
+            args.no_proposal_sync,
+            args.yes,
+            &args.base_tx_args,
+        )
+        .map_err(|e| e.to_string().into())
+        .and_then(to_dynamic)
+    }
+
+    #[rhai_fn(return_raw)]
+    pub fn batch(ops: Array) -> Result<Dynamic, Box<EvalAltResult>> {
+        batch_with_args(ops, Map::new())
+    }
+
     #[rhai_fn(return_raw)]
     pub fn get_config() -> Result<Dynamic, Box<EvalAltResult>> {
         CONTEXT
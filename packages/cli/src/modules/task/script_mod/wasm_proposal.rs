@@ -1,5 +1,7 @@
 use crate::modules::wasm;
 
+use super::reject_offline_args;
+
 use rhai::plugin::*;
 use rhai::Map;
 
@@ -16,6 +18,8 @@ pub(crate) mod commands {
 
     #[rhai_fn(return_raw)]
     pub fn store_code(mut cmd_args: Map) -> Result<Dynamic, Box<EvalAltResult>> {
+        reject_offline_args(&cmd_args)?;
+
         if cmd_args.contains_key("msg") {
             let msg = from_dynamic::<Map>(&cmd_args["msg"])?;
             let msg_json = serde_json::to_string(&msg).map_err(|e| e.to_string())?;
@@ -30,8 +34,112 @@ pub(crate) mod commands {
             .and_then(to_dynamic)
     }
 
+    #[rhai_fn(return_raw)]
+    pub fn store_and_instantiate(mut cmd_args: Map) -> Result<Dynamic, Box<EvalAltResult>> {
+        reject_offline_args(&cmd_args)?;
+
+        if cmd_args.contains_key("msg") {
+            let msg = from_dynamic::<Map>(&cmd_args["msg"])?;
+            let msg_json = serde_json::to_string(&msg).map_err(|e| e.to_string())?;
+            cmd_args.insert("raw".into(), msg_json.into());
+        }
+
+        let mut cmd = Map::new();
+        cmd.insert("StoreAndInstantiate".into(), cmd_args.into());
+
+        wasm::proposal::entrypoint::propose(CONTEXT, &from_dynamic(&to_dynamic(cmd)?)?)
+            .map_err(|e| e.to_string().into())
+            .and_then(to_dynamic)
+    }
+
+    #[rhai_fn(return_raw)]
+    pub fn instantiate(mut cmd_args: Map) -> Result<Dynamic, Box<EvalAltResult>> {
+        reject_offline_args(&cmd_args)?;
+
+        if cmd_args.contains_key("msg") {
+            let msg = from_dynamic::<Map>(&cmd_args["msg"])?;
+            let msg_json = serde_json::to_string(&msg).map_err(|e| e.to_string())?;
+            cmd_args.insert("raw".into(), msg_json.into());
+        }
+
+        let mut cmd = Map::new();
+        cmd.insert("Instantiate".into(), cmd_args.into());
+
+        wasm::proposal::entrypoint::propose(CONTEXT, &from_dynamic(&to_dynamic(cmd)?)?)
+            .map_err(|e| e.to_string().into())
+            .and_then(to_dynamic)
+    }
+
+    #[rhai_fn(return_raw)]
+    pub fn migrate(mut cmd_args: Map) -> Result<Dynamic, Box<EvalAltResult>> {
+        reject_offline_args(&cmd_args)?;
+
+        if cmd_args.contains_key("msg") {
+            let msg = from_dynamic::<Map>(&cmd_args["msg"])?;
+            let msg_json = serde_json::to_string(&msg).map_err(|e| e.to_string())?;
+            cmd_args.insert("raw".into(), msg_json.into());
+        }
+
+        let mut cmd = Map::new();
+        cmd.insert("Migrate".into(), cmd_args.into());
+
+        wasm::proposal::entrypoint::propose(CONTEXT, &from_dynamic(&to_dynamic(cmd)?)?)
+            .map_err(|e| e.to_string().into())
+            .and_then(to_dynamic)
+    }
+
+    #[rhai_fn(return_raw)]
+    pub fn update_admin(cmd_args: Map) -> Result<Dynamic, Box<EvalAltResult>> {
+        reject_offline_args(&cmd_args)?;
+
+        let mut cmd = Map::new();
+        cmd.insert("UpdateAdmin".into(), cmd_args.into());
+
+        wasm::proposal::entrypoint::propose(CONTEXT, &from_dynamic(&to_dynamic(cmd)?)?)
+            .map_err(|e| e.to_string().into())
+            .and_then(to_dynamic)
+    }
+
+    #[rhai_fn(return_raw)]
+    pub fn clear_admin(cmd_args: Map) -> Result<Dynamic, Box<EvalAltResult>> {
+        reject_offline_args(&cmd_args)?;
+
+        let mut cmd = Map::new();
+        cmd.insert("ClearAdmin".into(), cmd_args.into());
+
+        wasm::proposal::entrypoint::propose(CONTEXT, &from_dynamic(&to_dynamic(cmd)?)?)
+            .map_err(|e| e.to_string().into())
+            .and_then(to_dynamic)
+    }
+
+    #[rhai_fn(return_raw)]
+    pub fn pin_codes(cmd_args: Map) -> Result<Dynamic, Box<EvalAltResult>> {
+        reject_offline_args(&cmd_args)?;
+
+        let mut cmd = Map::new();
+        cmd.insert("PinCodes".into(), cmd_args.into());
+
+        wasm::proposal::entrypoint::propose(CONTEXT, &from_dynamic(&to_dynamic(cmd)?)?)
+            .map_err(|e| e.to_string().into())
+            .and_then(to_dynamic)
+    }
+
+    #[rhai_fn(return_raw)]
+    pub fn unpin_codes(cmd_args: Map) -> Result<Dynamic, Box<EvalAltResult>> {
+        reject_offline_args(&cmd_args)?;
+
+        let mut cmd = Map::new();
+        cmd.insert("UnpinCodes".into(), cmd_args.into());
+
+        wasm::proposal::entrypoint::propose(CONTEXT, &from_dynamic(&to_dynamic(cmd)?)?)
+            .map_err(|e| e.to_string().into())
+            .and_then(to_dynamic)
+    }
+
     #[rhai_fn(return_raw)]
     pub fn vote(mut cmd_args: Map) -> Result<Dynamic, Box<EvalAltResult>> {
+        reject_offline_args(&cmd_args)?;
+
         if cmd_args.contains_key("msg") {
             let msg = from_dynamic::<Map>(&cmd_args["msg"])?;
             let msg_json = serde_json::to_string(&msg).map_err(|e| e.to_string())?;
@@ -46,6 +154,46 @@ pub(crate) mod commands {
             .and_then(to_dynamic)
     }
 
+    #[rhai_fn(return_raw)]
+    pub fn deposit(cmd_args: Map) -> Result<Dynamic, Box<EvalAltResult>> {
+        reject_offline_args(&cmd_args)?;
+
+        let mut cmd = Map::new();
+        cmd.insert("Deposit".into(), cmd_args.into());
+
+        wasm::proposal::entrypoint::deposit(CONTEXT, &from_dynamic(&to_dynamic(cmd)?)?)
+            .map_err(|e| e.to_string().into())
+            .and_then(to_dynamic)
+    }
+
+    #[rhai_fn(return_raw)]
+    pub fn wait(mut cmd_args: Map) -> Result<Dynamic, Box<EvalAltResult>> {
+        reject_offline_args(&cmd_args)?;
+
+        if cmd_args.contains_key("msg") {
+            let msg = from_dynamic::<Map>(&cmd_args["msg"])?;
+            let msg_json = serde_json::to_string(&msg).map_err(|e| e.to_string())?;
+            cmd_args.insert("raw".into(), msg_json.into());
+        }
+
+        let mut cmd = Map::new();
+        cmd.insert("Wait".into(), cmd_args.into());
+
+        wasm::proposal::entrypoint::wait(CONTEXT, &from_dynamic(&to_dynamic(cmd)?)?)
+            .map_err(|e| e.to_string().into())
+            .and_then(to_dynamic)
+    }
+
+    #[rhai_fn(return_raw)]
+    pub fn verify(cmd_args: Map) -> Result<Dynamic, Box<EvalAltResult>> {
+        let mut cmd = Map::new();
+        cmd.insert("Verify".into(), cmd_args.into());
+
+        wasm::proposal::entrypoint::verify(CONTEXT, &from_dynamic(&to_dynamic(cmd)?)?)
+            .map_err(|e| e.to_string().into())
+            .and_then(to_dynamic)
+    }
+
     #[rhai_fn(return_raw)]
     pub fn query(cmd_args: Map) -> Result<Dynamic, Box<EvalAltResult>> {
         let cmd = Map::from([(
@@ -0,0 +1,6 @@
+use data_doc_derive::GetDataDocs;
+use serde::Deserialize;
+use serde::Serialize;
+
+#[derive(Serialize, Deserialize, GetDataDocs, Default)]
+pub struct TxConfig {}
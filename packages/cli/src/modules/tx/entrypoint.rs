@@ -0,0 +1,73 @@
+use std::path::PathBuf;
+
+use anyhow::Result;
+use clap::Subcommand;
+
+use super::config::TxConfig;
+use super::ops;
+use crate::framework::{Context, Module};
+use crate::support::signer::SignerArgs;
+
+#[derive(Subcommand, Debug)]
+pub enum TxCmd {
+    /// Sign transaction generated by `--generate-only`, no network access is required
+    Sign {
+        /// Path to the unsigned transaction file
+        file: PathBuf,
+
+        /// Path to write the signed transaction to
+        #[clap(short, long)]
+        output: PathBuf,
+
+        #[clap(flatten)]
+        signer_args: SignerArgs,
+    },
+    /// Combine signatures of multisig members from `beaker tx sign` into a signed transaction
+    Multisign {
+        /// Path to the unsigned transaction file
+        file: PathBuf,
+
+        /// Paths to the members' signature files
+        #[clap(required = true)]
+        signatures: Vec<PathBuf>,
+
+        /// Path to write the signed transaction to
+        #[clap(short, long)]
+        output: PathBuf,
+    },
+    /// Broadcast transaction signed by `beaker tx sign` or `beaker tx multisign`
+    Broadcast {
+        /// Path to the signed transaction file
+        file: PathBuf,
+
+        /// Name of the network to broadcast transaction to, the actual endpoint / chain-id are defined in config
+        #[clap(short, long, default_value = "local")]
+        network: String,
+    },
+}
+
+pub struct TxModule {}
+
+impl<'a> Module<'a, TxConfig, TxCmd, anyhow::Error> for TxModule {
+    fn execute<Ctx: Context<'a, TxConfig>>(ctx: Ctx, cmd: &TxCmd) -> Result<(), anyhow::Error> {
+        match cmd {
+            TxCmd::Sign {
+                file,
+                output,
+                signer_args,
+            } => ops::sign(
+                &ctx,
+                file,
+                output,
+                signer_args.private_key(&ctx.global_config()?)?,
+            )
+            .map(|_| ()),
+            TxCmd::Multisign {
+                file,
+                signatures,
+                output,
+            } => ops::multisign(&ctx, file, signatures, output).map(|_| ()),
+            TxCmd::Broadcast { file, network } => ops::broadcast(&ctx, file, network).map(|_| ()),
+        }
+    }
+}
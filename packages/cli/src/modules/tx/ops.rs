@@ -0,0 +1,331 @@
+use std::path::{Path, PathBuf};
+
+use anyhow::{bail, Context as _, Result};
+use cosmos_sdk_proto::cosmwasm::wasm::v1::{
+    MsgExecuteContractResponse, MsgInstantiateContractResponse, MsgMigrateContractResponse,
+    MsgStoreCodeResponse,
+};
+use cosmos_sdk_proto::traits::Message;
+use cosmrs::crypto::secp256k1::SigningKey;
+use cosmrs::{AccountId, Any};
+use serde::{Deserialize, Serialize};
+
+use crate::attrs_format;
+use crate::framework::Context;
+use crate::support::cosmos::{Client, TxOptions};
+use crate::support::future::block;
+use crate::support::gas::Gas;
+use crate::support::multisig::Multisig;
+use crate::support::ops_response::OpResponseDisplay;
+use crate::support::tx::{MultisigSignature, SignedTx, UnsignedTx};
+use crate::vars_format;
+
+use super::config::TxConfig;
+
+#[allow(clippy::too_many_arguments)]
+pub fn generate_only<'a, Cfg, Ctx>(
+    ctx: &Ctx,
+    msgs: Vec<Any>,
+    signer: &AccountId,
+    network: &str,
+    gas: &Gas,
+    tx_options: &TxOptions,
+    account_sequence: &Option<u64>,
+    multisig: Option<Multisig>,
+    output: &Path,
+) -> Result<GenerateOnlyResponse>
+where
+    Cfg: Serialize + Deserialize<'a> + Default,
+    Ctx: Context<'a, Cfg>,
+{
+    let global_config = ctx.global_config()?;
+
+    let network_info = global_config
+        .networks()
+        .get(network)
+        .with_context(|| format!("Unable to find network config: {network}"))?
+        .to_owned();
+
+    let client = Client::new(network_info, &ctx.root()?, &global_config);
+
+    block(async {
+        let unsigned_tx = UnsignedTx {
+            multisig,
+            ..client
+                .generate_unsigned(signer, msgs, gas, tx_options, account_sequence)
+                .await?
+        };
+        unsigned_tx.write(output)?;
+
+        let generate_only_response = GenerateOnlyResponse {
+            file: output.to_string_lossy().to_string(),
+            signer: unsigned_tx.signer,
+            account_number: unsigned_tx.account_number,
+            sequence: unsigned_tx.sequence,
+        };
+
+        generate_only_response.log();
+
+        Ok(generate_only_response)
+    })
+}
+
+pub fn dry_run<'a, Cfg, Ctx>(
+    ctx: &Ctx,
+    msgs: Vec<Any>,
+    signer: &AccountId,
+    network: &str,
+    gas: &Gas,
+    tx_options: &TxOptions,
+    account_sequence: &Option<u64>,
+) -> Result<DryRunResponse>
+where
+    Cfg: Serialize + Deserialize<'a> + Default,
+    Ctx: Context<'a, Cfg>,
+{
+    let global_config = ctx.global_config()?;
+
+    let network_info = global_config
+        .networks()
+        .get(network)
+        .with_context(|| format!("Unable to find network config: {network}"))?
+        .to_owned();
+
+    let client = Client::new(network_info, &ctx.root()?, &global_config);
+
+    block(async {
+        let (simulation, fee) = client
+            .dry_run(signer, msgs, gas, tx_options, account_sequence)
+            .await?;
+
+        let dry_run_response = DryRunResponse {
+            gas_used: simulation.gas_info.gas_used,
+            gas_limit: fee.gas_limit,
+            fee: fee
+                .amount
+                .iter()
+                .map(|c| format!("{}{}", c.amount, c.denom))
+                .collect::<Vec<_>>()
+                .join(","),
+            events: simulation.events.iter().map(format_event).collect(),
+            msg_responses: simulation
+                .msg_responses
+                .iter()
+                .map(format_msg_response)
+                .collect(),
+        };
+
+        dry_run_response.log();
+
+        Ok(dry_run_response)
+    })
+}
+
+fn format_event(event: &cosmwasm_std::Event) -> String {
+    let attributes = event
+        .attributes
+        .iter()
+        .map(|a| format!("{}={}", a.key, a.value))
+        .collect::<Vec<_>>()
+        .join(", ");
+    format!("{}: {attributes}", event.ty)
+}
+
+/// Type of the msg response, along with its content for the wasm messages.
+fn format_msg_response(msg_response: &Any) -> String {
+    // legacy msg data is typed by the msg instead of its response
+    let type_url = msg_response.type_url.trim_end_matches("Response");
+    let value = &msg_response.value[..];
+    let data = |data: &[u8]| {
+        if data.is_empty() {
+            "-".to_string()
+        } else {
+            String::from_utf8_lossy(data).to_string()
+        }
+    };
+
+    let content = match type_url {
+        "/cosmwasm.wasm.v1.MsgStoreCode" => MsgStoreCodeResponse::decode(value)
+            .ok()
+            .map(|r| format!("code_id={}", r.code_id)),
+        "/cosmwasm.wasm.v1.MsgInstantiateContract" => MsgInstantiateContractResponse::decode(value)
+            .ok()
+            .map(|r| format!("address={}, data={}", r.address, data(&r.data))),
+        "/cosmwasm.wasm.v1.MsgExecuteContract" => MsgExecuteContractResponse::decode(value)
+            .ok()
+            .map(|r| format!("data={}", data(&r.data))),
+        "/cosmwasm.wasm.v1.MsgMigrateContract" => MsgMigrateContractResponse::decode(value)
+            .ok()
+            .map(|r| format!("data={}", data(&r.data))),
+        _ => None,
+    };
+
+    match content {
+        Some(content) => format!("{type_url}: {content}"),
+        None => type_url.to_string(),
+    }
+}
+
+pub fn sign<'a, Ctx: Context<'a, TxConfig>>(
+    ctx: &Ctx,
+    file: &Path,
+    output: &Path,
+    signing_key: SigningKey,
+) -> Result<SignResponse> {
+    let global_config = ctx.global_config()?;
+    let account_prefix = global_config.account_prefix().as_str();
+
+    let unsigned_tx = UnsignedTx::read(file)?;
+
+    // multisig members only produce their own signature, to be combined by `multisign`
+    let signer = if unsigned_tx.multisig.is_some() {
+        let signature = unsigned_tx.sign_multisig(&signing_key, account_prefix)?;
+        signature.write(output)?;
+        signature.signer
+    } else {
+        let signed_tx = unsigned_tx.sign(&signing_key, account_prefix)?;
+        signed_tx.write(output)?;
+        signed_tx.signer
+    };
+
+    let sign_response = SignResponse {
+        file: output.to_string_lossy().to_string(),
+        signer,
+    };
+
+    sign_response.log();
+
+    Ok(sign_response)
+}
+
+pub fn multisign<'a, Ctx: Context<'a, TxConfig>>(
+    _ctx: &Ctx,
+    file: &Path,
+    signatures: &[PathBuf],
+    output: &Path,
+) -> Result<SignResponse> {
+    let signatures = signatures
+        .iter()
+        .map(|p| MultisigSignature::read(p))
+        .collect::<Result<Vec<_>>>()?;
+
+    let signed_tx = UnsignedTx::read(file)?.combine(&signatures)?;
+    signed_tx.write(output)?;
+
+    let sign_response = SignResponse {
+        file: output.to_string_lossy().to_string(),
+        signer: signed_tx.signer,
+    };
+
+    sign_response.log();
+
+    Ok(sign_response)
+}
+
+pub fn broadcast<'a, Ctx: Context<'a, TxConfig>>(
+    ctx: &Ctx,
+    file: &Path,
+    network: &str,
+) -> Result<BroadcastResponse> {
+    let global_config = ctx.global_config()?;
+
+    let network_info = global_config
+        .networks()
+        .get(network)
+        .with_context(|| format!("Unable to find network config: {network}"))?
+        .to_owned();
+
+    let signed_tx = SignedTx::read(file)?;
+    if &signed_tx.chain_id != network_info.chain_id() {
+        bail!(
+            "Transaction is signed for chain `{}` but network `{network}` is `{}`",
+            signed_tx.chain_id,
+            network_info.chain_id()
+        );
+    }
+
+    let client = Client::new(network_info, &ctx.root()?, &global_config);
+
+    block(async {
+        let response = client.broadcast(signed_tx.to_raw()?).await?;
+
+        let broadcast_response = BroadcastResponse {
+            tx_hash: response.hash.to_string(),
+            height: response.height.value(),
+        };
+
+        broadcast_response.log();
+
+        Ok(broadcast_response)
+    })
+}
+
+#[derive(Serialize)]
+pub struct GenerateOnlyResponse {
+    pub file: String,
+    pub signer: String,
+    pub account_number: u64,
+    pub sequence: u64,
+}
+
+impl OpResponseDisplay for GenerateOnlyResponse {
+    fn headline() -> &'static str {
+        "Unsigned transaction has been generated!! 🎉"
+    }
+    fn attrs(&self) -> Vec<String> {
+        attrs_format! { self | file, signer, account_number, sequence }
+    }
+}
+
+#[derive(Serialize)]
+pub struct DryRunResponse {
+    pub gas_used: u64,
+    pub gas_limit: u64,
+    pub fee: String,
+    pub events: Vec<String>,
+    pub msg_responses: Vec<String>,
+}
+
+impl OpResponseDisplay for DryRunResponse {
+    fn headline() -> &'static str {
+        "Transaction simulated successfully, nothing has been broadcasted!! 🎉"
+    }
+    fn attrs(&self) -> Vec<String> {
+        let events = self.events.join("\n");
+        let msg_responses = self.msg_responses.join("\n");
+        let gas_used = self.gas_used;
+        let gas_limit = self.gas_limit;
+        let fee = &self.fee;
+        vars_format!(events, msg_responses, gas_used, gas_limit, fee)
+    }
+}
+
+#[derive(Serialize)]
+pub struct SignResponse {
+    pub file: String,
+    pub signer: String,
+}
+
+impl OpResponseDisplay for SignResponse {
+    fn headline() -> &'static str {
+        "Transaction signed successfully!! 🎉"
+    }
+    fn attrs(&self) -> Vec<String> {
+        attrs_format! { self | file, signer }
+    }
+}
+
+#[derive(Serialize)]
+pub struct BroadcastResponse {
+    pub tx_hash: String,
+    pub height: u64,
+}
+
+impl OpResponseDisplay for BroadcastResponse {
+    fn headline() -> &'static str {
+        "Transaction broadcasted successfully!! 🎉"
+    }
+    fn attrs(&self) -> Vec<String> {
+        attrs_format! { self | tx_hash, height }
+    }
+}
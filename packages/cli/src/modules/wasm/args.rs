@@ -1,7 +1,13 @@
+use std::path::PathBuf;
+
+use anyhow::{anyhow, bail, Context, Result};
 use clap::Parser;
+use cosmrs::AccountId;
 use serde::Deserialize;
 
-use crate::support::{gas::GasArgs, signer::SignerArgs};
+use crate::support::{
+    cosmos::TxOptions, gas::GasArgs, signer::SignerArgs, storage_key::decode_hex,
+};
 
 #[derive(Debug, Parser, Clone, Deserialize)]
 pub struct BaseTxArgs {
@@ -23,10 +29,95 @@ pub struct BaseTxArgs {
     #[serde(default = "default_value::timeout_height")]
     pub timeout_height: u32,
 
+    /// Number of blocks after the latest one past which the tx can no longer be committed
+    #[clap(long, conflicts_with = "timeout_height")]
+    pub timeout_blocks: Option<u32>,
+
+    /// Memo attached to the transaction
+    #[clap(long)]
+    pub memo: Option<String>,
+
+    /// Address whose fee grant to the signer pays the transaction fee
+    #[clap(long)]
+    pub fee_granter: Option<String>,
+
+    /// Address paying the transaction fee instead of the signer, it must sign the transaction as well
+    #[clap(long)]
+    pub fee_payer: Option<String>,
+
     /// Account sequence number to use for the transaction, if not provided, sequence will be fetched from the chain.
     /// This is useful if there is an account sequence mismatch.
     #[clap(short, long)]
     pub account_sequence: Option<u64>,
+
+    /// Write the unsigned transaction to the given file instead of signing and broadcasting it.
+    /// The file can then be signed with `beaker tx sign` and submitted with `beaker tx broadcast`.
+    #[clap(long)]
+    pub generate_only: Option<PathBuf>,
+
+    /// Simulate the transaction and print its gas, fee, events and responses without broadcasting it.
+    /// Beaker's state is left untouched.
+    #[clap(long, conflicts_with = "generate_only")]
+    #[serde(default = "default_value::dry_run")]
+    pub dry_run: bool,
+}
+
+impl BaseTxArgs {
+    pub fn tx_options(&self) -> Result<TxOptions> {
+        let parse_address = |address: &Option<String>| {
+            address
+                .as_ref()
+                .map(|a| {
+                    a.parse::<AccountId>()
+                        .map_err(|e| anyhow!(e))
+                        .with_context(|| format!("Invalid address: {a}"))
+                })
+                .transpose()
+        };
+
+        Ok(TxOptions {
+            memo: self.memo.clone().unwrap_or_default(),
+            timeout_height: self.timeout_height,
+            timeout_blocks: self.timeout_blocks,
+            fee_granter: parse_address(&self.fee_granter)?,
+            fee_payer: parse_address(&self.fee_payer)?,
+        })
+    }
+}
+
+#[derive(Debug, Parser, Clone, Deserialize, Default)]
+pub struct Instantiate2Args {
+    /// Salt for instantiating the contract at a predictable address with `MsgInstantiateContract2`.
+    /// Taken as utf-8, unless prefixed by `hex:` or `base64:`
+    #[clap(long)]
+    pub salt: Option<String>,
+
+    /// Include the instantiate msg in the derivation of the predictable address
+    #[clap(long, requires = "salt")]
+    #[serde(default)]
+    pub fix_msg: bool,
+}
+
+impl Instantiate2Args {
+    pub fn salt(&self) -> Result<Option<Vec<u8>>> {
+        self.salt.as_deref().map(parse_salt).transpose()
+    }
+}
+
+/// Salt bytes, which wasmd accepts from 1 to 64 of.
+pub fn parse_salt(salt: &str) -> Result<Vec<u8>> {
+    let bytes = if let Some(hex) = salt.strip_prefix("hex:") {
+        decode_hex(hex)?
+    } else if let Some(b64) = salt.strip_prefix("base64:") {
+        base64::decode(b64)?
+    } else {
+        salt.as_bytes().to_vec()
+    };
+
+    if bytes.is_empty() || bytes.len() > 64 {
+        bail!("Salt must be 1 to 64 bytes long, got {}", bytes.len());
+    }
+    Ok(bytes)
 }
 
 mod default_value {
@@ -38,4 +129,22 @@ mod default_value {
     pub(crate) fn timeout_height() -> u32 {
         0
     }
+
+    pub(crate) fn dry_run() -> bool {
+        false
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_salt_encodings() {
+        assert_eq!(parse_salt("beaker").unwrap(), b"beaker".to_vec());
+        assert_eq!(parse_salt("hex:00ff").unwrap(), vec![0, 0xff]);
+        assert_eq!(parse_salt("base64:AP8=").unwrap(), vec![0, 0xff]);
+        assert!(parse_salt("").is_err());
+        assert!(parse_salt(&"a".repeat(65)).is_err());
+    }
 }
@@ -0,0 +1,120 @@
+use anyhow::{Context as _, Result};
+use clap::Subcommand;
+use serde::Deserialize;
+
+use crate::{
+    framework::Context,
+    modules::wasm::WasmConfig,
+    support::storage_key::{decode_hex, KeyPart},
+};
+
+#[derive(Subcommand, Debug, Deserialize)]
+pub enum ContractStateCmd {
+    /// Dump all keys stored by the contract with their values
+    Dump {
+        /// Name of the contract to dump the state of
+        contract_name: String,
+
+        #[clap(short, long, default_value = "default")]
+        #[serde(default = "default_value::label")]
+        label: String,
+
+        /// Only dump the keys under this namespace
+        #[clap(long)]
+        namespace: Option<String>,
+
+        /// Name of the network to query, the actual endpoint / chain-id are defined in config
+        #[clap(short, long, default_value = "local")]
+        #[serde(default = "default_value::network")]
+        network: String,
+    },
+    /// Get the value of an `Item`, or of a `Map` entry by its typed key
+    Get {
+        /// Name of the contract to read the state of
+        contract_name: String,
+
+        /// Namespace of the `Item` or `Map`
+        namespace: String,
+
+        /// Map key parts as `<type>:<value>`, eg. `addr:osmo1...` or `u64:1`.
+        /// Types are string, addr, u8 - u128, i8 - i128, hex and base64, untyped values are strings
+        #[clap(short, long = "key")]
+        #[serde(default)]
+        keys: Vec<String>,
+
+        #[clap(short, long, default_value = "default")]
+        #[serde(default = "default_value::label")]
+        label: String,
+
+        /// Name of the network to query, the actual endpoint / chain-id are defined in config
+        #[clap(short, long, default_value = "local")]
+        #[serde(default = "default_value::network")]
+        network: String,
+    },
+    /// Encode the storage key of an `Item`, or of a `Map` entry by its typed key
+    EncodeKey {
+        /// Namespace of the `Item` or `Map`
+        namespace: String,
+
+        /// Map key parts as `<type>:<value>`, eg. `addr:osmo1...` or `u64:1`
+        #[clap(short, long = "key")]
+        #[serde(default)]
+        keys: Vec<String>,
+    },
+    /// Decode a hex storage key into its namespace and key parts
+    DecodeKey {
+        /// Storage key in hex
+        key: String,
+    },
+}
+
+mod default_value {
+    pub fn network() -> String {
+        "local".to_string()
+    }
+    pub fn label() -> String {
+        "default".to_string()
+    }
+}
+
+pub fn execute<'a, Ctx: Context<'a, WasmConfig>>(
+    ctx: Ctx,
+    cmd: &ContractStateCmd,
+) -> Result<(), anyhow::Error> {
+    match cmd {
+        ContractStateCmd::Dump {
+            contract_name,
+            label,
+            namespace,
+            network,
+        } => {
+            super::ops::dump(&ctx, contract_name, label, namespace.as_deref(), network).map(|_| ())
+        }
+        ContractStateCmd::Get {
+            contract_name,
+            namespace,
+            keys,
+            label,
+            network,
+        } => super::ops::get(
+            &ctx,
+            contract_name,
+            label,
+            namespace,
+            &key_parts(keys)?,
+            network,
+        )
+        .map(|_| ()),
+        ContractStateCmd::EncodeKey { namespace, keys } => {
+            super::ops::encode(namespace, &key_parts(keys)?).map(|_| ())
+        }
+        ContractStateCmd::DecodeKey { key } => {
+            super::ops::decode(&decode_hex(key).with_context(|| "Storage key must be hex encoded")?)
+                .map(|_| ())
+        }
+    }
+}
+
+fn key_parts(keys: &[String]) -> Result<Vec<KeyPart>> {
+    keys.iter().map(|k| k.parse()).collect()
+}
@@ -0,0 +1,234 @@
+use crate::attrs_format;
+use crate::modules::wasm::config::WasmConfig;
+use crate::support::future::block;
+use crate::support::ops_response::OpResponseDisplay;
+use crate::support::state::State;
+use crate::support::storage_key::{decode_key, display_key_part, encode_hex, encode_key, KeyPart};
+use crate::{framework::Context, support::cosmos::Client};
+use anyhow::{anyhow, Context as _, Result};
+use cosmrs::AccountId;
+use serde::Serialize;
+use serde_json::{json, Value};
+
+/// Dump every key stored by the contract, optionally only those under `namespace`.
+pub fn dump<'a, Ctx: Context<'a, WasmConfig>>(
+    ctx: &Ctx,
+    contract_name: &str,
+    label: &str,
+    namespace: Option<&str>,
+    network: &str,
+) -> Result<DumpStateResponse> {
+    let (client, contract) = contract_client(ctx, contract_name, label, network)?;
+
+    block(async {
+        let entries = client
+            .all_contract_state(contract.to_string())
+            .await?
+            .into_iter()
+            .map(|(key, value)| state_entry(&key, &value))
+            .filter(|entry| namespace.is_none_or(|ns| entry["namespace"] == ns))
+            .collect::<Vec<_>>();
+
+        let dump_state_response = DumpStateResponse {
+            label: label.to_string(),
+            contract_address: contract.to_string(),
+            count: entries.len(),
+            entries: pretty_json(&Value::Array(entries))?,
+        };
+        dump_state_response.log();
+
+        Ok(dump_state_response)
+    })
+}
+
+/// Look up the value of an `Item` under `namespace`, or of a `Map` entry by its typed `key`.
+pub fn get<'a, Ctx: Context<'a, WasmConfig>>(
+    ctx: &Ctx,
+    contract_name: &str,
+    label: &str,
+    namespace: &str,
+    key: &[KeyPart],
+    network: &str,
+) -> Result<GetStateResponse> {
+    let (client, contract) = contract_client(ctx, contract_name, label, network)?;
+    let raw_key = encode_key(namespace, key)?;
+
+    block(async {
+        let value = client
+            .query_raw(contract.to_string(), raw_key.clone())
+            .await?
+            .with_context(|| format!("Key `{}` is not set", encode_hex(&raw_key)))?;
+
+        let get_state_response = GetStateResponse {
+            label: label.to_string(),
+            contract_address: contract.to_string(),
+            key: encode_hex(&raw_key),
+            value: pretty_json(&value_json(&value))?,
+        };
+        get_state_response.log();
+
+        Ok(get_state_response)
+    })
+}
+
+pub fn encode(namespace: &str, key: &[KeyPart]) -> Result<EncodeKeyResponse> {
+    let raw_key = encode_key(namespace, key)?;
+
+    let encode_key_response = EncodeKeyResponse {
+        hex: encode_hex(&raw_key),
+        base64: base64::encode(&raw_key),
+    };
+    encode_key_response.log();
+
+    Ok(encode_key_response)
+}
+
+pub fn decode(raw_key: &[u8]) -> Result<DecodeKeyResponse> {
+    let (namespace, key) = decode_key(raw_key);
+
+    let decode_key_response = DecodeKeyResponse {
+        namespace,
+        key: key.iter().map(|part| display_key_part(part)).collect(),
+    };
+    decode_key_response.log();
+
+    Ok(decode_key_response)
+}
+
+fn contract_client<'a, Ctx: Context<'a, WasmConfig>>(
+    ctx: &Ctx,
+    contract_name: &str,
+    label: &str,
+    network: &str,
+) -> Result<(Client, AccountId)> {
+    let global_config = ctx.global_config()?;
+    let network_info = global_config
+        .networks()
+        .get(network)
+        .with_context(|| format!("Unable to find network config: {network}"))?
+        .to_owned();
+
+    let client = Client::new(network_info.clone(), &ctx.root()?, &global_config);
+    let state = State::load_by_network(network_info, ctx.root()?)?;
+
+    let contract = state
+        .get_ref(network, contract_name)?
+        .addresses()
+        .get(label)
+        .with_context(|| format!("Unable to retrieve contract for {contract_name}:{label}"))?
+        .parse::<AccountId>()
+        .map_err(|e| anyhow!(e))?;
+
+    Ok((client, contract))
+}
+
+fn state_entry(key: &[u8], value: &[u8]) -> Value {
+    let (namespace, key_parts) = decode_key(key);
+    json!({
+        "key": encode_hex(key),
+        "namespace": namespace,
+        "key_parts": key_parts.iter().map(|part| display_key_part(part)).collect::<Vec<_>>(),
+        "value": value_json(value),
+    })
+}
+
+/// Value as json when it is, as cw-storage-plus stores it, otherwise base64 encoded.
+fn value_json(value: &[u8]) -> Value {
+    serde_json::from_slice(value).unwrap_or_else(|_| Value::String(base64::encode(value)))
+}
+
+fn pretty_json(value: &Value) -> Result<String> {
+    Ok(format!(
+        "\n{}",
+        textwrap::indent(&serde_json::to_string_pretty(value)?, "        ")
+    ))
+}
+
+#[derive(Serialize)]
+pub struct DumpStateResponse {
+    pub label: String,
+    pub contract_address: String,
+    pub count: usize,
+    pub entries: String,
+}
+
+impl OpResponseDisplay for DumpStateResponse {
+    fn headline() -> &'static str {
+        "Successfully dumped contract state!! 🎉"
+    }
+    fn attrs(&self) -> Vec<String> {
+        attrs_format! { self | label, contract_address, count, entries }
+    }
+}
+
+#[derive(Serialize)]
+pub struct GetStateResponse {
+    pub label: String,
+    pub contract_address: String,
+    pub key: String,
+    pub value: String,
+}
+
+impl OpResponseDisplay for GetStateResponse {
+    fn headline() -> &'static str {
+        "Successfully read contract state!! 🎉"
+    }
+    fn attrs(&self) -> Vec<String> {
+        attrs_format! { self | label, contract_address, key, value }
+    }
+}
+
+#[derive(Serialize)]
+pub struct EncodeKeyResponse {
+    pub hex: String,
+    pub base64: String,
+}
+
+impl OpResponseDisplay for EncodeKeyResponse {
+    fn headline() -> &'static str {
+        "Successfully encoded storage key!! 🎉"
+    }
+    fn attrs(&self) -> Vec<String> {
+        attrs_format! { self | hex, base64 }
+    }
+}
+
+#[derive(Serialize)]
+pub struct DecodeKeyResponse {
+    pub namespace: String,
+    pub key: Vec<String>,
+}
+
+impl OpResponseDisplay for DecodeKeyResponse {
+    fn headline() -> &'static str {
+        "Successfully decoded storage key!! 🎉"
+    }
+    fn attrs(&self) -> Vec<String> {
+        let key = self.key.join(", ");
+        vec![
+            format!("    ├── namespace: {}", self.namespace),
+            format!("    └── key: [{key}]"),
+        ]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn state_entry_of_map_key() {
+        let key = encode_key("balance", &["addr:osmo1abc".parse().unwrap()]).unwrap();
+
+        assert_eq!(
+            state_entry(&key, b"\"1000\""),
+            json!({
+                "key": encode_hex(&key),
+                "namespace": "balance",
+                "key_parts": ["osmo1abc"],
+                "value": "1000",
+            })
+        );
+        assert_eq!(value_json(&[0xff]), json!("/w=="));
+    }
+}
@@ -1,6 +1,8 @@
-use anyhow::{bail, Result};
+use anyhow::{anyhow, bail, Context as _, Result};
 use clap::Subcommand;
 use console::style;
+use cosmrs::tx::{MessageExt, Msg};
+use cosmrs::{AccountId, Any};
 use derive_new::new;
 use serde::Deserialize;
 use std::env;
@@ -9,18 +11,33 @@ use std::path::PathBuf;
 use std::process::Command;
 use std::str::FromStr;
 
+use crate::framework::config::GlobalConfig;
 use crate::framework::{Context, Module};
+use crate::modules::tx;
+use crate::modules::tx::ops::{DryRunResponse, GenerateOnlyResponse};
+use crate::support::authz;
 use crate::support::command::run_command;
 use crate::support::gas::Gas;
+use crate::support::multisig::{Multisig, MultisigArgs};
+use crate::support::signer::SignerArgs;
 
+use super::args::{BaseTxArgs, Instantiate2Args};
+use super::ops::batch::{BatchOp, BatchResponse};
 use super::ops::clear_admin::ClearAdminResponse;
+use super::ops::code_info::CodeInfoResponse;
 use super::ops::execute::ExecuteResponse;
+use super::ops::info::InfoResponse;
 use super::ops::instantiate::InstantiateResponse;
 use super::ops::migrate::MigrateResponse;
+use super::ops::predict_address::PredictAddressResponse;
 use super::ops::query::QueryResponse;
+use super::ops::rollback::RollbackResponse;
 use super::ops::store_code::StoreCodeResponse;
 use super::ops::update_admin::UpdateAdminResponse;
-use super::{args::BaseTxArgs, config::WasmConfig, proposal::entrypoint::ProposalCmd};
+use super::ops::update_instantiate_config::UpdateInstantiateConfigResponse;
+use super::ops::verify::VerifyResponse;
+use super::{config::WasmConfig, proposal::entrypoint::ProposalCmd};
+use super::{contract_state, contract_state::entrypoint::ContractStateCmd};
 use super::{ops, proposal};
 
 #[derive(clap::ValueEnum, Clone, Debug, Deserialize)]
@@ -94,10 +111,16 @@ pub enum WasmCmd {
         #[serde(default = "default_value::no_wasm_opt")]
         no_wasm_opt: bool,
 
-        /// Restricting the code to be able to instantiate only by given address, no restriction by default
+        /// Restricting the code to be able to instantiate only by given addresses, no restriction by default.
+        /// Takes `nobody` or a comma separated list of `signer`, predefined account names and addresses
         #[clap(long)]
         permit_instantiate_only: Option<String>,
 
+        /// Store the code even if a code id recorded in state for the contract already holds the same wasm
+        #[clap(long)]
+        #[serde(default)]
+        force_store: bool,
+
         #[clap(flatten)]
         #[serde(flatten)]
         base_tx_args: BaseTxArgs,
@@ -133,6 +156,14 @@ pub enum WasmCmd {
         #[clap(long)]
         new_admin: String,
 
+        /// Address to send the message on behalf of, through its authz grant to the signer
+        #[clap(long)]
+        granter: Option<String>,
+
+        #[clap(flatten)]
+        #[serde(flatten)]
+        multisig_args: MultisigArgs,
+
         #[clap(flatten)]
         #[serde(flatten)]
         base_tx_args: BaseTxArgs,
@@ -147,6 +178,32 @@ pub enum WasmCmd {
         #[serde(default = "default_value::label")]
         label: String,
 
+        #[clap(flatten)]
+        #[serde(flatten)]
+        multisig_args: MultisigArgs,
+
+        #[clap(flatten)]
+        #[serde(flatten)]
+        base_tx_args: BaseTxArgs,
+    },
+    /// Update the addresses permitted to instantiate a stored code
+    UpdateInstantiateConfig {
+        /// Name of the contract whose code to update
+        contract_name: String,
+
+        /// Code id to update, the contract's stored code id by default
+        #[clap(long)]
+        code_id: Option<u64>,
+
+        /// Restricting the code to be able to instantiate only by given addresses, no restriction by default.
+        /// Takes `nobody` or a comma separated list of `signer`, predefined account names and addresses
+        #[clap(long)]
+        permit_instantiate_only: Option<String>,
+
+        #[clap(flatten)]
+        #[serde(flatten)]
+        multisig_args: MultisigArgs,
+
         #[clap(flatten)]
         #[serde(flatten)]
         base_tx_args: BaseTxArgs,
@@ -185,10 +242,58 @@ pub enum WasmCmd {
         #[serde(default = "default_value::yes")]
         yes: bool,
 
+        /// Address to send the message on behalf of, through its authz grant to the signer
+        #[clap(long)]
+        granter: Option<String>,
+
+        #[clap(flatten)]
+        #[serde(flatten)]
+        instantiate2_args: Instantiate2Args,
+
         #[clap(flatten)]
         #[serde(flatten)]
         base_tx_args: BaseTxArgs,
     },
+    /// Predict the address of the contract instantiated with `--salt`, without querying the chain
+    PredictAddress {
+        /// Name of the contract to predict the address of
+        contract_name: String,
+
+        /// Label to record the predicted address under
+        #[clap(short, long, default_value = "default")]
+        #[serde(default = "default_value::label")]
+        label: String,
+
+        /// Salt the contract is instantiated with, taken as utf-8 unless prefixed by `hex:` or `base64:`
+        #[clap(long)]
+        salt: String,
+
+        /// Include the instantiate msg in the derivation of the address
+        #[clap(long)]
+        #[serde(default)]
+        fix_msg: bool,
+
+        /// Raw json string to use as instantiate msg with `--fix-msg`
+        #[clap(short, long)]
+        raw: Option<String>,
+
+        /// Hex checksum of the code to instantiate, the local artifact's by default
+        #[clap(long)]
+        checksum: Option<String>,
+
+        /// Address instantiating the contract, the signer by default
+        #[clap(long)]
+        creator: Option<String>,
+
+        /// Name of the network to record the predicted address for
+        #[clap(short, long, default_value = "local")]
+        #[serde(default = "default_value::network")]
+        network: String,
+
+        #[clap(flatten)]
+        #[serde(flatten)]
+        signer_args: SignerArgs,
+    },
     /// Migrated instanitate contract to use other code stored on chain
     Migrate {
         /// Name of the contract to instantiate
@@ -212,6 +317,45 @@ pub enum WasmCmd {
         #[serde(default = "default_value::yes")]
         yes: bool,
 
+        /// Migrate even if, per cw2, the contract would become a different contract or be downgraded
+        #[clap(long)]
+        #[serde(default)]
+        force: bool,
+
+        /// Address to send the message on behalf of, through its authz grant to the signer
+        #[clap(long)]
+        granter: Option<String>,
+
+        #[clap(flatten)]
+        #[serde(flatten)]
+        multisig_args: MultisigArgs,
+
+        #[clap(flatten)]
+        #[serde(flatten)]
+        base_tx_args: BaseTxArgs,
+    },
+    /// Migrate contract back to the code id it ran before its current one
+    Rollback {
+        /// Name of the contract to roll back
+        contract_name: String,
+
+        /// Label of the contract to roll back
+        #[clap(short, long, default_value = "default")]
+        #[serde(default = "default_value::label")]
+        label: String,
+
+        /// Raw json string to use as migrate msg, the msg of the migration to the previous code id by default
+        #[clap(short, long)]
+        raw: Option<String>,
+
+        /// Address to send the message on behalf of, through its authz grant to the signer
+        #[clap(long)]
+        granter: Option<String>,
+
+        #[clap(flatten)]
+        #[serde(flatten)]
+        multisig_args: MultisigArgs,
+
         #[clap(flatten)]
         #[serde(flatten)]
         base_tx_args: BaseTxArgs,
@@ -230,7 +374,8 @@ pub enum WasmCmd {
         #[clap(short, long)]
         raw: Option<String>,
 
-        /// Restricting the code to be able to instantiate only by given address, no restriction by default
+        /// Restricting the code to be able to instantiate only by given addresses, no restriction by default.
+        /// Takes `nobody` or a comma separated list of `signer`, predefined account names and addresses
         #[clap(long)]
         permit_instantiate_only: Option<String>,
 
@@ -254,6 +399,15 @@ pub enum WasmCmd {
         #[serde(default = "default_value::no_wasm_opt")]
         no_wasm_opt: bool,
 
+        /// Store the code even if a code id recorded in state for the contract already holds the same wasm
+        #[clap(long)]
+        #[serde(default)]
+        force_store: bool,
+
+        #[clap(flatten)]
+        #[serde(flatten)]
+        instantiate2_args: Instantiate2Args,
+
         #[clap(flatten)]
         #[serde(flatten)]
         base_tx_args: BaseTxArgs,
@@ -282,10 +436,21 @@ pub enum WasmCmd {
         #[serde(default = "default_value::no_wasm_opt")]
         no_wasm_opt: bool,
 
-        /// Restricting the code to be able to instantiate only by given address, no restriction by default
+        /// Restricting the code to be able to instantiate only by given addresses, no restriction by default.
+        /// Takes `nobody` or a comma separated list of `signer`, predefined account names and addresses
         #[clap(long)]
         permit_instantiate_only: Option<String>,
 
+        /// Store the code even if a code id recorded in state for the contract already holds the same wasm
+        #[clap(long)]
+        #[serde(default)]
+        force_store: bool,
+
+        /// Migrate even if, per cw2, the contract would become a different contract or be downgraded
+        #[clap(long)]
+        #[serde(default)]
+        force: bool,
+
         #[clap(flatten)]
         #[serde(flatten)]
         base_tx_args: BaseTxArgs,
@@ -308,10 +473,71 @@ pub enum WasmCmd {
         #[clap(short, long)]
         funds: Option<String>,
 
+        /// Address to send the message on behalf of, through its authz grant to the signer
+        #[clap(long)]
+        granter: Option<String>,
+
+        #[clap(flatten)]
+        #[serde(flatten)]
+        base_tx_args: BaseTxArgs,
+    },
+    /// Execute, instantiate, migrate and update admin of contracts atomically in a single transaction
+    Batch {
+        /// Path to the json file with the list of operations,
+        /// eg. `[{ "execute": { "contract_name": "counter", "msg": { "increment": {} } } }]`.
+        /// A contract instantiated in the batch can not be operated on later in the same batch
+        file: PathBuf,
+
+        /// Skip the check for proposal's updated code_id
+        #[clap(long)]
+        #[serde(default = "default_value::no_proposal_sync")]
+        no_proposal_sync: bool,
+
+        /// Agree to all prompts
+        #[clap(short, long)]
+        #[serde(default = "default_value::yes")]
+        yes: bool,
+
         #[clap(flatten)]
         #[serde(flatten)]
         base_tx_args: BaseTxArgs,
     },
+    /// Read raw contract state and encode / decode its storage keys
+    State {
+        #[clap(subcommand)]
+        cmd: ContractStateCmd,
+    },
+    /// Show contract info and its code history
+    Info {
+        contract_name: String,
+
+        #[clap(short, long, default_value = "default")]
+        #[serde(default = "default_value::label")]
+        label: String,
+
+        /// Name of the network to query, the actual endpoint / chain-id are defined in config
+        #[clap(short, long, default_value = "local")]
+        #[serde(default = "default_value::network")]
+        network: String,
+    },
+    /// Show checksum, creator and instantiate permission of the contract's stored code
+    CodeInfo {
+        contract_name: String,
+
+        /// Name of the network to query, the actual endpoint / chain-id are defined in config
+        #[clap(short, long, default_value = "local")]
+        #[serde(default = "default_value::network")]
+        network: String,
+    },
+    /// Compare the code deployed on each network with the local artifact and `checksums.txt`
+    Verify {
+        contract_name: String,
+
+        /// Networks to verify, all networks with the contract in their state by default
+        #[clap(short, long)]
+        #[serde(default)]
+        network: Vec<String>,
+    },
     /// Query contract state
     Query {
         contract_name: String,
@@ -329,6 +555,84 @@ pub enum WasmCmd {
     },
 }
 
+impl WasmCmd {
+    /// Common tx args of the commands that produce a transaction
+    fn tx_args(&self) -> Option<&BaseTxArgs> {
+        match self {
+            WasmCmd::StoreCode { base_tx_args, .. }
+            | WasmCmd::UpdateAdmin { base_tx_args, .. }
+            | WasmCmd::ClearAdmin { base_tx_args, .. }
+            | WasmCmd::UpdateInstantiateConfig { base_tx_args, .. }
+            | WasmCmd::Instantiate { base_tx_args, .. }
+            | WasmCmd::Migrate { base_tx_args, .. }
+            | WasmCmd::Rollback { base_tx_args, .. }
+            | WasmCmd::Deploy { base_tx_args, .. }
+            | WasmCmd::Upgrade { base_tx_args, .. }
+            | WasmCmd::Execute { base_tx_args, .. }
+            | WasmCmd::Batch { base_tx_args, .. } => Some(base_tx_args),
+            _ => None,
+        }
+    }
+
+    /// Multisig args of the commands that can be sent from a multisig account
+    fn multisig_args(&self) -> Option<&MultisigArgs> {
+        match self {
+            WasmCmd::UpdateAdmin { multisig_args, .. }
+            | WasmCmd::ClearAdmin { multisig_args, .. }
+            | WasmCmd::UpdateInstantiateConfig { multisig_args, .. }
+            | WasmCmd::Migrate { multisig_args, .. }
+            | WasmCmd::Rollback { multisig_args, .. } => Some(multisig_args),
+            _ => None,
+        }
+    }
+
+    /// Sender of the command's tx, the multisig account if it is sent from one
+    fn tx_sender(&self, global_config: &GlobalConfig) -> Result<(AccountId, Option<Multisig>)> {
+        let multisig = self
+            .multisig_args()
+            .map(MultisigArgs::multisig)
+            .transpose()?
+            .flatten();
+        let sender = match (&multisig, self.tx_args()) {
+            (Some(multisig), _) => multisig.account_id(global_config.account_prefix())?,
+            (None, Some(args)) => args.signer_args.account_id(global_config)?,
+            (None, None) => unimplemented!(),
+        };
+
+        Ok((sender, multisig))
+    }
+
+    /// Granter that the command's message is sent on behalf of, through `MsgExec` by the tx sender
+    fn granter(&self) -> Result<Option<AccountId>> {
+        let granter = match self {
+            WasmCmd::UpdateAdmin { granter, .. }
+            | WasmCmd::Instantiate { granter, .. }
+            | WasmCmd::Migrate { granter, .. }
+            | WasmCmd::Rollback { granter, .. }
+            | WasmCmd::Execute { granter, .. } => granter,
+            _ => &None,
+        };
+
+        granter
+            .as_ref()
+            .map(|g| {
+                g.parse::<AccountId>()
+                    .map_err(|e| anyhow!(e))
+                    .with_context(|| format!("Invalid granter address: {g}"))
+            })
+            .transpose()
+    }
+
+    fn is_generate_only(&self) -> bool {
+        self.tx_args()
+            .is_some_and(|args| args.generate_only.is_some())
+    }
+
+    fn is_dry_run(&self) -> bool {
+        self.tx_args().is_some_and(|args| args.dry_run)
+    }
+}
+
 mod default_value {
     use super::NodePackageManager;
 
@@ -336,6 +640,10 @@ mod default_value {
         "default".to_string()
     }
 
+    pub(crate) fn network() -> String {
+        "local".to_string()
+    }
+
     pub(crate) fn node_package_manager() -> NodePackageManager {
         NodePackageManager::Yarn
     }
@@ -367,6 +675,8 @@ pub struct WasmModule {}
 impl<'a> Module<'a, WasmConfig, WasmCmd, anyhow::Error> for WasmModule {
     fn execute<Ctx: Context<'a, WasmConfig>>(ctx: Ctx, cmd: &WasmCmd) -> Result<(), anyhow::Error> {
         match cmd {
+            cmd if cmd.is_generate_only() => generate_only(ctx, cmd).map(|_| ()),
+            cmd if cmd.is_dry_run() => dry_run(ctx, cmd).map(|_| ()),
             WasmCmd::New {
                 contract_name: name,
                 target_dir, // TODO: Rremove this
@@ -383,11 +693,17 @@ impl<'a> Module<'a, WasmConfig, WasmCmd, anyhow::Error> for WasmModule {
             cmd @ WasmCmd::StoreCode { .. } => store_code(ctx, cmd).map(|_| ()),
             cmd @ WasmCmd::UpdateAdmin { .. } => update_admin(ctx, cmd).map(|_| ()),
             cmd @ WasmCmd::ClearAdmin { .. } => clear_admin(ctx, cmd).map(|_| ()),
+            cmd @ WasmCmd::UpdateInstantiateConfig { .. } => {
+                update_instantiate_config(ctx, cmd).map(|_| ())
+            }
             cmd @ WasmCmd::Instantiate { .. } => instantiate(ctx, cmd).map(|_| ()),
+            cmd @ WasmCmd::PredictAddress { .. } => predict_address(ctx, cmd).map(|_| ()),
             cmd @ WasmCmd::Migrate { .. } => migrate(ctx, cmd).map(|_| ()),
+            cmd @ WasmCmd::Rollback { .. } => rollback(ctx, cmd).map(|_| ()),
             cmd @ WasmCmd::Deploy { .. } => deploy(ctx, cmd).map(|_| ()),
             cmd @ WasmCmd::Upgrade { .. } => upgrade(ctx, cmd).map(|_| ()),
             WasmCmd::Proposal { cmd } => proposal::entrypoint::execute(ctx, cmd),
+            WasmCmd::State { cmd } => contract_state::entrypoint::execute(ctx, cmd),
             WasmCmd::TsGen {
                 contract_name,
                 schema_gen_cmd,
@@ -434,11 +750,250 @@ impl<'a> Module<'a, WasmConfig, WasmCmd, anyhow::Error> for WasmModule {
                 Ok(())
             }
             cmd @ WasmCmd::Execute { .. } => execute(ctx, cmd).map(|_| ()),
+            cmd @ WasmCmd::Batch { .. } => batch(ctx, cmd).map(|_| ()),
+            cmd @ WasmCmd::Info { .. } => info(ctx, cmd).map(|_| ()),
+            cmd @ WasmCmd::CodeInfo { .. } => code_info(ctx, cmd).map(|_| ()),
+            cmd @ WasmCmd::Verify { .. } => verify(ctx, cmd).map(|_| ()),
             cmd @ WasmCmd::Query { .. } => query(ctx, cmd).map(|_| ()),
         }
     }
 }
 
+pub(crate) fn generate_only<'a>(
+    ctx: impl Context<'a, WasmConfig>,
+    cmd: &WasmCmd,
+) -> Result<GenerateOnlyResponse> {
+    let base_tx_args = cmd.tx_args().unwrap_or_else(|| unimplemented!());
+    let BaseTxArgs {
+        network,
+        gas_args,
+        account_sequence,
+        generate_only,
+        ..
+    }: &BaseTxArgs = base_tx_args;
+
+    let global_conf = ctx.global_config()?;
+    let (sender, multisig) = cmd.tx_sender(&global_conf)?;
+
+    tx::ops::generate_only(
+        &ctx,
+        cmd_msgs(&ctx, cmd, network, &sender)?,
+        &sender,
+        network,
+        &Gas::from_args(
+            gas_args,
+            global_conf.gas_price(),
+            global_conf.gas_adjustment(),
+        )?,
+        &base_tx_args.tx_options()?,
+        account_sequence,
+        multisig,
+        generate_only.as_ref().unwrap(),
+    )
+}
+
+pub(crate) fn dry_run<'a>(
+    ctx: impl Context<'a, WasmConfig>,
+    cmd: &WasmCmd,
+) -> Result<DryRunResponse> {
+    let base_tx_args = cmd.tx_args().unwrap_or_else(|| unimplemented!());
+    let BaseTxArgs {
+        network,
+        gas_args,
+        account_sequence,
+        ..
+    }: &BaseTxArgs = base_tx_args;
+
+    let global_conf = ctx.global_config()?;
+    let (sender, _) = cmd.tx_sender(&global_conf)?;
+
+    tx::ops::dry_run(
+        &ctx,
+        cmd_msgs(&ctx, cmd, network, &sender)?,
+        &sender,
+        network,
+        &Gas::from_args(
+            gas_args,
+            global_conf.gas_price(),
+            global_conf.gas_adjustment(),
+        )?,
+        &base_tx_args.tx_options()?,
+        account_sequence,
+    )
+}
+
+/// Messages of the command, for `--generate-only` and `--dry-run`, which leave the state untouched.
+fn cmd_msgs<'a, Ctx: Context<'a, WasmConfig>>(
+    ctx: &Ctx,
+    cmd: &WasmCmd,
+    network: &str,
+    sender: &AccountId,
+) -> Result<Vec<Any>> {
+    match cmd {
+        WasmCmd::Batch {
+            file,
+            no_proposal_sync,
+            yes,
+            ..
+        } => ops::batch::batch_msgs(
+            ctx,
+            &ops::batch::read_batch_file(file)?,
+            *no_proposal_sync,
+            *yes,
+            false,
+            network,
+            sender,
+        ),
+        cmd => {
+            let granter = cmd.granter()?;
+            let msg = cmd_msg(ctx, cmd, network, granter.as_ref().unwrap_or(sender))?;
+            Ok(vec![authz::exec_on_behalf(granter.as_ref(), sender, msg)?])
+        }
+    }
+}
+
+/// Single message of the command.
+fn cmd_msg<'a, Ctx: Context<'a, WasmConfig>>(
+    ctx: &Ctx,
+    cmd: &WasmCmd,
+    network: &str,
+    sender: &AccountId,
+) -> Result<Any> {
+    Ok(match cmd {
+        WasmCmd::StoreCode {
+            contract_name,
+            no_wasm_opt,
+            permit_instantiate_only,
+            ..
+        } => ops::store_code::store_code_msg(
+            ctx,
+            contract_name,
+            no_wasm_opt,
+            permit_instantiate_only,
+            sender,
+        )?
+        .to_any()
+        .map_err(|e| anyhow!(e))?,
+        WasmCmd::UpdateAdmin {
+            contract_name,
+            label,
+            new_admin,
+            ..
+        } => ops::update_admin::update_admin_msg(
+            ctx,
+            contract_name,
+            label,
+            network,
+            new_admin,
+            sender,
+        )?
+        .to_any()?,
+        WasmCmd::ClearAdmin {
+            contract_name,
+            label,
+            ..
+        } => ops::clear_admin::clear_admin_msg(ctx, contract_name, label, network, sender)?
+            .to_any()?,
+        WasmCmd::UpdateInstantiateConfig {
+            contract_name,
+            code_id,
+            permit_instantiate_only,
+            ..
+        } => ops::update_instantiate_config::update_instantiate_config_any(
+            &ops::update_instantiate_config::update_instantiate_config_msg(
+                ctx,
+                contract_name,
+                *code_id,
+                permit_instantiate_only,
+                network,
+                sender,
+            )?,
+        )?,
+        WasmCmd::Instantiate {
+            contract_name,
+            label,
+            raw,
+            admin,
+            funds,
+            no_proposal_sync,
+            yes,
+            instantiate2_args,
+            ..
+        } => ops::instantiate::instantiate_any(
+            ops::instantiate::instantiate_msg(
+                ctx,
+                contract_name,
+                label.as_str(),
+                raw.as_ref(),
+                admin.as_ref(),
+                *no_proposal_sync,
+                *yes,
+                false,
+                funds.as_ref().map(|s| s.as_str()).try_into()?,
+                network,
+                sender,
+            )?,
+            instantiate2_args,
+        )?,
+        WasmCmd::Migrate {
+            contract_name,
+            label,
+            raw,
+            no_proposal_sync,
+            yes,
+            force,
+            ..
+        } => ops::migrate::migrate_msg(
+            ctx,
+            contract_name,
+            label.as_str(),
+            raw.as_ref(),
+            *no_proposal_sync,
+            *yes,
+            false,
+            network,
+            sender,
+            *force,
+        )?
+        .to_any()?,
+        WasmCmd::Rollback {
+            contract_name,
+            label,
+            raw,
+            ..
+        } => ops::rollback::rollback_msg(
+            ctx,
+            contract_name,
+            label.as_str(),
+            raw.as_ref(),
+            network,
+            sender,
+        )?
+        .to_any()?,
+        WasmCmd::Execute {
+            contract_name,
+            label,
+            raw,
+            funds,
+            ..
+        } => ops::execute::execute_msg(
+            ctx,
+            contract_name,
+            label.as_str(),
+            raw.as_ref(),
+            funds.as_ref().map(|s| s.as_str()).try_into()?,
+            network,
+            sender,
+        )?
+        .to_any()
+        .map_err(|e| anyhow!(e))?,
+        WasmCmd::Deploy { .. } | WasmCmd::Upgrade { .. } => bail!(
+            "`--generate-only` and `--dry-run` are not supported for commands with multiple transactions, use `store-code` and `instantiate` / `migrate` instead"
+        ),
+        _ => unimplemented!(),
+    })
+}
+
 pub(crate) fn deploy<'a>(
     ctx: impl Context<'a, WasmConfig>,
     cmd: &WasmCmd,
@@ -453,14 +1008,16 @@ pub(crate) fn deploy<'a>(
             funds,
             no_rebuild,
             no_wasm_opt,
+            force_store,
+            instantiate2_args,
             base_tx_args,
         } => {
             let BaseTxArgs {
                 network,
                 signer_args,
                 gas_args,
-                timeout_height,
                 account_sequence,
+                ..
             }: &BaseTxArgs = base_tx_args;
             ops::deploy(
                 &ctx,
@@ -470,8 +1027,9 @@ pub(crate) fn deploy<'a>(
                 permit_instantiate_only,
                 admin.as_ref(),
                 funds.as_ref().map(|s| s.as_str()).try_into()?,
+                instantiate2_args,
                 network,
-                timeout_height,
+                &base_tx_args.tx_options()?,
                 {
                     let global_conf = ctx.global_config()?;
                     &Gas::from_args(
@@ -485,6 +1043,7 @@ pub(crate) fn deploy<'a>(
                 no_rebuild,
                 no_wasm_opt,
                 account_sequence,
+                force_store,
             )
         }
         _ => unimplemented!(),
@@ -506,6 +1065,43 @@ pub(crate) fn query<'a>(ctx: impl Context<'a, WasmConfig>, cmd: &WasmCmd) -> Res
     }
 }
 
+pub(crate) fn info<'a>(ctx: impl Context<'a, WasmConfig>, cmd: &WasmCmd) -> Result<InfoResponse> {
+    match cmd {
+        WasmCmd::Info {
+            contract_name,
+            label,
+            network,
+        } => ops::info(&ctx, contract_name, label, network),
+        _ => unimplemented!(),
+    }
+}
+
+pub(crate) fn code_info<'a>(
+    ctx: impl Context<'a, WasmConfig>,
+    cmd: &WasmCmd,
+) -> Result<CodeInfoResponse> {
+    match cmd {
+        WasmCmd::CodeInfo {
+            contract_name,
+            network,
+        } => ops::code_info(&ctx, contract_name, network),
+        _ => unimplemented!(),
+    }
+}
+
+pub(crate) fn verify<'a>(
+    ctx: impl Context<'a, WasmConfig>,
+    cmd: &WasmCmd,
+) -> Result<VerifyResponse> {
+    match cmd {
+        WasmCmd::Verify {
+            contract_name,
+            network,
+        } => ops::verify(&ctx, contract_name, network),
+        _ => unimplemented!(),
+    }
+}
+
 pub(crate) fn build<'a>(ctx: impl Context<'a, WasmConfig>, cmd: &WasmCmd) -> Result<()> {
     match cmd {
         WasmCmd::Build {
@@ -525,14 +1121,15 @@ pub(crate) fn store_code<'a>(
             contract_name,
             no_wasm_opt,
             permit_instantiate_only,
+            force_store,
             base_tx_args,
         } => {
             let BaseTxArgs {
                 network,
                 signer_args,
                 gas_args,
-                timeout_height,
                 account_sequence,
+                ..
             }: &BaseTxArgs = base_tx_args;
 
             ops::store_code(
@@ -549,9 +1146,10 @@ pub(crate) fn store_code<'a>(
                         global_conf.gas_adjustment(),
                     )?
                 },
-                timeout_height,
+                &base_tx_args.tx_options()?,
                 signer_args.private_key(&ctx.global_config()?)?,
                 account_sequence,
+                force_store,
             )
         }
         _ => unimplemented!(),
@@ -570,14 +1168,16 @@ pub(crate) fn upgrade<'a>(
             no_rebuild,
             no_wasm_opt,
             permit_instantiate_only,
+            force_store,
+            force,
             base_tx_args,
         } => {
             let BaseTxArgs {
                 network,
                 signer_args,
                 gas_args,
-                timeout_height,
                 account_sequence,
+                ..
             }: &BaseTxArgs = base_tx_args;
             ops::upgrade(
                 &ctx,
@@ -586,7 +1186,7 @@ pub(crate) fn upgrade<'a>(
                 raw.as_ref(),
                 permit_instantiate_only,
                 network,
-                timeout_height,
+                &base_tx_args.tx_options()?,
                 {
                     let global_conf = ctx.global_config()?;
                     &Gas::from_args(
@@ -600,6 +1200,8 @@ pub(crate) fn upgrade<'a>(
                 no_rebuild,
                 no_wasm_opt,
                 account_sequence,
+                force_store,
+                force,
             )
         }
         _ => unimplemented!(),
@@ -617,14 +1219,16 @@ pub(crate) fn migrate<'a>(
             raw,
             no_proposal_sync,
             yes,
+            force,
             base_tx_args,
+            ..
         } => {
             let BaseTxArgs {
                 network,
                 signer_args,
                 gas_args,
-                timeout_height,
                 account_sequence,
+                ..
             }: &BaseTxArgs = base_tx_args;
             ops::migrate(
                 &ctx,
@@ -634,7 +1238,52 @@ pub(crate) fn migrate<'a>(
                 *no_proposal_sync,
                 *yes,
                 network,
-                timeout_height,
+                cmd.granter()?.as_ref(),
+                &base_tx_args.tx_options()?,
+                {
+                    let global_conf = ctx.global_config()?;
+                    &Gas::from_args(
+                        gas_args,
+                        global_conf.gas_price(),
+                        global_conf.gas_adjustment(),
+                    )?
+                },
+                signer_args.private_key(&ctx.global_config()?)?,
+                account_sequence,
+                *force,
+            )
+        }
+        _ => unimplemented!(),
+    }
+}
+
+pub(crate) fn rollback<'a>(
+    ctx: impl Context<'a, WasmConfig>,
+    cmd: &WasmCmd,
+) -> Result<RollbackResponse> {
+    match cmd {
+        WasmCmd::Rollback {
+            contract_name,
+            label,
+            raw,
+            base_tx_args,
+            ..
+        } => {
+            let BaseTxArgs {
+                network,
+                signer_args,
+                gas_args,
+                account_sequence,
+                ..
+            }: &BaseTxArgs = base_tx_args;
+            ops::rollback(
+                &ctx,
+                contract_name,
+                label.as_str(),
+                raw.as_ref(),
+                network,
+                cmd.granter()?.as_ref(),
+                &base_tx_args.tx_options()?,
                 {
                     let global_conf = ctx.global_config()?;
                     &Gas::from_args(
@@ -661,13 +1310,14 @@ pub(crate) fn update_admin<'a>(
             label,
             new_admin,
             base_tx_args,
+            ..
         } => {
             let BaseTxArgs {
                 network,
                 signer_args,
                 gas_args,
-                timeout_height,
                 account_sequence,
+                ..
             }: &BaseTxArgs = base_tx_args;
 
             ops::update_admin(
@@ -676,6 +1326,7 @@ pub(crate) fn update_admin<'a>(
                 label,
                 network,
                 new_admin,
+                cmd.granter()?.as_ref(),
                 {
                     let global_conf = ctx.global_config()?;
                     &Gas::from_args(
@@ -684,7 +1335,7 @@ pub(crate) fn update_admin<'a>(
                         global_conf.gas_adjustment(),
                     )?
                 },
-                timeout_height,
+                &base_tx_args.tx_options()?,
                 signer_args.private_key(&ctx.global_config()?)?,
                 account_sequence,
             )
@@ -702,13 +1353,14 @@ pub(crate) fn clear_admin<'a>(
             contract_name,
             label,
             base_tx_args,
+            ..
         } => {
             let BaseTxArgs {
                 network,
                 signer_args,
                 gas_args,
-                timeout_height,
                 account_sequence,
+                ..
             }: &BaseTxArgs = base_tx_args;
 
             ops::clear_admin(
@@ -724,7 +1376,50 @@ pub(crate) fn clear_admin<'a>(
                         global_conf.gas_adjustment(),
                     )?
                 },
-                timeout_height,
+                &base_tx_args.tx_options()?,
+                signer_args.private_key(&ctx.global_config()?)?,
+                account_sequence,
+            )
+        }
+        _ => unimplemented!(),
+    }
+}
+
+pub(crate) fn update_instantiate_config<'a>(
+    ctx: impl Context<'a, WasmConfig>,
+    cmd: &WasmCmd,
+) -> Result<UpdateInstantiateConfigResponse> {
+    match cmd {
+        WasmCmd::UpdateInstantiateConfig {
+            contract_name,
+            code_id,
+            permit_instantiate_only,
+            base_tx_args,
+            ..
+        } => {
+            let BaseTxArgs {
+                network,
+                signer_args,
+                gas_args,
+                account_sequence,
+                ..
+            }: &BaseTxArgs = base_tx_args;
+
+            ops::update_instantiate_config(
+                &ctx,
+                contract_name,
+                *code_id,
+                permit_instantiate_only,
+                network,
+                {
+                    let global_conf = ctx.global_config()?;
+                    &Gas::from_args(
+                        gas_args,
+                        global_conf.gas_price(),
+                        global_conf.gas_adjustment(),
+                    )?
+                },
+                &base_tx_args.tx_options()?,
                 signer_args.private_key(&ctx.global_config()?)?,
                 account_sequence,
             )
@@ -746,14 +1441,16 @@ pub(crate) fn instantiate<'a>(
             funds,
             no_proposal_sync,
             yes,
+            instantiate2_args,
             base_tx_args,
+            ..
         } => {
             let BaseTxArgs {
                 network,
                 signer_args,
                 gas_args,
-                timeout_height,
                 account_sequence,
+                ..
             }: &BaseTxArgs = base_tx_args;
             ops::instantiate(
                 &ctx,
@@ -764,8 +1461,10 @@ pub(crate) fn instantiate<'a>(
                 *no_proposal_sync,
                 *yes,
                 funds.as_ref().map(|s| s.as_str()).try_into()?,
+                instantiate2_args,
                 network,
-                timeout_height,
+                cmd.granter()?.as_ref(),
+                &base_tx_args.tx_options()?,
                 {
                     let global_conf = ctx.global_config()?;
                     &Gas::from_args(
@@ -782,6 +1481,46 @@ pub(crate) fn instantiate<'a>(
     }
 }
 
+pub(crate) fn predict_address<'a>(
+    ctx: impl Context<'a, WasmConfig>,
+    cmd: &WasmCmd,
+) -> Result<PredictAddressResponse> {
+    match cmd {
+        WasmCmd::PredictAddress {
+            contract_name,
+            label,
+            salt,
+            fix_msg,
+            raw,
+            checksum,
+            creator,
+            network,
+            signer_args,
+        } => {
+            let creator = match creator {
+                Some(creator) => creator
+                    .parse::<AccountId>()
+                    .map_err(|e| anyhow!(e))
+                    .with_context(|| format!("Invalid creator address: {creator}"))?,
+                None => signer_args.account_id(&ctx.global_config()?)?,
+            };
+
+            ops::predict_address(
+                &ctx,
+                contract_name,
+                label,
+                salt,
+                *fix_msg,
+                raw.as_ref(),
+                checksum.as_deref(),
+                &creator,
+                network,
+            )
+        }
+        _ => unimplemented!(),
+    }
+}
+
 pub(crate) fn execute<'a>(
     ctx: impl Context<'a, WasmConfig>,
     cmd: &WasmCmd,
@@ -793,13 +1532,14 @@ pub(crate) fn execute<'a>(
             raw,
             funds,
             base_tx_args,
+            ..
         } => {
             let BaseTxArgs {
                 network,
                 signer_args,
                 gas_args,
-                timeout_height,
                 account_sequence,
+                ..
             }: &BaseTxArgs = base_tx_args;
             ops::execute(
                 &ctx,
@@ -808,7 +1548,8 @@ pub(crate) fn execute<'a>(
                 raw.as_ref(),
                 funds.as_ref().map(|s| s.as_str()).try_into()?,
                 network,
-                timeout_height,
+                cmd.granter()?.as_ref(),
+                &base_tx_args.tx_options()?,
                 {
                     let global_conf = ctx.global_config()?;
                     &Gas::from_args(
@@ -825,6 +1566,58 @@ pub(crate) fn execute<'a>(
     }
 }
 
+pub(crate) fn batch<'a>(ctx: impl Context<'a, WasmConfig>, cmd: &WasmCmd) -> Result<BatchResponse> {
+    match cmd {
+        WasmCmd::Batch {
+            file,
+            no_proposal_sync,
+            yes,
+            base_tx_args,
+        } => batch_ops(
+            ctx,
+            &ops::batch::read_batch_file(file)?,
+            *no_proposal_sync,
+            *yes,
+            base_tx_args,
+        ),
+        _ => unimplemented!(),
+    }
+}
+
+pub(crate) fn batch_ops<'a>(
+    ctx: impl Context<'a, WasmConfig>,
+    batch_ops: &[BatchOp],
+    no_proposal_sync: bool,
+    yes: bool,
+    base_tx_args: &BaseTxArgs,
+) -> Result<BatchResponse> {
+    let BaseTxArgs {
+        network,
+        signer_args,
+        gas_args,
+        account_sequence,
+        ..
+    }: &BaseTxArgs = base_tx_args;
+    ops::batch(
+        &ctx,
+        batch_ops,
+        no_proposal_sync,
+        yes,
+        network,
+        &base_tx_args.tx_options()?,
+        {
+            let global_conf = ctx.global_config()?;
+            &Gas::from_args(
+                gas_args,
+                global_conf.gas_price(),
+                global_conf.gas_adjustment(),
+            )?
+        },
+        signer_args.private_key(&ctx.global_config()?)?,
+        account_sequence,
+    )
+}
+
 #[cfg(test)]
 mod tests {
     use std::{collections::HashMap, env, fs, path::Path};
@@ -5,5 +5,6 @@ pub mod ops;
 pub use crate::modules::wasm::config::WasmConfig;
 pub use crate::modules::wasm::entrypoint::{WasmCmd, WasmModule};
 
-mod args;
+pub(crate) mod args;
+pub(crate) mod contract_state;
 pub(crate) mod proposal;
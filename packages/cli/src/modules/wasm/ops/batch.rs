@@ -0,0 +1,460 @@
+use crate::attrs_format;
+use crate::modules::wasm::config::WasmConfig;
+use crate::support::cosmos::{attribute_value, msg_events, TxOptions};
+use crate::support::future::block;
+use crate::support::gas::Gas;
+use crate::support::ops_response::OpResponseDisplay;
+use crate::support::state::{Deployment, DeploymentKind, State};
+use crate::{framework::Context, support::cosmos::Client};
+use anyhow::{anyhow, bail, Context as _, Result};
+use cosmos_sdk_proto::cosmwasm::wasm::v1::{
+    MsgExecuteContractResponse, MsgMigrateContract, MsgUpdateAdmin,
+};
+use cosmos_sdk_proto::traits::Message;
+use cosmrs::cosmwasm::{MsgExecuteContract, MsgInstantiateContract};
+use cosmrs::crypto::secp256k1::SigningKey;
+use cosmrs::tx::{MessageExt, Msg};
+use cosmrs::{AccountId, Any};
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use std::fs;
+use std::path::Path;
+
+use super::execute::{execute_msg, DeliverTxData, ExecuteResponse};
+use super::instantiate::{instantiate_msg, InstantiateResponse};
+use super::migrate::{migrate_msg, MigrateResponse};
+use super::update_admin::{update_admin_msg, UpdateAdminResponse};
+
+/// Single operation of a batch, `msg` is used in place of the contract's msg files if specified.
+/// Contracts are addressed through beaker's state, so a contract instantiated in a batch can not be
+/// the target of later operations of the same batch.
+#[derive(Deserialize, Debug, Clone)]
+#[serde(rename_all = "snake_case")]
+pub enum BatchOp {
+    Execute {
+        contract_name: String,
+        #[serde(default = "default_value::label")]
+        label: String,
+        msg: Option<serde_json::Value>,
+        funds: Option<String>,
+    },
+    Instantiate {
+        contract_name: String,
+        #[serde(default = "default_value::label")]
+        label: String,
+        msg: Option<serde_json::Value>,
+        admin: Option<String>,
+        funds: Option<String>,
+    },
+    Migrate {
+        contract_name: String,
+        #[serde(default = "default_value::label")]
+        label: String,
+        msg: Option<serde_json::Value>,
+        /// Migrate even if, per cw2, the contract would become a different contract or be downgraded
+        #[serde(default)]
+        force: bool,
+    },
+    UpdateAdmin {
+        contract_name: String,
+        #[serde(default = "default_value::label")]
+        label: String,
+        new_admin: String,
+    },
+}
+
+mod default_value {
+    pub(crate) fn label() -> String {
+        "default".to_string()
+    }
+}
+
+pub fn read_batch_file(path: &Path) -> Result<Vec<BatchOp>> {
+    let content = fs::read_to_string(path)
+        .with_context(|| format!("Unable to read `{}`", path.to_string_lossy()))?;
+    serde_json::from_str(&content)
+        .with_context(|| format!("Unable to parse `{}`", path.to_string_lossy()))
+}
+
+#[allow(clippy::too_many_arguments)]
+pub fn batch<'a, Ctx: Context<'a, WasmConfig>>(
+    ctx: &Ctx,
+    batch_ops: &[BatchOp],
+    no_proposal_sync: bool,
+    yes: bool,
+    network: &str,
+    tx_options: &TxOptions,
+    gas: &Gas,
+    signing_key: SigningKey,
+    account_sequence: &Option<u64>,
+) -> Result<BatchResponse> {
+    let global_config = ctx.global_config()?;
+
+    let network_info = global_config
+        .networks()
+        .get(network)
+        .with_context(|| format!("Unable to find network config: {network}"))?
+        .to_owned();
+
+    let query_client = Client::new(network_info.clone(), &ctx.root()?, &global_config);
+    let client = query_client.to_signing_client(signing_key);
+    let signer = client.signer_account_id();
+
+    let msgs = batch_msgs(
+        ctx,
+        batch_ops,
+        no_proposal_sync,
+        yes,
+        true,
+        network,
+        &client.signer_account_id(),
+    )?;
+
+    block(async {
+        let response = client
+            .sign_and_broadcast(msgs.clone(), gas, tx_options, account_sequence)
+            .await?;
+
+        let events = msg_events(&response);
+        let msg_data = base64::decode(&response.deliver_tx.data[..])
+            .ok()
+            .and_then(|bytes| DeliverTxData::decode(&bytes[..]).ok())
+            .map(|d| d.data)
+            .unwrap_or_default();
+
+        let mut results = vec![];
+        for (i, (op, msg)) in batch_ops.iter().zip(msgs.iter()).enumerate() {
+            let result = match op {
+                BatchOp::Execute { label, .. } => {
+                    let msg = MsgExecuteContract::from_any(msg).map_err(|e| anyhow!(e))?;
+                    let data = msg_data
+                        .get(i)
+                        .and_then(|d| MsgExecuteContractResponse::decode(&d.value[..]).ok())
+                        .and_then(|r| serde_json::from_slice(&r.data[..]).ok());
+
+                    BatchOpResponse::Execute(ExecuteResponse {
+                        label: label.to_string(),
+                        contract_address: msg.contract.to_string(),
+                        data,
+                    })
+                }
+                BatchOp::Instantiate {
+                    contract_name,
+                    label,
+                    msg: op_msg,
+                    ..
+                } => {
+                    let msg = MsgInstantiateContract::from_any(msg).map_err(|e| anyhow!(e))?;
+                    let deployment = Deployment::new(
+                        DeploymentKind::Instantiate,
+                        msg.code_id,
+                        String::from_utf8_lossy(&msg.msg).to_string(),
+                    )
+                    .with_msg_file(op_msg.is_none().then(|| {
+                        format!("contracts/{contract_name}/instantiate-msgs/{label}.json")
+                    }))
+                    .committed(&query_client, &response, &signer)
+                    .await;
+                    let contract_address = events
+                        .get(i)
+                        .and_then(|events| events.iter().find(|e| e.kind == "instantiate"))
+                        .and_then(|e| attribute_value(e, "_contract_address"))
+                        .with_context(|| {
+                            format!(
+                                "Unable to find instantiated address of {contract_name}:{label}"
+                            )
+                        })?;
+
+                    State::update_state_file(
+                        network_info.network_variant(),
+                        ctx.root()?,
+                        &|s: &State| -> State {
+                            s.update_address(network, contract_name, label, &contract_address)
+                                .record_deployment(network, contract_name, label, &deployment)
+                        },
+                    )?;
+
+                    BatchOpResponse::Instantiate(InstantiateResponse {
+                        label: label.to_string(),
+                        contract_address,
+                        code_id: msg.code_id,
+                        creator: msg.sender.to_string(),
+                        admin: msg
+                            .admin
+                            .map(|a| a.to_string())
+                            .unwrap_or_else(|| "-".to_string()),
+                    })
+                }
+                BatchOp::Migrate {
+                    contract_name,
+                    label,
+                    msg: op_msg,
+                    ..
+                } => {
+                    let msg = MsgMigrateContract::decode(&msg.value[..])?;
+                    let deployment =
+                        Deployment::new(
+                            DeploymentKind::Migrate,
+                            msg.code_id,
+                            String::from_utf8_lossy(&msg.msg).to_string(),
+                        )
+                        .with_msg_file(op_msg.is_none().then(|| {
+                            format!("contracts/{contract_name}/migrate-msgs/{label}.json")
+                        }))
+                        .committed(&query_client, &response, &signer)
+                        .await;
+                    State::update_state_file(
+                        network_info.network_variant(),
+                        ctx.root()?,
+                        &|s: &State| -> State {
+                            s.record_deployment(network, contract_name, label, &deployment)
+                        },
+                    )?;
+
+                    BatchOpResponse::Migrate(MigrateResponse {
+                        label: label.to_string(),
+                        contract_address: msg.contract,
+                        code_id: msg.code_id,
+                        creator: msg.sender,
+                    })
+                }
+                BatchOp::UpdateAdmin { .. } => {
+                    let msg = MsgUpdateAdmin::decode(&msg.value[..])?;
+                    BatchOpResponse::UpdateAdmin(UpdateAdminResponse {
+                        contract: msg.contract,
+                        new_admin: msg.new_admin,
+                    })
+                }
+            };
+            results.push(result);
+        }
+
+        let batch_response = BatchResponse {
+            tx_hash: response.hash.to_string(),
+            msg_count: results.len(),
+            results,
+        };
+
+        batch_response.log();
+        batch_response.results.iter().for_each(BatchOpResponse::log);
+
+        Ok(batch_response)
+    })
+}
+
+/// Build the messages of all `batch_ops` in order, sent by `sender`.
+pub fn batch_msgs<'a, Ctx: Context<'a, WasmConfig>>(
+    ctx: &Ctx,
+    batch_ops: &[BatchOp],
+    no_proposal_sync: bool,
+    yes: bool,
+    update_state: bool,
+    network: &str,
+    sender: &AccountId,
+) -> Result<Vec<Any>> {
+    check_targets(batch_ops)?;
+
+    batch_ops
+        .iter()
+        .map(|op| match op {
+            BatchOp::Execute {
+                contract_name,
+                label,
+                msg,
+                funds,
+            } => execute_msg(
+                ctx,
+                contract_name,
+                label,
+                msg.as_ref().map(|m| m.to_string()).as_ref(),
+                funds.as_deref().try_into()?,
+                network,
+                sender,
+            )?
+            .to_any()
+            .map_err(|e| anyhow!(e)),
+            BatchOp::Instantiate {
+                contract_name,
+                label,
+                msg,
+                admin,
+                funds,
+            } => instantiate_msg(
+                ctx,
+                contract_name,
+                label,
+                msg.as_ref().map(|m| m.to_string()).as_ref(),
+                admin.as_ref(),
+                no_proposal_sync,
+                yes,
+                update_state,
+                funds.as_deref().try_into()?,
+                network,
+                sender,
+            )?
+            .to_any()
+            .map_err(|e| anyhow!(e)),
+            BatchOp::Migrate {
+                contract_name,
+                label,
+                msg,
+                force,
+            } => Ok(migrate_msg(
+                ctx,
+                contract_name,
+                label,
+                msg.as_ref().map(|m| m.to_string()).as_ref(),
+                no_proposal_sync,
+                yes,
+                update_state,
+                network,
+                sender,
+                *force,
+            )?
+            .to_any()?),
+            BatchOp::UpdateAdmin {
+                contract_name,
+                label,
+                new_admin,
+            } => Ok(
+                update_admin_msg(ctx, contract_name, label, network, new_admin, sender)?
+                    .to_any()?,
+            ),
+        })
+        .collect()
+}
+
+/// Refuse operations on a contract instantiated earlier in the batch, as its address is only known
+/// once the batch is committed and the state would otherwise point them at a previous instance.
+fn check_targets(batch_ops: &[BatchOp]) -> Result<()> {
+    let mut instantiated = HashSet::new();
+
+    for op in batch_ops {
+        let (contract_name, label) = match op {
+            BatchOp::Instantiate {
+                contract_name,
+                label,
+                ..
+            } => {
+                instantiated.insert((contract_name, label));
+                continue;
+            }
+            BatchOp::Execute {
+                contract_name,
+                label,
+                ..
+            }
+            | BatchOp::Migrate {
+                contract_name,
+                label,
+                ..
+            }
+            | BatchOp::UpdateAdmin {
+                contract_name,
+                label,
+                ..
+            } => (contract_name, label),
+        };
+
+        if instantiated.contains(&(contract_name, label)) {
+            bail!(
+                "`{contract_name}:{label}` is instantiated in this batch, its address is not known until the batch is committed. \
+                Operate on it in a separate batch"
+            );
+        }
+    }
+
+    Ok(())
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum BatchOpResponse {
+    Execute(ExecuteResponse),
+    Instantiate(InstantiateResponse),
+    Migrate(MigrateResponse),
+    UpdateAdmin(UpdateAdminResponse),
+}
+
+impl BatchOpResponse {
+    fn log(&self) {
+        match self {
+            BatchOpResponse::Execute(r) => r.log(),
+            BatchOpResponse::Instantiate(r) => r.log(),
+            BatchOpResponse::Migrate(r) => r.log(),
+            BatchOpResponse::UpdateAdmin(r) => r.log(),
+        }
+    }
+}
+
+#[derive(Serialize)]
+pub struct BatchResponse {
+    pub tx_hash: String,
+    pub msg_count: usize,
+    pub results: Vec<BatchOpResponse>,
+}
+
+impl OpResponseDisplay for BatchResponse {
+    fn headline() -> &'static str {
+        "Batch transaction broadcasted successfully!! 🎉"
+    }
+    fn attrs(&self) -> Vec<String> {
+        attrs_format! { self | tx_hash, msg_count }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_batch_ops() {
+        let batch_ops: Vec<BatchOp> = serde_json::from_str(
+            r#"[
+                { "instantiate": { "contract_name": "router", "msg": {} } },
+                { "execute": { "contract_name": "router", "label": "main", "msg": { "register": {} }, "funds": "10uosmo" } },
+                { "update_admin": { "contract_name": "router", "new_admin": "osmo1admin" } }
+            ]"#,
+        )
+        .unwrap();
+
+        assert_eq!(batch_ops.len(), 3);
+        assert!(matches!(
+            &batch_ops[0],
+            BatchOp::Instantiate { label, admin: None, .. } if label == "default"
+        ));
+        assert!(matches!(
+            &batch_ops[1],
+            BatchOp::Execute { label, funds: Some(funds), .. } if label == "main" && funds == "10uosmo"
+        ));
+        assert!(matches!(
+            &batch_ops[2],
+            BatchOp::UpdateAdmin { new_admin, .. } if new_admin == "osmo1admin"
+        ));
+    }
+
+    #[test]
+    fn refuse_ops_on_contract_instantiated_in_batch() {
+        let batch_ops = |ops: &str| serde_json::from_str::<Vec<BatchOp>>(ops).unwrap();
+
+        assert!(check_targets(&batch_ops(
+            r#"[
+                { "execute": { "contract_name": "router", "msg": {} } },
+                { "instantiate": { "contract_name": "router", "msg": {} } },
+                { "execute": { "contract_name": "router", "label": "main", "msg": {} } }
+            ]"#
+        ))
+        .is_ok());
+
+        let err = check_targets(&batch_ops(
+            r#"[
+                { "instantiate": { "contract_name": "router", "msg": {} } },
+                { "migrate": { "contract_name": "router" } }
+            ]"#,
+        ))
+        .unwrap_err();
+        assert!(err
+            .to_string()
+            .contains("`router:default` is instantiated in this batch"));
+    }
+}
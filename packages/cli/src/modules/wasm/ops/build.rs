@@ -51,20 +51,33 @@ pub fn build<'a, Ctx: Context<'a, WasmConfig>>(
         println!("Optimizing wasm...");
         let optimizer_version = ctx.config()?.optimizer_version;
 
-        let arch_suffix = if *aarch64 { "-arm64" } else { "" };
-
-        run_command(Command::new("docker").args([
-            "run",
-            "--rm",
-            "-v",
-            format!("{root_dir_str}:/code").as_str(),
-            "--mount",
-            format!("type=volume,source={wp_name}_cache,target=/code/target").as_str(),
-            "--mount",
-            "type=volume,source=registry_cache,target=/usr/local/cargo/registry",
-            format!("cosmwasm/workspace-optimizer{arch_suffix}:{optimizer_version}").as_str(),
-        ]))?;
+        run_command(Command::new("docker").args(optimizer_args(
+            root_dir_str,
+            wp_name,
+            &optimizer_image(&optimizer_version, aarch64),
+        )))?;
     }
 
     Ok(())
 }
+
+/// Docker image of the workspace optimizer, the arm64 one does not produce the same wasm.
+pub fn optimizer_image(optimizer_version: &str, aarch64: &bool) -> String {
+    let arch_suffix = if *aarch64 { "-arm64" } else { "" };
+    format!("cosmwasm/workspace-optimizer{arch_suffix}:{optimizer_version}")
+}
+
+/// Args of `docker` for optimizing the wasm of the workspace at `root_dir` with `image`.
+pub fn optimizer_args(root_dir: &str, wp_name: &str, image: &str) -> Vec<String> {
+    vec![
+        "run".to_string(),
+        "--rm".to_string(),
+        "-v".to_string(),
+        format!("{root_dir}:/code"),
+        "--mount".to_string(),
+        format!("type=volume,source={wp_name}_cache,target=/code/target"),
+        "--mount".to_string(),
+        "type=volume,source=registry_cache,target=/usr/local/cargo/registry".to_string(),
+        image.to_string(),
+    ]
+}
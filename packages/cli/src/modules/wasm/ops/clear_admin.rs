@@ -8,11 +8,15 @@ use anyhow::Context as _;
 
 use cosmos_sdk_proto::cosmwasm::wasm::v1::MsgClearAdmin;
 use cosmrs::tx::MessageExt;
+use cosmrs::AccountId;
 use serde::Serialize;
 
 use crate::support::state::State;
 
-use crate::{framework::Context, support::cosmos::Client};
+use crate::{
+    framework::Context,
+    support::cosmos::{Client, TxOptions},
+};
 
 use anyhow::Result;
 
@@ -25,12 +29,11 @@ pub fn clear_admin<'a, Ctx: Context<'a, WasmConfig>>(
     label: &str,
     network: &str,
     gas: &Gas,
-    timeout_height: &u32,
+    tx_options: &TxOptions,
     signing_key: SigningKey,
     account_sequence: &Option<u64>,
 ) -> Result<ClearAdminResponse> {
     let global_config = ctx.global_config()?;
-    let account_prefix = global_config.account_prefix().as_str();
 
     let network_info = global_config
         .networks()
@@ -38,35 +41,30 @@ pub fn clear_admin<'a, Ctx: Context<'a, WasmConfig>>(
         .with_context(|| format!("Unable to find network config: {network}"))?
         .to_owned();
 
-    let client = Client::new(network_info.clone()).to_signing_client(signing_key, account_prefix);
+    let client = Client::new(network_info.clone(), &ctx.root()?, &global_config)
+        .to_signing_client(signing_key);
 
-    let state = State::load_by_network(network_info, ctx.root()?)?;
-    let wasm_ref = state.get_ref(network, contract_name)?;
-    let contract = wasm_ref
-        .addresses()
-        .get(label)
-        .with_context(|| format!("Unable to retrieve contract for {contract_name}:{label}"))?;
-
-    let msg_clear_admin = MsgClearAdmin {
-        sender: client.signer_account_id().to_string(),
-        contract: contract.to_string(),
-    }
-    .to_any()
-    .unwrap();
+    let msg_clear_admin = clear_admin_msg(
+        ctx,
+        contract_name,
+        label,
+        network,
+        &client.signer_account_id(),
+    )?;
+    let contract = msg_clear_admin.contract.clone();
 
     block(async {
         let _response = client
             .sign_and_broadcast(
-                vec![msg_clear_admin],
+                vec![msg_clear_admin.to_any().unwrap()],
                 gas,
-                "",
-                timeout_height,
+                tx_options,
                 account_sequence,
             )
             .await?;
 
         let clear_admin_response = ClearAdminResponse {
-            contract: contract.to_string(),
+            contract,
             admin: "–".to_string(),
         };
 
@@ -76,6 +74,34 @@ pub fn clear_admin<'a, Ctx: Context<'a, WasmConfig>>(
     })
 }
 
+/// Build the `MsgClearAdmin` for the labeled contract, sent by `sender`.
+pub fn clear_admin_msg<'a, Ctx: Context<'a, WasmConfig>>(
+    ctx: &Ctx,
+    contract_name: &str,
+    label: &str,
+    network: &str,
+    sender: &AccountId,
+) -> Result<MsgClearAdmin> {
+    let network_info = ctx
+        .global_config()?
+        .networks()
+        .get(network)
+        .with_context(|| format!("Unable to find network config: {network}"))?
+        .to_owned();
+
+    let state = State::load_by_network(network_info, ctx.root()?)?;
+    let wasm_ref = state.get_ref(network, contract_name)?;
+    let contract = wasm_ref
+        .addresses()
+        .get(label)
+        .with_context(|| format!("Unable to retrieve contract for {contract_name}:{label}"))?;
+
+    Ok(MsgClearAdmin {
+        sender: sender.to_string(),
+        contract: contract.to_string(),
+    })
+}
+
 #[derive(Serialize)]
 pub struct ClearAdminResponse {
     pub contract: String,
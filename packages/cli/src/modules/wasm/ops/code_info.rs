@@ -0,0 +1,121 @@
+use crate::attrs_format;
+use crate::modules::wasm::config::WasmConfig;
+use crate::support::future::block;
+use crate::support::ops_response::OpResponseDisplay;
+use crate::support::state::State;
+use crate::support::storage_key::encode_hex;
+use crate::{framework::Context, support::cosmos::Client};
+use anyhow::{Context as _, Result};
+use cosmos_sdk_proto::cosmwasm::wasm::v1::{AccessConfig, AccessType};
+use serde::Serialize;
+
+/// Show the checksum, creator and instantiate permission of the contract's stored code.
+pub fn code_info<'a, Ctx: Context<'a, WasmConfig>>(
+    ctx: &Ctx,
+    contract_name: &str,
+    network: &str,
+) -> Result<CodeInfoResponse> {
+    let global_config = ctx.global_config()?;
+    let network_info = global_config
+        .networks()
+        .get(network)
+        .with_context(|| format!("Unable to find network config: {network}"))?
+        .to_owned();
+
+    let client = Client::new(network_info.clone(), &ctx.root()?, &global_config);
+    let state = State::load_by_network(network_info, ctx.root()?)?;
+
+    let code_id = state
+        .get_ref(network, contract_name)?
+        .code_id()
+        .with_context(|| {
+            format!("Code id not found for contract `{contract_name}` on network `{network}`")
+        })?;
+
+    block(async {
+        let code_info = client.code_info(&code_id).await?;
+
+        let code_info_response = CodeInfoResponse {
+            code_id,
+            checksum: encode_hex(&code_info.data_hash),
+            creator: code_info.creator,
+            instantiate_permission: access_config_str(code_info.instantiate_permission.as_ref()),
+        };
+        code_info_response.log();
+
+        Ok(code_info_response)
+    })
+}
+
+/// Permission with the addresses it is granted to, eg. `OnlyAddress(osmo1...)`.
+pub fn access_config_str(access_config: Option<&AccessConfig>) -> String {
+    let Some(access_config) = access_config else {
+        return "–".to_string();
+    };
+
+    let addresses = || {
+        let mut addresses = access_config.addresses.clone();
+        if !access_config.address.is_empty() && !addresses.contains(&access_config.address) {
+            addresses.insert(0, access_config.address.clone());
+        }
+        addresses.join(", ")
+    };
+
+    match AccessType::from_i32(access_config.permission).unwrap_or_default() {
+        AccessType::Unspecified => "Unspecified".to_string(),
+        AccessType::Nobody => "Nobody".to_string(),
+        AccessType::Everybody => "Everybody".to_string(),
+        AccessType::OnlyAddress => format!("OnlyAddress({})", addresses()),
+        AccessType::AnyOfAddresses => format!("AnyOfAddresses({})", addresses()),
+    }
+}
+
+#[derive(Serialize)]
+pub struct CodeInfoResponse {
+    pub code_id: u64,
+    pub checksum: String,
+    pub creator: String,
+    pub instantiate_permission: String,
+}
+
+impl OpResponseDisplay for CodeInfoResponse {
+    fn headline() -> &'static str {
+        "Code found!! 🎉"
+    }
+    fn attrs(&self) -> Vec<String> {
+        attrs_format! { self | code_id, checksum, creator, instantiate_permission }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn access_config_with_addresses() {
+        assert_eq!(
+            access_config_str(Some(&AccessConfig {
+                permission: AccessType::OnlyAddress as i32,
+                address: "osmo1abc".to_string(),
+                addresses: vec![],
+            })),
+            "OnlyAddress(osmo1abc)"
+        );
+        assert_eq!(
+            access_config_str(Some(&AccessConfig {
+                permission: AccessType::AnyOfAddresses as i32,
+                address: String::new(),
+                addresses: vec!["osmo1abc".to_string(), "osmo1def".to_string()],
+            })),
+            "AnyOfAddresses(osmo1abc, osmo1def)"
+        );
+        assert_eq!(
+            access_config_str(Some(&AccessConfig {
+                permission: AccessType::Everybody as i32,
+                ..Default::default()
+            })),
+            "Everybody"
+        );
+        assert_eq!(access_config_str(None), "–");
+    }
+}
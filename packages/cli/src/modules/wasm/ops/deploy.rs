@@ -1,6 +1,8 @@
 use crate::framework::Context;
+use crate::modules::wasm::args::Instantiate2Args;
 use crate::modules::wasm::WasmConfig;
 use crate::support::coin::Coins;
+use crate::support::cosmos::TxOptions;
 use crate::support::gas::Gas;
 use anyhow::Result;
 
@@ -20,14 +22,16 @@ pub fn deploy<'a, Ctx: Context<'a, WasmConfig>>(
     permit_instantiate_only: &Option<String>,
     admin: Option<&String>,
     funds: Coins,
+    instantiate2_args: &Instantiate2Args,
     network: &str,
-    timeout_height: &u32,
+    tx_options: &TxOptions,
     gas: &Gas,
     store_code_signing_key: SigningKey,
     instantiate_signing_key: SigningKey,
     no_rebuild: &bool,
     no_wasm_opt: &bool,
     account_sequence: &Option<u64>,
+    force_store: &bool,
 ) -> Result<InstantiateResponse> {
     if !*no_rebuild {
         build(ctx, no_wasm_opt, &false)?;
@@ -39,9 +43,10 @@ pub fn deploy<'a, Ctx: Context<'a, WasmConfig>>(
         no_wasm_opt,
         permit_instantiate_only,
         gas,
-        timeout_height,
+        tx_options,
         store_code_signing_key,
         account_sequence,
+        force_store,
     )?;
     instantiate(
         ctx,
@@ -53,10 +58,13 @@ pub fn deploy<'a, Ctx: Context<'a, WasmConfig>>(
         true,
         true,
         funds,
+        instantiate2_args,
         network,
-        timeout_height,
+        None,
+        tx_options,
         gas,
         instantiate_signing_key,
-        &account_sequence.map(|seq| seq + 1),
+        // following sequence is tracked by the signing client
+        &None,
     )
 }
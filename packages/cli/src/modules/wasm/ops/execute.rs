@@ -1,7 +1,8 @@
 use crate::attrs_format;
 use crate::modules::wasm::config::WasmConfig;
+use crate::support::authz::{exec_on_behalf, unwrap_exec_response};
 use crate::support::coin::Coins;
-use crate::support::cosmos::ResponseValuePicker;
+use crate::support::cosmos::{ResponseValuePicker, TxOptions};
 use crate::support::future::block;
 use crate::support::gas::Gas;
 use crate::support::ops_response::OpResponseDisplay;
@@ -28,13 +29,13 @@ pub fn execute<'a, Ctx: Context<'a, WasmConfig>>(
     raw: Option<&String>,
     funds: Coins,
     network: &str,
-    timeout_height: &u32,
+    granter: Option<&AccountId>,
+    tx_options: &TxOptions,
     gas: &Gas,
     signing_key: SigningKey,
     account_sequence: &Option<u64>,
 ) -> Result<ExecuteResponse> {
     let global_config = ctx.global_config()?;
-    let account_prefix = global_config.account_prefix().as_str();
 
     let network_info = global_config
         .networks()
@@ -42,7 +43,67 @@ pub fn execute<'a, Ctx: Context<'a, WasmConfig>>(
         .with_context(|| format!("Unable to find network config: {network}"))?
         .to_owned();
 
-    let client = Client::new(network_info.clone()).to_signing_client(signing_key, account_prefix);
+    let client = Client::new(network_info.clone(), &ctx.root()?, &global_config)
+        .to_signing_client(signing_key);
+    let signer = client.signer_account_id();
+    let msg_execute_contract = execute_msg(
+        ctx,
+        contract_name,
+        label,
+        raw,
+        funds,
+        network,
+        granter.unwrap_or(&signer),
+    )?;
+    let msg = exec_on_behalf(granter, &signer, msg_execute_contract.to_any().unwrap())?;
+
+    block(async {
+        let response = client
+            .sign_and_broadcast(vec![msg], gas, tx_options, account_sequence)
+            .await?;
+
+        let contract_address = response.pick("execute", "_contract_address");
+
+        let deliver_tx_data_bytes = base64::decode(&response.deliver_tx.data[..])?;
+        let deliver_tx_data: DeliverTxData =
+            DeliverTxData::decode(&mut &deliver_tx_data_bytes[..])?;
+
+        let response: MsgExecuteContractResponse = MsgExecuteContractResponse::decode(
+            &unwrap_exec_response(&deliver_tx_data.data[0])?[..],
+        )?;
+
+        let decoded = serde_json::from_slice::<serde_json::Value>(&response.data[..]);
+
+        let execute_response = ExecuteResponse {
+            contract_address,
+            label: label.to_string(),
+            data: decoded.ok(),
+        };
+
+        execute_response.log();
+
+        Ok(execute_response)
+    })
+}
+
+/// Build the `MsgExecuteContract` for the labeled contract, sent by `sender`.
+#[allow(clippy::too_many_arguments)]
+pub fn execute_msg<'a, Ctx: Context<'a, WasmConfig>>(
+    ctx: &Ctx,
+    contract_name: &str,
+    label: &str,
+    raw: Option<&String>,
+    funds: Coins,
+    network: &str,
+    sender: &AccountId,
+) -> Result<MsgExecuteContract> {
+    let network_info = ctx
+        .global_config()?
+        .networks()
+        .get(network)
+        .with_context(|| format!("Unable to find network config: {network}"))?
+        .to_owned();
+
     let state = State::load_by_network(network_info, ctx.root()?)?;
 
     let contract = state
@@ -53,8 +114,8 @@ pub fn execute<'a, Ctx: Context<'a, WasmConfig>>(
         .parse::<AccountId>()
         .map_err(|e| anyhow!(e))?;
 
-    let msg_execute_contract = MsgExecuteContract {
-        sender: client.signer_account_id(),
+    Ok(MsgExecuteContract {
+        sender: sender.clone(),
         contract,
         msg: raw
             .map(|s| s.as_bytes().to_vec())
@@ -71,39 +132,6 @@ pub fn execute<'a, Ctx: Context<'a, WasmConfig>>(
                     .map(|s| s.as_bytes().to_vec())
             })?,
         funds: funds.into(),
-    };
-
-    block(async {
-        let response = client
-            .sign_and_broadcast(
-                vec![msg_execute_contract.to_any().unwrap()],
-                gas,
-                "",
-                timeout_height,
-                account_sequence,
-            )
-            .await?;
-
-        let contract_address = response.pick("execute", "_contract_address");
-
-        let deliver_tx_data_bytes = base64::decode(&response.deliver_tx.data[..])?;
-        let deliver_tx_data: DeliverTxData =
-            DeliverTxData::decode(&mut &deliver_tx_data_bytes[..])?;
-
-        let response: MsgExecuteContractResponse =
-            MsgExecuteContractResponse::decode(&mut &deliver_tx_data.data[0].value[..])?;
-
-        let decoded = serde_json::from_slice::<serde_json::Value>(&response.data[..]);
-
-        let execute_response = ExecuteResponse {
-            contract_address,
-            label: label.to_string(),
-            data: decoded.ok(),
-        };
-
-        execute_response.log();
-
-        Ok(execute_response)
     })
 }
 
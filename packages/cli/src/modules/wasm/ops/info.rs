@@ -0,0 +1,154 @@
+use crate::modules::wasm::config::WasmConfig;
+use crate::support::future::block;
+use crate::support::state::State;
+use crate::vars_format;
+use crate::{framework::Context, support::cosmos::Client};
+use anyhow::{anyhow, Context as _, Result};
+use cosmos_sdk_proto::cosmwasm::wasm::v1::{
+    ContractCodeHistoryEntry, ContractCodeHistoryOperationType, ContractInfo,
+};
+use cosmrs::AccountId;
+use serde::Serialize;
+
+/// Show the contract's info as stored by the wasm module, along with its code history.
+pub fn info<'a, Ctx: Context<'a, WasmConfig>>(
+    ctx: &Ctx,
+    contract_name: &str,
+    label: &str,
+    network: &str,
+) -> Result<InfoResponse> {
+    let global_config = ctx.global_config()?;
+    let network_info = global_config
+        .networks()
+        .get(network)
+        .with_context(|| format!("Unable to find network config: {network}"))?
+        .to_owned();
+
+    let client = Client::new(network_info.clone(), &ctx.root()?, &global_config);
+    let state = State::load_by_network(network_info, ctx.root()?)?;
+
+    let contract = state
+        .get_ref(network, contract_name)?
+        .addresses()
+        .get(label)
+        .with_context(|| format!("Unable to retrieve contract for {contract_name}:{label}"))?
+        .parse::<AccountId>()
+        .map_err(|e| anyhow!(e))?;
+
+    block(async {
+        let ContractInfo {
+            code_id,
+            creator,
+            admin,
+            label: contract_label,
+            ibc_port_id,
+            ..
+        } = client.contract_info(contract.to_string()).await?;
+
+        let history = client
+            .contract_history(contract.to_string())
+            .await?
+            .iter()
+            .map(history_entry_str)
+            .collect::<Vec<_>>();
+
+        let contract_address = contract.to_string();
+        let admin = or_none(admin);
+        let ibc_port_id = or_none(ibc_port_id);
+        let code_history = history.join("\n");
+
+        println!(
+            "{}",
+            [
+                vars_format!(
+                    "Contract found!",
+                    contract_address,
+                    code_id,
+                    admin,
+                    creator,
+                    contract_label,
+                    ibc_port_id
+                ),
+                vars_format!("History", code_history),
+            ]
+            .concat()
+            .join("\n")
+        );
+
+        Ok(InfoResponse {
+            label: label.to_string(),
+            contract_address,
+            code_id,
+            admin,
+            creator,
+            contract_label,
+            ibc_port_id,
+            history,
+        })
+    })
+}
+
+fn history_entry_str(entry: &ContractCodeHistoryEntry) -> String {
+    let operation =
+        match ContractCodeHistoryOperationType::from_i32(entry.operation).unwrap_or_default() {
+            ContractCodeHistoryOperationType::Init => "instantiate",
+            ContractCodeHistoryOperationType::Migrate => "migrate",
+            ContractCodeHistoryOperationType::Genesis => "genesis",
+            ContractCodeHistoryOperationType::Unspecified => "unspecified",
+        };
+    let height = entry
+        .updated
+        .as_ref()
+        .map(|p| p.block_height.to_string())
+        .unwrap_or_else(|| "–".to_string());
+
+    format!(
+        "{operation} · code_id: {} · height: {height} · msg: {}",
+        entry.code_id,
+        String::from_utf8_lossy(&entry.msg)
+    )
+}
+
+fn or_none(value: String) -> String {
+    if value.is_empty() {
+        "none".to_string()
+    } else {
+        value
+    }
+}
+
+#[derive(Serialize)]
+pub struct InfoResponse {
+    pub label: String,
+    pub contract_address: String,
+    pub code_id: u64,
+    pub admin: String,
+    pub creator: String,
+    pub contract_label: String,
+    pub ibc_port_id: String,
+    pub history: Vec<String>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use cosmos_sdk_proto::cosmwasm::wasm::v1::AbsoluteTxPosition;
+
+    #[test]
+    fn history_entry_shows_operation_code_and_msg() {
+        let entry = ContractCodeHistoryEntry {
+            operation: ContractCodeHistoryOperationType::Migrate as i32,
+            code_id: 2,
+            updated: Some(AbsoluteTxPosition {
+                block_height: 120,
+                tx_index: 0,
+            }),
+            msg: br#"{"version":"2"}"#.to_vec(),
+        };
+
+        assert_eq!(
+            history_entry_str(&entry),
+            r#"migrate · code_id: 2 · height: 120 · msg: {"version":"2"}"#
+        );
+    }
+}
@@ -1,23 +1,29 @@
 use crate::attrs_format;
+use crate::modules::wasm::args::Instantiate2Args;
 use crate::modules::wasm::config::WasmConfig;
+use crate::support::authz::exec_on_behalf;
 use crate::support::coin::Coins;
-use crate::support::cosmos::ResponseValuePicker;
+use crate::support::cosmos::{ResponseValuePicker, TxOptions};
 use crate::support::future::block;
 use crate::support::gas::Gas;
 use crate::support::hooks::use_code_id;
 use crate::support::ops_response::OpResponseDisplay;
 use crate::support::permission::compute_admin;
-use crate::support::state::State;
+use crate::support::state::{Deployment, DeploymentKind, State};
 use crate::{framework::Context, support::cosmos::Client};
 use anyhow::Context as _;
-use anyhow::Result;
+use anyhow::{anyhow, Result};
+use cosmos_sdk_proto::cosmwasm::wasm::v1 as wasm;
 use cosmrs::cosmwasm::MsgInstantiateContract;
 use cosmrs::crypto::secp256k1::SigningKey;
-use cosmrs::tx::Msg;
+use cosmrs::tx::{MessageExt, Msg};
+use cosmrs::{AccountId, Any};
 use serde::Serialize;
 
 use std::{fs, vec};
 
+pub const MSG_INSTANTIATE_CONTRACT2_TYPE_URL: &str = "/cosmwasm.wasm.v1.MsgInstantiateContract2";
+
 #[allow(clippy::too_many_arguments)]
 pub fn instantiate<'a, Ctx: Context<'a, WasmConfig>>(
     ctx: &Ctx,
@@ -28,15 +34,16 @@ pub fn instantiate<'a, Ctx: Context<'a, WasmConfig>>(
     no_proposal_sync: bool,
     yes: bool,
     funds: Coins,
+    instantiate2_args: &Instantiate2Args,
     network: &str,
-    timeout_height: &u32,
+    granter: Option<&AccountId>,
+    tx_options: &TxOptions,
     gas: &Gas,
     signing_key: SigningKey,
 
     account_sequence: &Option<u64>,
 ) -> Result<InstantiateResponse> {
     let global_config = ctx.global_config()?;
-    let account_prefix = global_config.account_prefix().as_str();
 
     let network_info = global_config
         .networks()
@@ -44,56 +51,49 @@ pub fn instantiate<'a, Ctx: Context<'a, WasmConfig>>(
         .with_context(|| format!("Unable to find network config: {network}"))?
         .to_owned();
 
-    let client = Client::new(network_info.clone()).to_signing_client(signing_key, account_prefix);
-
-    let state = State::load_by_network(network_info.clone(), ctx.root()?)?;
+    let query_client = Client::new(network_info.clone(), &ctx.root()?, &global_config);
+    let client = query_client.to_signing_client(signing_key);
+    let signer = client.signer_account_id();
 
-    let code_id = use_code_id(
+    let msg_instantiate_contract = instantiate_msg(
         ctx,
-        network,
-        &network_info,
-        state,
         contract_name,
+        label,
+        raw,
+        admin,
         no_proposal_sync,
         yes,
+        true,
+        funds,
+        network,
+        granter.unwrap_or(&signer),
     )?;
-
-    let msg_instantiate_contract = MsgInstantiateContract {
-        sender: client.signer_account_id(),
-        admin: compute_admin(admin, client.signer_account_id())?,
+    let code_id = msg_instantiate_contract.code_id;
+    let deployment = Deployment::new(
+        DeploymentKind::Instantiate,
         code_id,
-        label: Some(label.to_string()),
-        msg: raw
-            .map(|s| s.as_bytes().to_vec())
-            .map(Ok)
-            .unwrap_or_else(|| {
-                let path = ctx
-                    .root()?
-                    .join("contracts")
-                    .join(contract_name)
-                    .join("instantiate-msgs")
-                    .join(format!("{label}.json"));
-                fs::read_to_string(&path)
-                    .with_context(|| {
-                        format!("Unable to instantiate with `{}`", path.to_string_lossy())
-                    })
-                    .map(|s| s.as_bytes().to_vec())
-            })?,
-        funds: funds.into(),
-    };
+        String::from_utf8_lossy(&msg_instantiate_contract.msg).to_string(),
+    )
+    .with_msg_file(
+        raw.is_none()
+            .then(|| format!("contracts/{contract_name}/instantiate-msgs/{label}.json")),
+    );
+    let msg = exec_on_behalf(
+        granter,
+        &signer,
+        instantiate_any(msg_instantiate_contract.clone(), instantiate2_args)?,
+    )?;
 
     block(async {
         let response = client
-            .sign_and_broadcast(
-                vec![msg_instantiate_contract.to_any().unwrap()],
-                gas,
-                "",
-                timeout_height,
-                account_sequence,
-            )
+            .sign_and_broadcast(vec![msg], gas, tx_options, account_sequence)
             .await?;
 
         let contract_address = response.pick("instantiate", "_contract_address");
+        let deployment = deployment
+            .clone()
+            .committed(&query_client, &response, &signer)
+            .await;
 
         let instantiate_response = InstantiateResponse {
             code_id,
@@ -115,6 +115,7 @@ pub fn instantiate<'a, Ctx: Context<'a, WasmConfig>>(
             ctx.root()?,
             &|s: &State| -> State {
                 s.update_address(network, contract_name, label, &contract_address)
+                    .record_deployment(network, contract_name, label, &deployment)
             },
         )?;
 
@@ -122,6 +123,101 @@ pub fn instantiate<'a, Ctx: Context<'a, WasmConfig>>(
     })
 }
 
+/// Build the `MsgInstantiateContract` for the contract's latest code id, sent by `sender`.
+/// A code id synced from proposal is only saved to state when `update_state` is set.
+#[allow(clippy::too_many_arguments)]
+pub fn instantiate_msg<'a, Ctx: Context<'a, WasmConfig>>(
+    ctx: &Ctx,
+    contract_name: &str,
+    label: &str,
+    raw: Option<&String>,
+    admin: Option<&String>,
+    no_proposal_sync: bool,
+    yes: bool,
+    update_state: bool,
+    funds: Coins,
+    network: &str,
+    sender: &AccountId,
+) -> Result<MsgInstantiateContract> {
+    let global_config = ctx.global_config()?;
+    let network_info = global_config
+        .networks()
+        .get(network)
+        .with_context(|| format!("Unable to find network config: {network}"))?
+        .to_owned();
+
+    let state = State::load_by_network(network_info.clone(), ctx.root()?)?;
+
+    let code_id = use_code_id(
+        ctx,
+        network,
+        &network_info,
+        state,
+        contract_name,
+        no_proposal_sync,
+        yes,
+        update_state,
+    )?;
+
+    Ok(MsgInstantiateContract {
+        sender: sender.clone(),
+        admin: compute_admin(admin, sender.clone())?,
+        code_id,
+        label: Some(label.to_string()),
+        msg: read_instantiate_msg(ctx, contract_name, label, raw)?,
+        funds: funds.into(),
+    })
+}
+
+/// `msg` as `MsgInstantiateContract2` when a salt is given, which instantiates the contract
+/// at the address predicted from its code checksum, sender and salt.
+pub fn instantiate_any(
+    msg: MsgInstantiateContract,
+    instantiate2_args: &Instantiate2Args,
+) -> Result<Any> {
+    let Some(salt) = instantiate2_args.salt()? else {
+        return msg.to_any().map_err(|e| anyhow!(e));
+    };
+
+    let msg: wasm::MsgInstantiateContract = msg.into();
+    Ok(Any {
+        type_url: MSG_INSTANTIATE_CONTRACT2_TYPE_URL.to_owned(),
+        value: wasm::MsgInstantiateContract2 {
+            sender: msg.sender,
+            admin: msg.admin,
+            code_id: msg.code_id,
+            label: msg.label,
+            msg: msg.msg,
+            funds: msg.funds,
+            salt,
+            fix_msg: instantiate2_args.fix_msg,
+        }
+        .to_bytes()?,
+    })
+}
+
+/// Instantiate msg from `raw` if given, otherwise from the contract's `instantiate-msgs/<label>.json`.
+pub fn read_instantiate_msg<'a, Ctx: Context<'a, WasmConfig>>(
+    ctx: &Ctx,
+    contract_name: &str,
+    label: &str,
+    raw: Option<&String>,
+) -> Result<Vec<u8>> {
+    raw.map(|s| s.as_bytes().to_vec())
+        .map(Ok)
+        .unwrap_or_else(|| {
+            let path = ctx
+                .root()?
+                .join("contracts")
+                .join(contract_name)
+                .join("instantiate-msgs")
+                .join(format!("{label}.json"));
+            fs::read_to_string(&path)
+                .with_context(|| format!("Unable to instantiate with `{}`", path.to_string_lossy()))
+                .map(|s| s.as_bytes().to_vec())
+        })
+}
+
 #[derive(Serialize)]
 pub struct InstantiateResponse {
     pub label: String,
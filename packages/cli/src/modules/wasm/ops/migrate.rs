@@ -1,20 +1,28 @@
 use crate::attrs_format;
 use crate::modules::wasm::config::WasmConfig;
-use crate::support::cosmos::ResponseValuePicker;
+use crate::support::authz::exec_on_behalf;
+use crate::support::cosmos::{ResponseValuePicker, TxOptions};
+use crate::support::cw2::{
+    local_contract_version, migration_violation, ContractVersion, CONTRACT_INFO_KEY,
+};
 use crate::support::future::block;
 use crate::support::gas::Gas;
 use crate::support::hooks::use_code_id;
 use crate::support::ops_response::OpResponseDisplay;
-use crate::support::state::State;
+use crate::support::state::{Deployment, DeploymentKind, State};
+use crate::support::storage_key::encode_hex;
 use crate::{framework::Context, support::cosmos::Client};
 
 use anyhow::Context as _;
-use anyhow::Result;
+use anyhow::{bail, Result};
+use console::style;
 use cosmos_sdk_proto::cosmwasm::wasm::v1::MsgMigrateContract;
 use cosmrs::crypto::secp256k1::SigningKey;
 use cosmrs::tx::MessageExt;
+use cosmrs::AccountId;
 use serde::Serialize;
 use std::fs;
+use std::path::Path;
 
 #[allow(clippy::too_many_arguments)]
 pub fn migrate<'a, Ctx: Context<'a, WasmConfig>>(
@@ -25,14 +33,15 @@ pub fn migrate<'a, Ctx: Context<'a, WasmConfig>>(
     no_proposal_sync: bool,
     yes: bool,
     network: &str,
-    timeout_height: &u32,
+    granter: Option<&AccountId>,
+    tx_options: &TxOptions,
     gas: &Gas,
     signing_key: SigningKey,
 
     account_sequence: &Option<u64>,
+    force: bool,
 ) -> Result<MigrateResponse> {
     let global_config = ctx.global_config()?;
-    let account_prefix = global_config.account_prefix().as_str();
 
     let network_info = global_config
         .networks()
@@ -40,7 +49,142 @@ pub fn migrate<'a, Ctx: Context<'a, WasmConfig>>(
         .with_context(|| format!("Unable to find network config: {network}"))?
         .to_owned();
 
-    let client = Client::new(network_info.clone()).to_signing_client(signing_key, account_prefix);
+    let query_client = Client::new(network_info.clone(), &ctx.root()?, &global_config);
+    let client = query_client.to_signing_client(signing_key);
+    let signer = client.signer_account_id();
+
+    let msg_migrate_contract = migrate_msg(
+        ctx,
+        contract_name,
+        label,
+        raw,
+        no_proposal_sync,
+        yes,
+        true,
+        network,
+        granter.unwrap_or(&signer),
+        force,
+    )?;
+    let msg = exec_on_behalf(granter, &signer, msg_migrate_contract.to_any().unwrap())?;
+    let deployment = Deployment::new(
+        DeploymentKind::Migrate,
+        msg_migrate_contract.code_id,
+        String::from_utf8_lossy(&msg_migrate_contract.msg).to_string(),
+    )
+    .with_msg_file(
+        raw.is_none()
+            .then(|| format!("contracts/{contract_name}/migrate-msgs/{label}.json")),
+    );
+
+    block(async {
+        let response = client
+            .sign_and_broadcast(vec![msg], gas, tx_options, account_sequence)
+            .await?;
+
+        let deployment = deployment
+            .clone()
+            .committed(&query_client, &response, &signer)
+            .await;
+
+        let contract_address = response.pick("migrate", "_contract_address");
+        let code_id = response.pick("migrate", "code_id");
+
+        let migrate_response = MigrateResponse {
+            code_id: code_id.parse()?,
+            contract_address: contract_address.clone(),
+            label: label.to_string(),
+            creator: msg_migrate_contract.sender.to_string(),
+        };
+
+        migrate_response.log();
+
+        State::update_state_file(
+            network_info.network_variant(),
+            ctx.root()?,
+            &|s: &State| -> State {
+                s.update_address(network, contract_name, label, &contract_address)
+                    .record_deployment(network, contract_name, label, &deployment)
+            },
+        )?;
+
+        Ok(migrate_response)
+    })
+}
+
+/// Check run before any migration is sent, showing how it changes the contract's code and version.
+/// Migrating to a different contract or to an older version than the local `contract_name`, per cw2,
+/// is refused unless `force`. Without `contract_name`, as for rollbacks, versions are only shown.
+pub fn check_migration(
+    client: &Client,
+    root: &Path,
+    contract_name: Option<&str>,
+    msg: &MsgMigrateContract,
+    force: bool,
+) -> Result<MigrationCheckResponse> {
+    block(async {
+        let old_code_id = client.contract_info(msg.contract.clone()).await?.code_id;
+        let old_checksum = encode_hex(&client.code_info(&old_code_id).await?.data_hash);
+        let new_checksum = encode_hex(&client.code_info(&msg.code_id).await?.data_hash);
+
+        let deployed = client
+            .query_raw(msg.contract.clone(), CONTRACT_INFO_KEY.to_vec())
+            .await?
+            .map(|raw| ContractVersion::from_slice(&raw))
+            .transpose()?;
+
+        let version = match (deployed, contract_name) {
+            (None, _) => "unknown, no cw2 contract info".to_string(),
+            (Some(deployed), None) => deployed.to_string(),
+            (Some(deployed), Some(contract_name)) => {
+                let local = local_contract_version(root, contract_name)?;
+                if let Some(violation) = migration_violation(&deployed, &local)? {
+                    if !force {
+                        bail!("Refusing to migrate, {violation}. Use `--force` to migrate anyway");
+                    }
+                    println!(
+                        "    {} {}",
+                        style("WARNING:").yellow().bold(),
+                        style(format!("{violation}, migrating anyway")).yellow()
+                    );
+                }
+                format!("{deployed} -> {local}")
+            }
+        };
+
+        let check_response = MigrationCheckResponse {
+            contract_address: msg.contract.clone(),
+            code_id: format!("{old_code_id} -> {}", msg.code_id),
+            checksum: format!("{old_checksum} -> {new_checksum}"),
+            version,
+        };
+        check_response.log();
+
+        Ok(check_response)
+    })
+}
+
+/// Build the `MsgMigrateContract` to the contract's latest code id, sent by `sender`,
+/// once it passes [`check_migration`].
+/// A code id synced from proposal is only saved to state when `update_state` is set.
+#[allow(clippy::too_many_arguments)]
+pub fn migrate_msg<'a, Ctx: Context<'a, WasmConfig>>(
+    ctx: &Ctx,
+    contract_name: &str,
+    label: &str,
+    raw: Option<&String>,
+    no_proposal_sync: bool,
+    yes: bool,
+    update_state: bool,
+    network: &str,
+    sender: &AccountId,
+    force: bool,
+) -> Result<MsgMigrateContract> {
+    let global_config = ctx.global_config()?;
+    let network_info = global_config
+        .networks()
+        .get(network)
+        .with_context(|| format!("Unable to find network config: {network}"))?
+        .to_owned();
 
     let state = State::load_by_network(network_info.clone(), ctx.root()?)?;
     let code_id = use_code_id(
@@ -51,6 +195,7 @@ pub fn migrate<'a, Ctx: Context<'a, WasmConfig>>(
         contract_name,
         no_proposal_sync,
         yes,
+        update_state,
     )?;
 
     let wasm_ref = state.get_ref(network, contract_name)?;
@@ -59,8 +204,8 @@ pub fn migrate<'a, Ctx: Context<'a, WasmConfig>>(
         .get(label)
         .with_context(|| format!("Unable to retrieve contract for {contract_name}:{label}"))?;
 
-    let msg_migrate_contract = MsgMigrateContract {
-        sender: client.signer_account_id().to_string(),
+    let msg = MsgMigrateContract {
+        sender: sender.to_string(),
         contract: contract.to_string(),
         code_id,
         msg: raw
@@ -79,39 +224,10 @@ pub fn migrate<'a, Ctx: Context<'a, WasmConfig>>(
             })?,
     };
 
-    block(async {
-        let response = client
-            .sign_and_broadcast(
-                vec![msg_migrate_contract.to_any().unwrap()],
-                gas,
-                "",
-                timeout_height,
-                account_sequence,
-            )
-            .await?;
-
-        let contract_address = response.pick("migrate", "_contract_address");
-        let code_id = response.pick("migrate", "code_id");
-
-        let migrate_response = MigrateResponse {
-            code_id: code_id.parse()?,
-            contract_address: contract_address.clone(),
-            label: label.to_string(),
-            creator: msg_migrate_contract.sender.to_string(),
-        };
-
-        migrate_response.log();
-
-        State::update_state_file(
-            network_info.network_variant(),
-            ctx.root()?,
-            &|s: &State| -> State {
-                s.update_address(network, contract_name, label, &contract_address)
-            },
-        )?;
+    let client = Client::new(network_info, &ctx.root()?, &global_config);
+    check_migration(&client, &ctx.root()?, Some(contract_name), &msg, force)?;
 
-        Ok(migrate_response)
-    })
+    Ok(msg)
 }
 
 #[derive(Serialize)]
@@ -130,3 +246,20 @@ impl OpResponseDisplay for MigrateResponse {
         attrs_format! { self | label, contract_address, code_id, creator }
     }
 }
+
+#[derive(Serialize)]
+pub struct MigrationCheckResponse {
+    pub contract_address: String,
+    pub code_id: String,
+    pub checksum: String,
+    pub version: String,
+}
+
+impl OpResponseDisplay for MigrationCheckResponse {
+    fn headline() -> &'static str {
+        "Migration checked"
+    }
+    fn attrs(&self) -> Vec<String> {
+        attrs_format! { self | contract_address, code_id, checksum, version }
+    }
+}
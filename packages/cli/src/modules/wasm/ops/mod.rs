@@ -1,23 +1,37 @@
+pub mod batch;
 pub mod build;
 pub mod clear_admin;
+pub mod code_info;
 pub mod deploy;
 pub mod execute;
+pub mod info;
 pub mod instantiate;
 pub mod migrate;
 pub mod new;
+pub mod predict_address;
 pub mod query;
+pub mod rollback;
 pub mod store_code;
 pub mod update_admin;
+pub mod update_instantiate_config;
 pub mod upgrade;
+pub mod verify;
 
+pub use batch::batch;
 pub use build::build;
 pub use clear_admin::clear_admin;
+pub use code_info::code_info;
 pub use deploy::deploy;
 pub use execute::execute;
+pub use info::info;
 pub use instantiate::instantiate;
 pub use migrate::migrate;
 pub use new::new;
+pub use predict_address::predict_address;
 pub use query::query;
+pub use rollback::rollback;
 pub use store_code::store_code;
 pub use update_admin::update_admin;
+pub use update_instantiate_config::update_instantiate_config;
 pub use upgrade::upgrade;
+pub use verify::verify;
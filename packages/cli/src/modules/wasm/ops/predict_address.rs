@@ -0,0 +1,101 @@
+use crate::attrs_format;
+use crate::framework::Context;
+use crate::modules::wasm::args::parse_salt;
+use crate::modules::wasm::config::WasmConfig;
+use crate::support::ops_response::OpResponseDisplay;
+use crate::support::state::State;
+use crate::support::storage_key::decode_hex;
+use crate::support::wasm::{predict_address as predict, read_wasm, wasm_checksum};
+use anyhow::{Context as _, Result};
+use cosmrs::AccountId;
+use serde::Serialize;
+
+use super::instantiate::read_instantiate_msg;
+
+/// Predict the address the contract gets when instantiated with `salt` by `creator`,
+/// from the local artifact unless `checksum` is given, and record it under `label`.
+#[allow(clippy::too_many_arguments)]
+pub fn predict_address<'a, Ctx: Context<'a, WasmConfig>>(
+    ctx: &Ctx,
+    contract_name: &str,
+    label: &str,
+    salt: &str,
+    fix_msg: bool,
+    raw: Option<&String>,
+    checksum: Option<&str>,
+    creator: &AccountId,
+    network: &str,
+) -> Result<PredictAddressResponse> {
+    let global_config = ctx.global_config()?;
+    let network_info = global_config
+        .networks()
+        .get(network)
+        .with_context(|| format!("Unable to find network config: {network}"))?
+        .to_owned();
+
+    let checksum = match checksum {
+        Some(checksum) => checksum.trim_start_matches("0x").to_lowercase(),
+        None => wasm_checksum(&read_wasm(
+            ctx.root()?,
+            contract_name.replace('-', "_").as_str(), // Handles file name mismatch
+            &false,
+        )?),
+    };
+    let msg = if fix_msg {
+        Some(read_instantiate_msg(ctx, contract_name, label, raw)?)
+    } else {
+        None
+    };
+
+    let contract_address = predict(
+        &decode_hex(&checksum)?,
+        creator,
+        &parse_salt(salt)?,
+        msg.as_deref(),
+    )?;
+
+    let state = State::update_state_file(
+        network_info.network_variant(),
+        ctx.root()?,
+        &|s: &State| -> State {
+            s.update_address(network, contract_name, label, contract_address.as_ref())
+        },
+    )?;
+    if !state
+        .get_ref(network, contract_name)
+        .is_ok_and(|wasm_ref| wasm_ref.addresses().contains_key(label))
+    {
+        println!(
+            "  `{contract_name}` has no code id on network `{network}` yet, the predicted address is not recorded"
+        );
+    }
+
+    let predict_address_response = PredictAddressResponse {
+        label: label.to_string(),
+        contract_address: contract_address.to_string(),
+        checksum,
+        creator: creator.to_string(),
+        salt: salt.to_string(),
+    };
+    predict_address_response.log();
+
+    Ok(predict_address_response)
+}
+
+#[derive(Serialize)]
+pub struct PredictAddressResponse {
+    pub label: String,
+    pub contract_address: String,
+    pub checksum: String,
+    pub creator: String,
+    pub salt: String,
+}
+
+impl OpResponseDisplay for PredictAddressResponse {
+    fn headline() -> &'static str {
+        "Contract address predicted!! 🎉"
+    }
+    fn attrs(&self) -> Vec<String> {
+        attrs_format! { self | label, contract_address, checksum, creator, salt }
+    }
+}
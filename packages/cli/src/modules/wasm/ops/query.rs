@@ -27,7 +27,7 @@ pub fn query<'a, Ctx: Context<'a, WasmConfig>>(
         .with_context(|| format!("Unable to find network config: {network}"))?
         .to_owned();
 
-    let client = Client::new(network_info.clone());
+    let client = Client::new(network_info.clone(), &ctx.root()?, &global_config);
     let state = State::load_by_network(network_info, ctx.root()?)?;
 
     let contract = state
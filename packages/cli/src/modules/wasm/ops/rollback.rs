@@ -0,0 +1,272 @@
+use crate::attrs_format;
+use crate::modules::wasm::config::WasmConfig;
+use crate::support::authz::exec_on_behalf;
+use crate::support::cosmos::{ResponseValuePicker, TxOptions};
+use crate::support::future::block;
+use crate::support::gas::Gas;
+use crate::support::ops_response::OpResponseDisplay;
+use crate::support::state::{Deployment, DeploymentKind, State};
+use crate::{framework::Context, support::cosmos::Client};
+
+use anyhow::Context as _;
+use anyhow::{anyhow, bail, Result};
+use cosmos_sdk_proto::cosmwasm::wasm::v1::{
+    ContractCodeHistoryEntry, ContractCodeHistoryOperationType, MsgMigrateContract,
+};
+use cosmrs::crypto::secp256k1::SigningKey;
+use cosmrs::tx::MessageExt;
+use cosmrs::AccountId;
+use serde::Serialize;
+
+use super::migrate::check_migration;
+
+/// Migrate the labeled contract back to the code id it ran before its current one.
+#[allow(clippy::too_many_arguments)]
+pub fn rollback<'a, Ctx: Context<'a, WasmConfig>>(
+    ctx: &Ctx,
+    contract_name: &str,
+    label: &str,
+    raw: Option<&String>,
+    network: &str,
+    granter: Option<&AccountId>,
+    tx_options: &TxOptions,
+    gas: &Gas,
+    signing_key: SigningKey,
+    account_sequence: &Option<u64>,
+) -> Result<RollbackResponse> {
+    let global_config = ctx.global_config()?;
+
+    let network_info = global_config
+        .networks()
+        .get(network)
+        .with_context(|| format!("Unable to find network config: {network}"))?
+        .to_owned();
+
+    let query_client = Client::new(network_info.clone(), &ctx.root()?, &global_config);
+    let client = query_client.to_signing_client(signing_key);
+    let signer = client.signer_account_id();
+
+    let msg_migrate_contract = rollback_msg(
+        ctx,
+        contract_name,
+        label,
+        raw,
+        network,
+        granter.unwrap_or(&signer),
+    )?;
+    let msg = exec_on_behalf(
+        granter,
+        &signer,
+        msg_migrate_contract.to_any().map_err(|e| anyhow!(e))?,
+    )?;
+    let deployment = Deployment::new(
+        DeploymentKind::Rollback,
+        msg_migrate_contract.code_id,
+        String::from_utf8_lossy(&msg_migrate_contract.msg).to_string(),
+    );
+
+    block(async {
+        let response = client
+            .sign_and_broadcast(vec![msg], gas, tx_options, account_sequence)
+            .await?;
+
+        let deployment = deployment
+            .clone()
+            .committed(&query_client, &response, &signer)
+            .await;
+
+        let rollback_response = RollbackResponse {
+            label: label.to_string(),
+            contract_address: msg_migrate_contract.contract.clone(),
+            code_id: response.pick("migrate", "code_id").parse()?,
+            creator: msg_migrate_contract.sender.clone(),
+        };
+
+        rollback_response.log();
+
+        State::update_state_file(
+            network_info.network_variant(),
+            ctx.root()?,
+            &|s: &State| -> State {
+                s.record_deployment(network, contract_name, label, &deployment)
+            },
+        )?;
+
+        Ok(rollback_response)
+    })
+}
+
+/// Build the `MsgMigrateContract` back to the labeled contract's previous code id, sent by `sender`.
+/// The previous code id comes from beaker's history of the label, or else from the contract's
+/// history on chain. Unless `raw` is given, the msg of the migration that introduced the previous
+/// code id is sent again.
+pub fn rollback_msg<'a, Ctx: Context<'a, WasmConfig>>(
+    ctx: &Ctx,
+    contract_name: &str,
+    label: &str,
+    raw: Option<&String>,
+    network: &str,
+    sender: &AccountId,
+) -> Result<MsgMigrateContract> {
+    let global_config = ctx.global_config()?;
+    let network_info = global_config
+        .networks()
+        .get(network)
+        .with_context(|| format!("Unable to find network config: {network}"))?
+        .to_owned();
+
+    let client = Client::new(network_info.clone(), &ctx.root()?, &global_config);
+    let wasm_ref =
+        State::load_by_network(network_info, ctx.root()?)?.get_ref(network, contract_name)?;
+    let contract = wasm_ref
+        .addresses()
+        .get(label)
+        .with_context(|| format!("Unable to retrieve contract for {contract_name}:{label}"))?;
+    let recorded = wasm_ref.history().get(label).cloned().unwrap_or_default();
+
+    let previous = block(async {
+        let current_code_id = client.contract_info(contract.to_string()).await?.code_id;
+
+        let previous = match rollback_target(&recorded, current_code_id) {
+            Some(previous) => previous,
+            None => {
+                let history = client
+                    .contract_history(contract.to_string())
+                    .await?
+                    .iter()
+                    .filter_map(history_entry_deployment)
+                    .collect::<Vec<_>>();
+                rollback_target(&history, current_code_id).with_context(|| {
+                    format!("No code id to roll back {contract_name}:{label} to, it has only run code {current_code_id}")
+                })?
+            }
+        };
+
+        anyhow::Ok(previous)
+    })?;
+    let (code_id, migrate_msg) = previous;
+
+    let msg = match (raw, migrate_msg) {
+        (Some(raw), _) => raw.to_string(),
+        (None, Some(migrate_msg)) => migrate_msg,
+        (None, None) => {
+            bail!("No migrate msg found for {contract_name}:{label}, use `--raw` to provide one")
+        }
+    };
+
+    let msg = MsgMigrateContract {
+        sender: sender.to_string(),
+        contract: contract.to_string(),
+        code_id,
+        msg: msg.into_bytes(),
+    };
+    // rolling back is a deliberate downgrade, so versions are only shown
+    check_migration(&client, &ctx.root()?, None, &msg, false)?;
+
+    Ok(msg)
+}
+
+/// Code id run before `current_code_id` in `history`, along with the msg of the migration that
+/// introduced it. There is no msg to reuse when it was introduced by instantiation.
+fn rollback_target(history: &[Deployment], current_code_id: u64) -> Option<(u64, Option<String>)> {
+    let target = deployed(history)
+        .into_iter()
+        .rev()
+        .find(|d| *d.code_id() != current_code_id)?;
+    let migrate_msg = (target.kind() == &DeploymentKind::Migrate).then(|| target.msg().to_string());
+
+    Some((*target.code_id(), migrate_msg))
+}
+
+/// Deployments still in effect after the rollbacks of `history`, oldest first. A rollback drops the
+/// deployments made after the one that introduced the code id it rolled back to.
+fn deployed(history: &[Deployment]) -> Vec<&Deployment> {
+    history.iter().fold(vec![], |mut deployed, d| {
+        if d.kind() == &DeploymentKind::Rollback {
+            if let Some(i) = deployed.iter().rposition(|p| p.code_id() == d.code_id()) {
+                deployed.truncate(i + 1);
+                return deployed;
+            }
+        }
+        deployed.push(d);
+        deployed
+    })
+}
+
+/// Deployment of an entry of the contract's history on chain.
+fn history_entry_deployment(entry: &ContractCodeHistoryEntry) -> Option<Deployment> {
+    let kind = match ContractCodeHistoryOperationType::from_i32(entry.operation)? {
+        ContractCodeHistoryOperationType::Init | ContractCodeHistoryOperationType::Genesis => {
+            DeploymentKind::Instantiate
+        }
+        ContractCodeHistoryOperationType::Migrate => DeploymentKind::Migrate,
+        ContractCodeHistoryOperationType::Unspecified => return None,
+    };
+
+    Some(Deployment::new(
+        kind,
+        entry.code_id,
+        String::from_utf8_lossy(&entry.msg).to_string(),
+    ))
+}
+
+#[derive(Serialize)]
+pub struct RollbackResponse {
+    pub label: String,
+    pub contract_address: String,
+    pub code_id: u64,
+    pub creator: String,
+}
+
+impl OpResponseDisplay for RollbackResponse {
+    fn headline() -> &'static str {
+        "Contract rolled back successfully!! 🎉"
+    }
+    fn attrs(&self) -> Vec<String> {
+        attrs_format! { self | label, contract_address, code_id, creator }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn deployment(kind: DeploymentKind, code_id: u64, msg: &str) -> Deployment {
+        Deployment::new(kind, code_id, msg.to_string())
+    }
+
+    #[test]
+    fn rollback_to_previous_code_id() {
+        let history = vec![
+            deployment(DeploymentKind::Instantiate, 1, r#"{"count":0}"#),
+            deployment(DeploymentKind::Migrate, 2, r#"{"v":2}"#),
+            deployment(DeploymentKind::Migrate, 3, r#"{"v":3}"#),
+        ];
+
+        assert_eq!(
+            rollback_target(&history, 3),
+            Some((2, Some(r#"{"v":2}"#.to_string())))
+        );
+        assert_eq!(rollback_target(&history[..2], 2), Some((1, None)));
+        assert_eq!(rollback_target(&history[..1], 1), None);
+        assert_eq!(rollback_target(&[], 1), None);
+    }
+
+    #[test]
+    fn rollback_twice_keeps_going_back() {
+        let history = vec![
+            deployment(DeploymentKind::Instantiate, 1, r#"{"count":0}"#),
+            deployment(DeploymentKind::Migrate, 2, r#"{"v":2}"#),
+            deployment(DeploymentKind::Migrate, 3, r#"{"v":3}"#),
+            deployment(DeploymentKind::Rollback, 2, r#"{"v":2}"#),
+        ];
+
+        assert_eq!(rollback_target(&history, 2), Some((1, None)));
+    }
+
+    #[test]
+    fn rollback_without_recorded_migration() {
+        let history = vec![deployment(DeploymentKind::Instantiate, 1, "{}")];
+        assert_eq!(rollback_target(&history, 2), Some((1, None)));
+    }
+}
@@ -1,18 +1,21 @@
+use super::code_info::access_config_str;
 use crate::attrs_format;
 use crate::modules::wasm::WasmConfig;
-use crate::support::cosmos::ResponseValuePicker;
+use crate::support::cosmos::{ResponseValuePicker, TxOptions};
 use crate::support::future::block;
 use crate::support::gas::Gas;
 use crate::support::ops_response::OpResponseDisplay;
-use crate::support::permission::compute_instantiate_permission;
-use crate::support::state::State;
-use crate::support::wasm::read_wasm;
+use crate::support::permission::{compute_instantiate_permission, is_same_permission};
+use crate::support::state::{State, WasmRef};
+use crate::support::storage_key::encode_hex;
+use crate::support::wasm::{read_wasm, wasm_checksum};
 use crate::{framework::Context, support::cosmos::Client};
 use anyhow::Context as _;
 use anyhow::Result;
-use cosmrs::cosmwasm::MsgStoreCode;
+use cosmrs::cosmwasm::{AccessConfig, MsgStoreCode};
 use cosmrs::crypto::secp256k1::SigningKey;
 use cosmrs::tx::Msg;
+use cosmrs::AccountId;
 use serde::Serialize;
 
 #[allow(clippy::too_many_arguments)]
@@ -23,12 +26,12 @@ pub fn store_code<'a, Ctx: Context<'a, WasmConfig>>(
     no_wasm_opt: &bool,
     permit_instantiate_only: &Option<String>,
     gas: &Gas,
-    timeout_height: &u32,
+    tx_options: &TxOptions,
     signing_key: SigningKey,
     account_sequence: &Option<u64>,
+    force_store: &bool,
 ) -> Result<StoreCodeResponse> {
     let global_config = ctx.global_config()?;
-    let account_prefix = global_config.account_prefix().as_str();
 
     let network_info = global_config
         .networks()
@@ -36,31 +39,60 @@ pub fn store_code<'a, Ctx: Context<'a, WasmConfig>>(
         .with_context(|| format!("Unable to find network config: {network}"))?
         .to_owned();
 
-    let client = Client::new(network_info.clone()).to_signing_client(signing_key, account_prefix);
+    let query_client = Client::new(network_info.clone(), &ctx.root()?, &global_config);
+    let client = query_client.to_signing_client(signing_key);
 
-    let instantiate_permission =
-        compute_instantiate_permission(permit_instantiate_only, client.signer_account_id())?;
-
-    let wasm = read_wasm(
-        ctx.root()?,
-        contract_name.replace('-', "_").as_str(), // Handles file name mismatch
+    let msg_store_code = store_code_msg(
+        ctx,
+        contract_name,
         no_wasm_opt,
+        permit_instantiate_only,
+        &client.signer_account_id(),
     )?;
-    let msg_store_code = MsgStoreCode {
-        sender: client.signer_account_id(),
-        wasm_byte_code: wasm,
-        instantiate_permission: instantiate_permission.clone(),
-    }
-    .to_any()
-    .unwrap();
+    let instantiate_permission = msg_store_code.instantiate_permission.clone();
+    let checksum = wasm_checksum(&msg_store_code.wasm_byte_code);
+    let instantiate_permission_str =
+        access_config_str(instantiate_permission.clone().map(Into::into).as_ref());
 
     block(async {
+        if !*force_store {
+            let recorded_code_ids = State::load_by_network(network_info.clone(), ctx.root()?)
+                .and_then(|s| s.get_ref(network, contract_name))
+                .map(|wasm_ref| recorded_code_ids(&wasm_ref))
+                .unwrap_or_default();
+
+            let stored_code_id = stored_code_id(
+                &query_client,
+                &recorded_code_ids,
+                &checksum,
+                &instantiate_permission,
+            )
+            .await;
+
+            if let Some(code_id) = stored_code_id {
+                println!(
+                    "  Code with checksum `{checksum}` is already stored as code id {code_id}, skipping store code (use `--force-store` to store it anyway)"
+                );
+                State::update_state_file(
+                    network_info.network_variant(),
+                    ctx.root()?,
+                    &|s: &State| -> State {
+                        s.update_code(network, contract_name, &code_id, &checksum)
+                    },
+                )?;
+
+                return Ok(StoreCodeResponse {
+                    code_id,
+                    instantiate_permission: instantiate_permission_str,
+                });
+            }
+        }
+
         let response = client
             .sign_and_broadcast(
-                vec![msg_store_code],
+                vec![msg_store_code.to_any().unwrap()],
                 gas,
-                "",
-                timeout_height,
+                tx_options,
                 account_sequence,
             )
             .await?;
@@ -68,15 +100,13 @@ pub fn store_code<'a, Ctx: Context<'a, WasmConfig>>(
         let code_id: u64 = response.pick("store_code", "code_id").parse()?;
         let store_code_response = StoreCodeResponse {
             code_id,
-            instantiate_permission: instantiate_permission
-                .map(|p| format!("only_addresses | {:?}", p.addresses))
-                .unwrap_or_else(|| "–".to_string()),
+            instantiate_permission: instantiate_permission_str,
         };
 
         State::update_state_file(
             network_info.network_variant(),
             ctx.root()?,
-            &|s: &State| -> State { s.update_code_id(network, contract_name, &code_id) },
+            &|s: &State| -> State { s.update_code(network, contract_name, &code_id, &checksum) },
         )?;
         store_code_response.log();
 
@@ -84,6 +114,74 @@ pub fn store_code<'a, Ctx: Context<'a, WasmConfig>>(
     })
 }
 
+/// Code ids recorded in state for the contract, latest first.
+fn recorded_code_ids(wasm_ref: &WasmRef) -> Vec<u64> {
+    let mut code_ids: Vec<u64> = wasm_ref
+        .code_id()
+        .iter()
+        .copied()
+        .chain(
+            wasm_ref
+                .history()
+                .values()
+                .flat_map(|deployments| deployments.iter().map(|d| *d.code_id())),
+        )
+        .collect();
+    code_ids.sort_unstable_by(|a, b| b.cmp(a));
+    code_ids.dedup();
+    code_ids
+}
+
+/// First of the recorded code ids that is still on chain with the given checksum and instantiate
+/// permission. Codes that can not be queried are skipped, eg. after a local chain reset.
+async fn stored_code_id(
+    client: &Client,
+    recorded_code_ids: &[u64],
+    checksum: &str,
+    instantiate_permission: &Option<AccessConfig>,
+) -> Option<u64> {
+    for code_id in recorded_code_ids {
+        if let Ok(code_info) = client.code_info(code_id).await {
+            if encode_hex(&code_info.data_hash) == checksum
+                && is_same_permission(
+                    instantiate_permission,
+                    code_info.instantiate_permission.as_ref(),
+                )
+            {
+                return Some(*code_id);
+            }
+        }
+    }
+    None
+}
+
+/// Build the `MsgStoreCode` for the contract's wasm, sent by `sender`.
+pub fn store_code_msg<'a, Ctx: Context<'a, WasmConfig>>(
+    ctx: &Ctx,
+    contract_name: &str,
+    no_wasm_opt: &bool,
+    permit_instantiate_only: &Option<String>,
+    sender: &AccountId,
+) -> Result<MsgStoreCode> {
+    let instantiate_permission = compute_instantiate_permission(
+        permit_instantiate_only,
+        sender.clone(),
+        &ctx.global_config()?,
+    )?;
+
+    let wasm = read_wasm(
+        ctx.root()?,
+        contract_name.replace('-', "_").as_str(), // Handles file name mismatch
+        no_wasm_opt,
+    )?;
+
+    Ok(MsgStoreCode {
+        sender: sender.clone(),
+        wasm_byte_code: wasm,
+        instantiate_permission,
+    })
+}
+
 #[derive(Serialize)]
 pub struct StoreCodeResponse {
     pub code_id: u64,
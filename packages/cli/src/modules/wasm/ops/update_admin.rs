@@ -1,5 +1,6 @@
 use crate::attrs_format;
 use crate::modules::wasm::WasmConfig;
+use crate::support::authz::exec_on_behalf;
 use crate::support::future::block;
 use crate::support::gas::Gas;
 use crate::support::ops_response::OpResponseDisplay;
@@ -9,10 +10,14 @@ use serde::Serialize;
 
 use crate::support::state::State;
 
-use crate::{framework::Context, support::cosmos::Client};
+use crate::{
+    framework::Context,
+    support::cosmos::{Client, TxOptions},
+};
 
 use anyhow::Result;
 use cosmrs::tx::MessageExt;
+use cosmrs::AccountId;
 
 use cosmrs::crypto::secp256k1::SigningKey;
 
@@ -23,13 +28,13 @@ pub fn update_admin<'a, Ctx: Context<'a, WasmConfig>>(
     label: &str,
     network: &str,
     new_admin: &str,
+    granter: Option<&AccountId>,
     gas: &Gas,
-    timeout_height: &u32,
+    tx_options: &TxOptions,
     signing_key: SigningKey,
     account_sequence: &Option<u64>,
 ) -> Result<UpdateAdminResponse> {
     let global_config = ctx.global_config()?;
-    let account_prefix = global_config.account_prefix().as_str();
 
     let network_info = global_config
         .networks()
@@ -37,37 +42,29 @@ pub fn update_admin<'a, Ctx: Context<'a, WasmConfig>>(
         .with_context(|| format!("Unable to find network config: {network}"))?
         .to_owned();
 
-    let client = Client::new(network_info.clone()).to_signing_client(signing_key, account_prefix);
+    let client = Client::new(network_info.clone(), &ctx.root()?, &global_config)
+        .to_signing_client(signing_key);
+    let signer = client.signer_account_id();
 
-    let state = State::load_by_network(network_info, ctx.root()?)?;
-    let wasm_ref = state.get_ref(network, contract_name)?;
-    let contract = wasm_ref
-        .addresses()
-        .get(label)
-        .with_context(|| format!("Unable to retrieve contract for {contract_name}:{label}"))?;
-
-    let msg_update_admin = MsgUpdateAdmin {
-        sender: client.signer_account_id().to_string(),
-        new_admin: new_admin.to_string(),
-        contract: contract.to_string(),
-    }
-    .to_any()
-    .unwrap();
+    let msg_update_admin = update_admin_msg(
+        ctx,
+        contract_name,
+        label,
+        network,
+        new_admin,
+        granter.unwrap_or(&signer),
+    )?;
+    let contract = msg_update_admin.contract.clone();
+    let msg = exec_on_behalf(granter, &signer, msg_update_admin.to_any().unwrap())?;
 
     block(async {
         let _response = client
-            .sign_and_broadcast(
-                vec![msg_update_admin],
-                gas,
-                "",
-                timeout_height,
-                account_sequence,
-            )
+            .sign_and_broadcast(vec![msg], gas, tx_options, account_sequence)
             .await?;
 
         let update_admin_response = UpdateAdminResponse {
             new_admin: new_admin.to_string(),
-            contract: contract.to_string(),
+            contract,
         };
 
         update_admin_response.log();
@@ -76,6 +73,36 @@ pub fn update_admin<'a, Ctx: Context<'a, WasmConfig>>(
     })
 }
 
+/// Build the `MsgUpdateAdmin` for the labeled contract, sent by `sender`.
+pub fn update_admin_msg<'a, Ctx: Context<'a, WasmConfig>>(
+    ctx: &Ctx,
+    contract_name: &str,
+    label: &str,
+    network: &str,
+    new_admin: &str,
+    sender: &AccountId,
+) -> Result<MsgUpdateAdmin> {
+    let network_info = ctx
+        .global_config()?
+        .networks()
+        .get(network)
+        .with_context(|| format!("Unable to find network config: {network}"))?
+        .to_owned();
+
+    let state = State::load_by_network(network_info, ctx.root()?)?;
+    let wasm_ref = state.get_ref(network, contract_name)?;
+    let contract = wasm_ref
+        .addresses()
+        .get(label)
+        .with_context(|| format!("Unable to retrieve contract for {contract_name}:{label}"))?;
+
+    Ok(MsgUpdateAdmin {
+        sender: sender.to_string(),
+        new_admin: new_admin.to_string(),
+        contract: contract.to_string(),
+    })
+}
+
 #[derive(Serialize)]
 pub struct UpdateAdminResponse {
     pub contract: String,
@@ -0,0 +1,146 @@
+use crate::attrs_format;
+use crate::modules::wasm::WasmConfig;
+use crate::support::future::block;
+use crate::support::gas::Gas;
+use crate::support::ops_response::OpResponseDisplay;
+use crate::support::permission::compute_instantiate_permission;
+use crate::support::wasm::{MsgUpdateInstantiateConfig, MSG_UPDATE_INSTANTIATE_CONFIG_TYPE_URL};
+
+use anyhow::Context as _;
+
+use cosmrs::cosmwasm::{AccessConfig, AccessType};
+use cosmrs::tx::MessageExt;
+use cosmrs::{AccountId, Any};
+use serde::Serialize;
+
+use crate::support::state::State;
+
+use crate::{
+    framework::Context,
+    support::cosmos::{Client, TxOptions},
+};
+
+use anyhow::Result;
+
+use cosmrs::crypto::secp256k1::SigningKey;
+
+use super::code_info::access_config_str;
+
+#[allow(clippy::too_many_arguments)]
+pub fn update_instantiate_config<'a, Ctx: Context<'a, WasmConfig>>(
+    ctx: &Ctx,
+    contract_name: &str,
+    code_id: Option<u64>,
+    permit_instantiate_only: &Option<String>,
+    network: &str,
+    gas: &Gas,
+    tx_options: &TxOptions,
+    signing_key: SigningKey,
+    account_sequence: &Option<u64>,
+) -> Result<UpdateInstantiateConfigResponse> {
+    let global_config = ctx.global_config()?;
+
+    let network_info = global_config
+        .networks()
+        .get(network)
+        .with_context(|| format!("Unable to find network config: {network}"))?
+        .to_owned();
+
+    let client =
+        Client::new(network_info, &ctx.root()?, &global_config).to_signing_client(signing_key);
+
+    let msg = update_instantiate_config_msg(
+        ctx,
+        contract_name,
+        code_id,
+        permit_instantiate_only,
+        network,
+        &client.signer_account_id(),
+    )?;
+
+    block(async {
+        let _response = client
+            .sign_and_broadcast(
+                vec![update_instantiate_config_any(&msg)?],
+                gas,
+                tx_options,
+                account_sequence,
+            )
+            .await?;
+
+        let update_instantiate_config_response = UpdateInstantiateConfigResponse {
+            code_id: msg.code_id,
+            instantiate_permission: access_config_str(msg.new_instantiate_permission.as_ref()),
+        };
+
+        update_instantiate_config_response.log();
+
+        Ok(update_instantiate_config_response)
+    })
+}
+
+/// Build the `MsgUpdateInstantiateConfig` of `code_id`, or of the contract's code id, sent by `sender`.
+/// Without `permit_instantiate_only`, the code can be instantiated by everybody.
+pub fn update_instantiate_config_msg<'a, Ctx: Context<'a, WasmConfig>>(
+    ctx: &Ctx,
+    contract_name: &str,
+    code_id: Option<u64>,
+    permit_instantiate_only: &Option<String>,
+    network: &str,
+    sender: &AccountId,
+) -> Result<MsgUpdateInstantiateConfig> {
+    let global_config = ctx.global_config()?;
+
+    let code_id = match code_id {
+        Some(code_id) => code_id,
+        None => {
+            let network_info = global_config
+                .networks()
+                .get(network)
+                .with_context(|| format!("Unable to find network config: {network}"))?
+                .to_owned();
+
+            let state = State::load_by_network(network_info, ctx.root()?)?;
+            state
+                .get_ref(network, contract_name)?
+                .code_id()
+                .with_context(|| format!("Unable to retrieve code_id for {contract_name}"))?
+        }
+    };
+
+    let new_instantiate_permission =
+        compute_instantiate_permission(permit_instantiate_only, sender.clone(), &global_config)?
+            .unwrap_or(AccessConfig {
+                permission: AccessType::Everybody,
+                addresses: vec![],
+            });
+
+    Ok(MsgUpdateInstantiateConfig {
+        sender: sender.to_string(),
+        code_id,
+        new_instantiate_permission: Some(new_instantiate_permission.into()),
+    })
+}
+
+/// `MsgUpdateInstantiateConfig` as `Any`, built from its type url as cosmrs does not provide the msg.
+pub fn update_instantiate_config_any(msg: &MsgUpdateInstantiateConfig) -> Result<Any> {
+    Ok(Any {
+        type_url: MSG_UPDATE_INSTANTIATE_CONFIG_TYPE_URL.to_owned(),
+        value: msg.to_bytes()?,
+    })
+}
+
+#[derive(Serialize)]
+pub struct UpdateInstantiateConfigResponse {
+    pub code_id: u64,
+    pub instantiate_permission: String,
+}
+
+impl OpResponseDisplay for UpdateInstantiateConfigResponse {
+    fn headline() -> &'static str {
+        "Instantiate config updated successfully!! 🎉"
+    }
+    fn attrs(&self) -> Vec<String> {
+        attrs_format! { self | code_id, instantiate_permission }
+    }
+}
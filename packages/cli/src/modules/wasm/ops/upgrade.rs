@@ -1,5 +1,6 @@
 use crate::framework::Context;
 use crate::modules::wasm::WasmConfig;
+use crate::support::cosmos::TxOptions;
 use crate::support::gas::Gas;
 use anyhow::Result;
 
@@ -17,13 +18,15 @@ pub fn upgrade<'a, Ctx: Context<'a, WasmConfig>>(
     raw: Option<&String>,
     permit_instantiate_only: &Option<String>,
     network: &str,
-    timeout_height: &u32,
+    tx_options: &TxOptions,
     gas: &Gas,
     store_code_signing_key: SigningKey,
     instantiate_signing_key: SigningKey,
     no_rebuild: &bool,
     no_wasm_opt: &bool,
     account_sequence: &Option<u64>,
+    force_store: &bool,
+    force: &bool,
 ) -> Result<MigrateResponse> {
     if !*no_rebuild {
         build(ctx, no_wasm_opt, &false)?;
@@ -35,9 +38,10 @@ pub fn upgrade<'a, Ctx: Context<'a, WasmConfig>>(
         no_wasm_opt,
         permit_instantiate_only,
         gas,
-        timeout_height,
+        tx_options,
         store_code_signing_key,
         account_sequence,
+        force_store,
     )?;
     migrate(
         ctx,
@@ -48,9 +52,12 @@ pub fn upgrade<'a, Ctx: Context<'a, WasmConfig>>(
         true,
         true,
         network,
-        timeout_height,
+        None,
+        tx_options,
         gas,
         instantiate_signing_key,
-        &account_sequence.map(|seq| seq + 1),
+        // following sequence is tracked by the signing client
+        &None,
+        *force,
     )
 }
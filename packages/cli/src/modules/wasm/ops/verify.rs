@@ -0,0 +1,232 @@
+use crate::modules::wasm::config::WasmConfig;
+use crate::support::future::block;
+use crate::support::state::State;
+use crate::support::wasm::{read_wasm, wasm_checksum};
+use crate::vars_format;
+use crate::{framework::Context, support::cosmos::Client};
+use anyhow::{bail, Context as _, Result};
+use serde::Serialize;
+use std::fs;
+
+/// Compare the code deployed for the contract on each network with the local artifact
+/// and `artifacts/checksums.txt`. Fails if any deployment runs different code.
+pub fn verify<'a, Ctx: Context<'a, WasmConfig>>(
+    ctx: &Ctx,
+    contract_name: &str,
+    networks: &[String],
+) -> Result<VerifyResponse> {
+    let global_config = ctx.global_config()?;
+    let root = ctx.root()?;
+
+    let checksum = wasm_checksum(&read_wasm(root.clone(), contract_name, &false)?);
+
+    let checksums_path = root.join("artifacts").join("checksums.txt");
+    let checksums_txt = match fs::read_to_string(&checksums_path) {
+        Ok(content) => match checksums_txt_entry(&content, &format!("{contract_name}.wasm")) {
+            Some(c) if c == checksum => "matches local artifact".to_string(),
+            Some(c) => format!("differs from local artifact: {c}"),
+            None => format!("no entry for `{contract_name}.wasm`"),
+        },
+        Err(_) => "not found".to_string(),
+    };
+
+    let mut network_names = global_config
+        .networks()
+        .keys()
+        .filter(|n| networks.is_empty() || networks.contains(n))
+        .cloned()
+        .collect::<Vec<_>>();
+    network_names.sort();
+
+    let mut deployments = vec![];
+    for network in network_names {
+        let network_info = global_config
+            .networks()
+            .get(&network)
+            .with_context(|| format!("Unable to find network config: {network}"))?
+            .to_owned();
+
+        let Ok(wasm_ref) = State::load_by_network(network_info.clone(), root.clone())
+            .and_then(|state| state.get_ref(&network, contract_name))
+        else {
+            continue;
+        };
+
+        let addresses = wasm_ref
+            .addresses()
+            .iter()
+            .map(|(label, address)| format!("{label}: {address}"))
+            .collect::<Vec<_>>();
+
+        let Some(code_id) = *wasm_ref.code_id() else {
+            deployments.push(Deployment {
+                network,
+                code_id: None,
+                checksum: None,
+                status: "no code id in state".to_string(),
+                addresses,
+            });
+            continue;
+        };
+
+        let client = Client::new(network_info, &root, &global_config);
+        let (deployed_checksum, status) = match block(client.code_wasm(&code_id)) {
+            Ok(wasm) => {
+                let deployed_checksum = wasm_checksum(&wasm);
+                let status = if deployed_checksum == checksum {
+                    "matches local artifact"
+                } else {
+                    "differs from local artifact"
+                };
+                (Some(deployed_checksum), status.to_string())
+            }
+            Err(e) => (None, format!("unable to query code: {e}")),
+        };
+
+        deployments.push(Deployment {
+            network,
+            code_id: Some(code_id),
+            checksum: deployed_checksum,
+            status,
+            addresses,
+        });
+    }
+
+    let drift = drift(&deployments);
+
+    let artifact = format!("{contract_name}.wasm");
+    let mut lines = vars_format!("Local artifact", artifact, checksum, checksums_txt);
+    for Deployment {
+        network,
+        code_id,
+        checksum,
+        status,
+        addresses,
+    } in deployments.iter()
+    {
+        let code_id = code_id.map_or_else(|| "–".to_string(), |id| id.to_string());
+        let checksum = checksum.as_deref().unwrap_or("–");
+        let addresses = addresses.join("\n");
+        lines.extend(vars_format!(
+            format!("Network `{network}`"),
+            code_id,
+            checksum,
+            status,
+            addresses
+        ));
+    }
+    if !drift.is_empty() {
+        let networks = drift.join("\n");
+        lines.extend(vars_format!("Drift between networks", networks));
+    }
+    println!("{}", lines.join("\n"));
+
+    let differing = deployments
+        .iter()
+        .filter(|d| d.checksum.as_ref().is_some_and(|c| c != &checksum))
+        .map(|d| d.network.as_str())
+        .collect::<Vec<_>>();
+    if !differing.is_empty() {
+        bail!(
+            "Deployed code of `{contract_name}` differs from the local artifact on: {}",
+            differing.join(", ")
+        );
+    }
+
+    Ok(VerifyResponse {
+        contract_name: contract_name.to_string(),
+        checksum,
+        checksums_txt,
+        deployments,
+        drift,
+    })
+}
+
+/// Checksum of `file` in the optimizer's `checksums.txt`, formatted as `<checksum>  <file>` lines.
+fn checksums_txt_entry(content: &str, file: &str) -> Option<String> {
+    content.lines().find_map(|line| {
+        let mut parts = line.split_whitespace();
+        match (parts.next(), parts.next()) {
+            (Some(checksum), Some(f)) if f == file => Some(checksum.to_string()),
+            _ => None,
+        }
+    })
+}
+
+/// `<network>: <checksum>` of each deployment, when not all networks run the same code.
+fn drift(deployments: &[Deployment]) -> Vec<String> {
+    let deployed = deployments
+        .iter()
+        .filter_map(|d| Some((d.network.as_str(), d.checksum.as_ref()?)))
+        .collect::<Vec<_>>();
+
+    if deployed.windows(2).all(|w| w[0].1 == w[1].1) {
+        return vec![];
+    }
+
+    deployed
+        .iter()
+        .map(|(network, checksum)| format!("{network}: {checksum}"))
+        .collect()
+}
+
+#[derive(Serialize)]
+pub struct Deployment {
+    pub network: String,
+    pub code_id: Option<u64>,
+    pub checksum: Option<String>,
+    pub status: String,
+    pub addresses: Vec<String>,
+}
+
+#[derive(Serialize)]
+pub struct VerifyResponse {
+    pub contract_name: String,
+    pub checksum: String,
+    pub checksums_txt: String,
+    pub deployments: Vec<Deployment>,
+    pub drift: Vec<String>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn deployment(network: &str, checksum: Option<&str>) -> Deployment {
+        Deployment {
+            network: network.to_string(),
+            code_id: Some(1),
+            checksum: checksum.map(str::to_string),
+            status: String::new(),
+            addresses: vec![],
+        }
+    }
+
+    #[test]
+    fn checksums_txt_entry_of_file() {
+        let content = "aaa  counter.wasm\nbbb  multiplier.wasm\n";
+
+        assert_eq!(
+            checksums_txt_entry(content, "multiplier.wasm"),
+            Some("bbb".to_string())
+        );
+        assert_eq!(checksums_txt_entry(content, "other.wasm"), None);
+    }
+
+    #[test]
+    fn drift_only_when_networks_run_different_code() {
+        assert!(drift(&[
+            deployment("local", Some("aaa")),
+            deployment("testnet", Some("aaa")),
+            deployment("mainnet", None),
+        ])
+        .is_empty());
+        assert_eq!(
+            drift(&[
+                deployment("local", Some("aaa")),
+                deployment("testnet", Some("bbb")),
+            ]),
+            vec!["local: aaa".to_string(), "testnet: bbb".to_string()]
+        );
+    }
+}
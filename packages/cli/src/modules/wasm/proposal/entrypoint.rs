@@ -1,19 +1,50 @@
-use anyhow::Result;
+use anyhow::{bail, Context as _, Result};
 use clap::Subcommand;
+use cosmrs::{AccountId, Any};
 use serde::Deserialize;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
 
 use crate::{
     framework::Context,
-    modules::wasm::{args::BaseTxArgs, WasmConfig},
-    support::gas::Gas,
+    modules::{
+        tx::{
+            self,
+            ops::{DryRunResponse, GenerateOnlyResponse},
+        },
+        wasm::{
+            args::{BaseTxArgs, Instantiate2Args},
+            ops as wasm_ops, WasmConfig,
+        },
+    },
+    support::{
+        cosmos::Client,
+        gas::Gas,
+        gov::{resolve_gov_version, GovVersion},
+        state::ProposalKind,
+    },
 };
 
 use super::{
-    ops::{propose::ProposeStoreCodeResponse, query::QueryProposalResponse, vote::VoteResponse},
+    ops::{
+        deposit::DepositResponse,
+        propose::{ProposeResponse, ProposeStoreCodeResponse},
+        query::QueryProposalResponse,
+        verify::VerifyResponse,
+        vote::VoteResponse,
+        wait::WaitResponse,
+    },
     proposal_struct::StoreCodeProposal,
 };
 
+/// Command run once a proposal passes
+#[derive(clap::ValueEnum, Clone, Copy, Debug, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum FollowUp {
+    Instantiate,
+    Migrate,
+}
+
 #[derive(Subcommand, Debug, Deserialize)]
 pub enum ProposalCmd {
     /// Proposal for storing .wasm on chain for later initialization
@@ -21,7 +52,52 @@ pub enum ProposalCmd {
         /// Name of the contract to store
         contract_name: String,
 
-        /// Restricting the code to be able to instantiate/migrate only by given address, no restriction by default
+        /// Restricting the code to be able to instantiate/migrate only by given addresses, no restriction by default.
+        /// Takes `nobody` or a comma separated list of `signer`, predefined account names and addresses
+        #[clap(long)]
+        permit_instantiate_only: Option<String>,
+
+        /// Path to proposal file, could be either yaml / toml format.
+        #[clap(short, long)]
+        proposal: Option<PathBuf>,
+
+        #[clap(flatten)]
+        #[serde(flatten)]
+        store_code_proposal: StoreCodeProposal,
+
+        /// Gov module version, detected from the chain by default
+        #[clap(long, value_enum)]
+        gov_version: Option<GovVersion>,
+
+        #[clap(flatten)]
+        #[serde(flatten)]
+        base_tx_args: BaseTxArgs,
+    },
+    /// Proposal for storing .wasm on chain and instantiating it once the proposal passes
+    StoreAndInstantiate {
+        /// Name of the contract to store and instantiate
+        contract_name: String,
+
+        /// Label for the instantiated contract for later reference
+        #[clap(short, long, default_value = "default")]
+        #[serde(default = "default_value::label")]
+        label: String,
+
+        /// Raw json string to use as instantiate msg
+        #[clap(short, long)]
+        raw: Option<String>,
+
+        /// Specifying admin required for contract migration.
+        /// Use "signer" for setting the proposer as admin.
+        #[clap(long)]
+        admin: Option<String>,
+
+        /// Funds to send to instantiated contract
+        #[clap(short, long)]
+        funds: Option<String>,
+
+        /// Restricting the code to be able to instantiate/migrate only by given addresses, no restriction by default.
+        /// Takes `nobody` or a comma separated list of `signer`, predefined account names and addresses
         #[clap(long)]
         permit_instantiate_only: Option<String>,
 
@@ -33,23 +109,321 @@ pub enum ProposalCmd {
         #[serde(flatten)]
         store_code_proposal: StoreCodeProposal,
 
+        /// Gov module version, detected from the chain by default
+        #[clap(long, value_enum)]
+        gov_version: Option<GovVersion>,
+
+        #[clap(flatten)]
+        #[serde(flatten)]
+        base_tx_args: BaseTxArgs,
+    },
+    /// Proposal for instantiating the contract's stored code
+    Instantiate {
+        /// Name of the contract to instantiate
+        contract_name: String,
+
+        /// Label for the instantiated contract for later reference
+        #[clap(short, long, default_value = "default")]
+        #[serde(default = "default_value::label")]
+        label: String,
+
+        /// Raw json string to use as instantiate msg
+        #[clap(short, long)]
+        raw: Option<String>,
+
+        /// Specifying admin required for contract migration.
+        /// Use "signer" for setting the proposer as admin.
+        #[clap(long)]
+        admin: Option<String>,
+
+        /// Funds to send to instantiated contract
+        #[clap(short, long)]
+        funds: Option<String>,
+
+        /// Skip the check for proposal's updated code_id
+        #[clap(long)]
+        #[serde(default)]
+        no_proposal_sync: bool,
+
+        /// Agree to all prompts
+        #[clap(short, long)]
+        #[serde(default)]
+        yes: bool,
+
+        /// Path to proposal file, could be either yaml / toml format.
+        #[clap(short, long)]
+        proposal: Option<PathBuf>,
+
+        #[clap(flatten)]
+        #[serde(flatten)]
+        store_code_proposal: StoreCodeProposal,
+
+        /// Gov module version, detected from the chain by default
+        #[clap(long, value_enum)]
+        gov_version: Option<GovVersion>,
+
+        #[clap(flatten)]
+        #[serde(flatten)]
+        base_tx_args: BaseTxArgs,
+    },
+    /// Proposal for migrating the contract to its stored code
+    Migrate {
+        /// Name of the contract to migrate
+        contract_name: String,
+
+        /// Label of the instantiated contract
+        #[clap(short, long, default_value = "default")]
+        #[serde(default = "default_value::label")]
+        label: String,
+
+        /// Raw json string to use as migrate msg
+        #[clap(short, long)]
+        raw: Option<String>,
+
+        /// Skip the check for proposal's updated code_id
+        #[clap(long)]
+        #[serde(default)]
+        no_proposal_sync: bool,
+
+        /// Agree to all prompts
+        #[clap(short, long)]
+        #[serde(default)]
+        yes: bool,
+
+        /// Propose even if, per cw2, the contract would become a different contract or be downgraded
+        #[clap(long)]
+        #[serde(default)]
+        force: bool,
+
+        /// Path to proposal file, could be either yaml / toml format.
+        #[clap(short, long)]
+        proposal: Option<PathBuf>,
+
+        #[clap(flatten)]
+        #[serde(flatten)]
+        store_code_proposal: StoreCodeProposal,
+
+        /// Gov module version, detected from the chain by default
+        #[clap(long, value_enum)]
+        gov_version: Option<GovVersion>,
+
+        #[clap(flatten)]
+        #[serde(flatten)]
+        base_tx_args: BaseTxArgs,
+    },
+    /// Proposal for updating the admin that can migrate the contract
+    UpdateAdmin {
+        /// Name of the contract
+        contract_name: String,
+
+        /// Label of the instantiated contract
+        #[clap(short, long, default_value = "default")]
+        #[serde(default = "default_value::label")]
+        label: String,
+
+        /// Address of new admin
+        #[clap(long)]
+        new_admin: String,
+
+        /// Path to proposal file, could be either yaml / toml format.
+        #[clap(short, long)]
+        proposal: Option<PathBuf>,
+
+        #[clap(flatten)]
+        #[serde(flatten)]
+        store_code_proposal: StoreCodeProposal,
+
+        /// Gov module version, detected from the chain by default
+        #[clap(long, value_enum)]
+        gov_version: Option<GovVersion>,
+
+        #[clap(flatten)]
+        #[serde(flatten)]
+        base_tx_args: BaseTxArgs,
+    },
+    /// Proposal for clearing the admin so no one can migrate the contract
+    ClearAdmin {
+        /// Name of the contract
+        contract_name: String,
+
+        /// Label of the instantiated contract
+        #[clap(short, long, default_value = "default")]
+        #[serde(default = "default_value::label")]
+        label: String,
+
+        /// Path to proposal file, could be either yaml / toml format.
+        #[clap(short, long)]
+        proposal: Option<PathBuf>,
+
+        #[clap(flatten)]
+        #[serde(flatten)]
+        store_code_proposal: StoreCodeProposal,
+
+        /// Gov module version, detected from the chain by default
+        #[clap(long, value_enum)]
+        gov_version: Option<GovVersion>,
+
+        #[clap(flatten)]
+        #[serde(flatten)]
+        base_tx_args: BaseTxArgs,
+    },
+    /// Proposal for pinning codes in the wasm cache for cheaper execution
+    PinCodes {
+        /// Name of the contract
+        contract_name: String,
+
+        /// Code ids to pin, eg. `1,2`. The contract's stored code id by default
+        #[clap(long, value_delimiter = ',')]
+        #[serde(default)]
+        code_ids: Vec<u64>,
+
+        /// Path to proposal file, could be either yaml / toml format.
+        #[clap(short, long)]
+        proposal: Option<PathBuf>,
+
+        #[clap(flatten)]
+        #[serde(flatten)]
+        store_code_proposal: StoreCodeProposal,
+
+        /// Gov module version, detected from the chain by default
+        #[clap(long, value_enum)]
+        gov_version: Option<GovVersion>,
+
+        #[clap(flatten)]
+        #[serde(flatten)]
+        base_tx_args: BaseTxArgs,
+    },
+    /// Proposal for unpinning codes from the wasm cache
+    UnpinCodes {
+        /// Name of the contract
+        contract_name: String,
+
+        /// Code ids to unpin, eg. `1,2`. The contract's stored code id by default
+        #[clap(long, value_delimiter = ',')]
+        #[serde(default)]
+        code_ids: Vec<u64>,
+
+        /// Path to proposal file, could be either yaml / toml format.
+        #[clap(short, long)]
+        proposal: Option<PathBuf>,
+
+        #[clap(flatten)]
+        #[serde(flatten)]
+        store_code_proposal: StoreCodeProposal,
+
+        /// Gov module version, detected from the chain by default
+        #[clap(long, value_enum)]
+        gov_version: Option<GovVersion>,
+
         #[clap(flatten)]
         #[serde(flatten)]
         base_tx_args: BaseTxArgs,
     },
     /// Vote for proposal
     Vote {
-        /// Name of the contract to store
-        contract_name: String,
+        /// Name of the contract whose store code proposal to vote for
+        #[clap(required_unless_present = "proposal_id")]
+        contract_name: Option<String>,
+
+        /// Id of the proposal to vote for, instead of the contract's store code proposal
+        #[clap(long, conflicts_with = "contract_name")]
+        proposal_id: Option<u64>,
 
-        /// Vote option, one of: yes, no, no_with_veto, abstain
+        /// Vote option, one of: yes, no, no_with_veto, abstain.
+        /// Weighted options are separated by comma, eg. `yes=0.6,no=0.4`
         #[clap(short, long)]
         option: String,
 
+        /// Gov module version, detected from the chain by default
+        #[clap(long, value_enum)]
+        gov_version: Option<GovVersion>,
+
+        #[clap(flatten)]
+        #[serde(flatten)]
+        base_tx_args: BaseTxArgs,
+    },
+    /// Deposit to proposal
+    Deposit {
+        /// Name of the contract whose store code proposal to deposit to
+        #[clap(required_unless_present = "proposal_id")]
+        contract_name: Option<String>,
+
+        /// Id of the proposal to deposit to, instead of the contract's store code proposal
+        #[clap(long, conflicts_with = "contract_name")]
+        proposal_id: Option<u64>,
+
+        /// Amount to deposit, eg. `1000uosmo`. Tops up to the minimum deposit by default
+        #[clap(long)]
+        amount: Option<String>,
+
+        /// Gov module version, detected from the chain by default
+        #[clap(long, value_enum)]
+        gov_version: Option<GovVersion>,
+
         #[clap(flatten)]
         #[serde(flatten)]
         base_tx_args: BaseTxArgs,
     },
+    /// Wait for the contract's code upload proposal to pass, then optionally instantiate or migrate
+    Wait {
+        /// Name of the contract whose proposal to wait for
+        contract_name: String,
+
+        /// Command to run with the stored code once the proposal passes
+        #[clap(long, value_enum)]
+        then: Option<FollowUp>,
+
+        /// Label of the contract to instantiate or migrate
+        #[clap(short, long, default_value = "default")]
+        #[serde(default = "default_value::label")]
+        label: String,
+
+        /// Raw json string to use as instantiate / migrate msg
+        #[clap(short, long)]
+        raw: Option<String>,
+
+        /// Specifying admin required for contract migration.
+        /// Use "signer" for setting tx signer as admin.
+        /// Use bech32 address (eg. "osmo1cyyzpxplxdzkeea7kwsydadg87357qnahakaks") for custom admin.
+        #[clap(long)]
+        admin: Option<String>,
+
+        /// Funds to send to instantiated contract
+        #[clap(short, long)]
+        funds: Option<String>,
+
+        /// Seconds between each poll of the proposal
+        #[clap(long, default_value = "10")]
+        #[serde(default = "default_value::interval")]
+        interval: u64,
+
+        /// Gov module version, detected from the chain by default
+        #[clap(long, value_enum)]
+        gov_version: Option<GovVersion>,
+
+        #[clap(flatten)]
+        #[serde(flatten)]
+        base_tx_args: BaseTxArgs,
+    },
+    /// Rebuild the contracts and compare their checksums with the code stored by a proposal
+    Verify {
+        /// Id of the proposal to verify
+        proposal_id: u64,
+
+        /// Compare with the existing artifacts instead of rebuilding them
+        #[clap(long)]
+        #[serde(default)]
+        no_rebuild: bool,
+
+        /// Name of the network to query, the actual endpoint / chain-id are defined in config
+        #[clap(short, long, default_value = "local")]
+        #[serde(default = "default_value::network")]
+        network: String,
+
+        /// Gov module version, detected from the chain by default
+        #[clap(long, value_enum)]
+        gov_version: Option<GovVersion>,
+    },
     Query {
         #[clap(subcommand)]
         cmd: ProposalQueryCmd,
@@ -66,13 +440,156 @@ pub enum ProposalQueryCmd {
         #[clap(short, long, default_value = "local")]
         #[serde(default = "default_value::network")]
         network: String,
+
+        /// Gov module version, detected from the chain by default
+        #[clap(long, value_enum)]
+        gov_version: Option<GovVersion>,
     },
 }
 
+impl ProposalCmd {
+    /// Common tx args of the commands that produce a transaction
+    fn tx_args(&self) -> Option<&BaseTxArgs> {
+        match self {
+            ProposalCmd::StoreCode { base_tx_args, .. }
+            | ProposalCmd::StoreAndInstantiate { base_tx_args, .. }
+            | ProposalCmd::Instantiate { base_tx_args, .. }
+            | ProposalCmd::Migrate { base_tx_args, .. }
+            | ProposalCmd::UpdateAdmin { base_tx_args, .. }
+            | ProposalCmd::ClearAdmin { base_tx_args, .. }
+            | ProposalCmd::PinCodes { base_tx_args, .. }
+            | ProposalCmd::UnpinCodes { base_tx_args, .. }
+            | ProposalCmd::Vote { base_tx_args, .. }
+            | ProposalCmd::Deposit { base_tx_args, .. } => Some(base_tx_args),
+            ProposalCmd::Wait { .. } | ProposalCmd::Verify { .. } | ProposalCmd::Query { .. } => {
+                None
+            }
+        }
+    }
+
+    fn gov_version(&self) -> Option<GovVersion> {
+        match self {
+            ProposalCmd::StoreCode { gov_version, .. }
+            | ProposalCmd::StoreAndInstantiate { gov_version, .. }
+            | ProposalCmd::Instantiate { gov_version, .. }
+            | ProposalCmd::Migrate { gov_version, .. }
+            | ProposalCmd::UpdateAdmin { gov_version, .. }
+            | ProposalCmd::ClearAdmin { gov_version, .. }
+            | ProposalCmd::PinCodes { gov_version, .. }
+            | ProposalCmd::UnpinCodes { gov_version, .. }
+            | ProposalCmd::Vote { gov_version, .. }
+            | ProposalCmd::Deposit { gov_version, .. }
+            | ProposalCmd::Wait { gov_version, .. }
+            | ProposalCmd::Verify { gov_version, .. } => *gov_version,
+            ProposalCmd::Query { .. } => None,
+        }
+    }
+
+    /// Proposal details from the proposal file if given, otherwise from the args
+    fn proposal_details(&self) -> Result<StoreCodeProposal> {
+        match self {
+            ProposalCmd::StoreCode {
+                proposal,
+                store_code_proposal,
+                ..
+            }
+            | ProposalCmd::StoreAndInstantiate {
+                proposal,
+                store_code_proposal,
+                ..
+            }
+            | ProposalCmd::Instantiate {
+                proposal,
+                store_code_proposal,
+                ..
+            }
+            | ProposalCmd::Migrate {
+                proposal,
+                store_code_proposal,
+                ..
+            }
+            | ProposalCmd::UpdateAdmin {
+                proposal,
+                store_code_proposal,
+                ..
+            }
+            | ProposalCmd::ClearAdmin {
+                proposal,
+                store_code_proposal,
+                ..
+            }
+            | ProposalCmd::PinCodes {
+                proposal,
+                store_code_proposal,
+                ..
+            }
+            | ProposalCmd::UnpinCodes {
+                proposal,
+                store_code_proposal,
+                ..
+            } => match proposal {
+                Some(p) => read_proposal_file(p),
+                None => Ok(store_code_proposal.clone()),
+            },
+            ProposalCmd::Vote { .. }
+            | ProposalCmd::Deposit { .. }
+            | ProposalCmd::Wait { .. }
+            | ProposalCmd::Verify { .. }
+            | ProposalCmd::Query { .. } => unimplemented!(),
+        }
+    }
+
+    /// Contract and kind the submitted proposal is recorded under in the state
+    fn proposal_ref(&self) -> (&str, ProposalKind) {
+        match self {
+            ProposalCmd::StoreCode { contract_name, .. } => {
+                (contract_name, ProposalKind::StoreCode)
+            }
+            ProposalCmd::StoreAndInstantiate { contract_name, .. } => {
+                (contract_name, ProposalKind::StoreAndInstantiate)
+            }
+            ProposalCmd::Instantiate { contract_name, .. } => {
+                (contract_name, ProposalKind::Instantiate)
+            }
+            ProposalCmd::Migrate { contract_name, .. } => (contract_name, ProposalKind::Migrate),
+            ProposalCmd::UpdateAdmin { contract_name, .. } => {
+                (contract_name, ProposalKind::UpdateAdmin)
+            }
+            ProposalCmd::ClearAdmin { contract_name, .. } => {
+                (contract_name, ProposalKind::ClearAdmin)
+            }
+            ProposalCmd::PinCodes { contract_name, .. } => (contract_name, ProposalKind::PinCodes),
+            ProposalCmd::UnpinCodes { contract_name, .. } => {
+                (contract_name, ProposalKind::UnpinCodes)
+            }
+            ProposalCmd::Vote { .. }
+            | ProposalCmd::Deposit { .. }
+            | ProposalCmd::Wait { .. }
+            | ProposalCmd::Verify { .. }
+            | ProposalCmd::Query { .. } => unimplemented!(),
+        }
+    }
+
+    fn is_generate_only(&self) -> bool {
+        self.tx_args()
+            .is_some_and(|args| args.generate_only.is_some())
+    }
+
+    fn is_dry_run(&self) -> bool {
+        self.tx_args().is_some_and(|args| args.dry_run)
+    }
+}
+
 mod default_value {
     pub fn network() -> String {
         "local".to_string()
     }
+    pub fn label() -> String {
+        "default".to_string()
+    }
+    pub fn interval() -> u64 {
+        10
+    }
 }
 
 pub fn execute<'a, Ctx: Context<'a, WasmConfig>>(
@@ -80,12 +597,299 @@ pub fn execute<'a, Ctx: Context<'a, WasmConfig>>(
     cmd: &ProposalCmd,
 ) -> Result<(), anyhow::Error> {
     match cmd {
+        cmd if cmd.is_generate_only() => generate_only(ctx, cmd).map(|_| ()),
+        cmd if cmd.is_dry_run() => dry_run(ctx, cmd).map(|_| ()),
         cmd @ ProposalCmd::StoreCode { .. } => store_code(ctx, cmd).map(|_| ()),
         cmd @ ProposalCmd::Query { .. } => query(ctx, cmd).map(|_| ()),
         cmd @ ProposalCmd::Vote { .. } => vote(ctx, cmd).map(|_| ()),
+        cmd @ ProposalCmd::Deposit { .. } => deposit(ctx, cmd).map(|_| ()),
+        cmd @ ProposalCmd::Wait { .. } => wait(ctx, cmd).map(|_| ()),
+        cmd @ ProposalCmd::Verify { .. } => verify(ctx, cmd).map(|_| ()),
+        cmd => propose(ctx, cmd).map(|_| ()),
     }
 }
 
+pub(crate) fn generate_only<'a>(
+    ctx: impl Context<'a, WasmConfig>,
+    cmd: &ProposalCmd,
+) -> Result<GenerateOnlyResponse> {
+    let base_tx_args = cmd.tx_args().unwrap_or_else(|| unimplemented!());
+    let BaseTxArgs {
+        network,
+        signer_args,
+        gas_args,
+        account_sequence,
+        generate_only,
+        ..
+    }: &BaseTxArgs = base_tx_args;
+
+    let global_conf = ctx.global_config()?;
+    let sender = signer_args.account_id(&global_conf)?;
+
+    tx::ops::generate_only(
+        &ctx,
+        vec![cmd_msg(
+            &ctx,
+            cmd,
+            chain_gov_version(&ctx, network, cmd.gov_version())?,
+            network,
+            &sender,
+        )?],
+        &sender,
+        network,
+        &Gas::from_args(
+            gas_args,
+            global_conf.gas_price(),
+            global_conf.gas_adjustment(),
+        )?,
+        &base_tx_args.tx_options()?,
+        account_sequence,
+        None,
+        generate_only.as_ref().unwrap(),
+    )
+}
+
+pub(crate) fn dry_run<'a>(
+    ctx: impl Context<'a, WasmConfig>,
+    cmd: &ProposalCmd,
+) -> Result<DryRunResponse> {
+    let base_tx_args = cmd.tx_args().unwrap_or_else(|| unimplemented!());
+    let BaseTxArgs {
+        network,
+        signer_args,
+        gas_args,
+        account_sequence,
+        ..
+    }: &BaseTxArgs = base_tx_args;
+
+    let global_conf = ctx.global_config()?;
+    let sender = signer_args.account_id(&global_conf)?;
+
+    tx::ops::dry_run(
+        &ctx,
+        vec![cmd_msg(
+            &ctx,
+            cmd,
+            chain_gov_version(&ctx, network, cmd.gov_version())?,
+            network,
+            &sender,
+        )?],
+        &sender,
+        network,
+        &Gas::from_args(
+            gas_args,
+            global_conf.gas_price(),
+            global_conf.gas_adjustment(),
+        )?,
+        &base_tx_args.tx_options()?,
+        account_sequence,
+    )
+}
+
+/// Message of the command sent by `sender`.
+/// The state is left untouched on `--generate-only` and `--dry-run`.
+fn cmd_msg<'a, Ctx: Context<'a, WasmConfig>>(
+    ctx: &Ctx,
+    cmd: &ProposalCmd,
+    gov_version: GovVersion,
+    network: &str,
+    sender: &AccountId,
+) -> Result<Any> {
+    use super::ops::propose_wasm::*;
+
+    let update_state = !(cmd.is_generate_only() || cmd.is_dry_run());
+
+    Ok(match cmd {
+        ProposalCmd::StoreCode {
+            contract_name,
+            permit_instantiate_only,
+            ..
+        } => super::ops::propose::propose_store_code_msg(
+            ctx,
+            contract_name,
+            &cmd.proposal_details()?,
+            gov_version,
+            permit_instantiate_only,
+            sender,
+        )?,
+        ProposalCmd::StoreAndInstantiate {
+            contract_name,
+            label,
+            raw,
+            admin,
+            funds,
+            permit_instantiate_only,
+            ..
+        } => propose_store_and_instantiate_msg(
+            ctx,
+            contract_name,
+            label,
+            raw.as_ref(),
+            admin.as_ref(),
+            funds.as_deref().try_into()?,
+            permit_instantiate_only,
+            &cmd.proposal_details()?,
+            gov_version,
+            sender,
+        )?,
+        ProposalCmd::Instantiate {
+            contract_name,
+            label,
+            raw,
+            admin,
+            funds,
+            no_proposal_sync,
+            yes,
+            ..
+        } => propose_instantiate_msg(
+            ctx,
+            contract_name,
+            label,
+            raw.as_ref(),
+            admin.as_ref(),
+            *no_proposal_sync,
+            *yes,
+            update_state,
+            funds.as_deref().try_into()?,
+            &cmd.proposal_details()?,
+            gov_version,
+            network,
+            sender,
+        )?,
+        ProposalCmd::Migrate {
+            contract_name,
+            label,
+            raw,
+            no_proposal_sync,
+            yes,
+            force,
+            ..
+        } => propose_migrate_msg(
+            ctx,
+            contract_name,
+            label,
+            raw.as_ref(),
+            *no_proposal_sync,
+            *yes,
+            update_state,
+            &cmd.proposal_details()?,
+            gov_version,
+            network,
+            sender,
+            *force,
+        )?,
+        ProposalCmd::UpdateAdmin {
+            contract_name,
+            label,
+            new_admin,
+            ..
+        } => propose_update_admin_msg(
+            ctx,
+            contract_name,
+            label,
+            new_admin,
+            &cmd.proposal_details()?,
+            gov_version,
+            network,
+            sender,
+        )?,
+        ProposalCmd::ClearAdmin {
+            contract_name,
+            label,
+            ..
+        } => propose_clear_admin_msg(
+            ctx,
+            contract_name,
+            label,
+            &cmd.proposal_details()?,
+            gov_version,
+            network,
+            sender,
+        )?,
+        ProposalCmd::PinCodes {
+            contract_name,
+            code_ids,
+            ..
+        }
+        | ProposalCmd::UnpinCodes {
+            contract_name,
+            code_ids,
+            ..
+        } => propose_pin_codes_msg(
+            ctx,
+            contract_name,
+            code_ids,
+            matches!(cmd, ProposalCmd::UnpinCodes { .. }),
+            &cmd.proposal_details()?,
+            gov_version,
+            network,
+            sender,
+        )?,
+        ProposalCmd::Vote {
+            contract_name,
+            proposal_id,
+            option,
+            ..
+        } => super::ops::vote::vote_msg(
+            ctx,
+            contract_name.as_deref(),
+            *proposal_id,
+            option,
+            gov_version,
+            network,
+            sender,
+        )?,
+        ProposalCmd::Deposit {
+            contract_name,
+            proposal_id,
+            amount,
+            ..
+        } => super::ops::deposit::deposit_msg(
+            ctx,
+            contract_name.as_deref(),
+            *proposal_id,
+            amount.as_deref(),
+            gov_version,
+            network,
+            sender,
+        )?,
+        ProposalCmd::Wait { .. } | ProposalCmd::Verify { .. } | ProposalCmd::Query { .. } => {
+            unimplemented!()
+        }
+    })
+}
+
+/// `gov_version` if given, otherwise the latest version supported by the network.
+fn chain_gov_version<'a, Ctx: Context<'a, WasmConfig>>(
+    ctx: &Ctx,
+    network: &str,
+    gov_version: Option<GovVersion>,
+) -> Result<GovVersion> {
+    let global_config = ctx.global_config()?;
+    let network_info = global_config
+        .networks()
+        .get(network)
+        .with_context(|| format!("Unable to find network config: {network}"))?
+        .to_owned();
+
+    let client = Client::new(network_info, &ctx.root()?, &global_config);
+    resolve_gov_version(&client, gov_version)
+}
+
+fn read_proposal_file(p: &Path) -> Result<StoreCodeProposal> {
+    let proposal_str = std::fs::read_to_string(p)?;
+    let extention_error_msg = "Extension must be one of `yaml`, `yml` or `toml`";
+    let ext = p.extension().expect(extention_error_msg);
+    let store_code_proposal: StoreCodeProposal = if ext == "yaml" || ext == "yml" {
+        serde_yaml::from_str(proposal_str.as_str())?
+    } else if ext == "toml" {
+        toml::from_str(proposal_str.as_str())?
+    } else {
+        panic!("{}", extention_error_msg);
+    };
+    Ok(store_code_proposal)
+}
+
 pub(crate) fn store_code<'a>(
     ctx: impl Context<'a, WasmConfig>,
     cmd: &ProposalCmd,
@@ -94,48 +898,23 @@ pub(crate) fn store_code<'a>(
         ProposalCmd::StoreCode {
             contract_name,
             permit_instantiate_only,
-            proposal,
-            store_code_proposal,
+            gov_version,
             base_tx_args,
+            ..
         } => {
-            let proposal = if let Some(p) = proposal {
-                let proposal_str = std::fs::read_to_string(p)?;
-                let extention_error_msg = "Extension must be one of `yaml`, `yml` or `toml`";
-                let ext = p.extension().expect(extention_error_msg);
-                let store_code_proposal: StoreCodeProposal = if ext == "yaml" || ext == "yml" {
-                    serde_yaml::from_str(proposal_str.as_str())?
-                } else if ext == "toml" {
-                    toml::from_str(proposal_str.as_str())?
-                } else {
-                    panic!("{}", extention_error_msg);
-                };
-                Some(store_code_proposal)
-            } else {
-                None
-            };
-
-            let StoreCodeProposal {
-                title,
-                deposit,
-                unpin_code,
-                description,
-            } = proposal.as_ref().unwrap_or(store_code_proposal);
-
             let BaseTxArgs {
                 network,
                 signer_args,
                 gas_args,
-                timeout_height,
                 account_sequence,
+                ..
             }: &BaseTxArgs = base_tx_args;
 
             super::ops::propose_store_code(
                 &ctx,
                 contract_name,
-                title.as_str(),
-                description.as_str(),
-                deposit.as_ref().map(|s| s.as_str()).try_into()?,
-                *unpin_code,
+                &cmd.proposal_details()?,
+                *gov_version,
                 network,
                 {
                     let global_conf = ctx.global_config()?;
@@ -146,7 +925,7 @@ pub(crate) fn store_code<'a>(
                     )?
                 },
                 permit_instantiate_only,
-                timeout_height,
+                &base_tx_args.tx_options()?,
                 signer_args.private_key(&ctx.global_config()?)?,
                 account_sequence,
             )
@@ -155,6 +934,41 @@ pub(crate) fn store_code<'a>(
     }
 }
 
+/// Submit the wasm proposal of `cmd` other than store code.
+pub(crate) fn propose<'a>(
+    ctx: impl Context<'a, WasmConfig>,
+    cmd: &ProposalCmd,
+) -> Result<ProposeResponse> {
+    let base_tx_args = cmd.tx_args().unwrap_or_else(|| unimplemented!());
+    let BaseTxArgs {
+        network,
+        signer_args,
+        gas_args,
+        account_sequence,
+        ..
+    }: &BaseTxArgs = base_tx_args;
+
+    let global_conf = ctx.global_config()?;
+    let (contract_name, kind) = cmd.proposal_ref();
+
+    super::ops::propose(
+        &ctx,
+        contract_name,
+        kind,
+        cmd.gov_version(),
+        network,
+        &Gas::from_args(
+            gas_args,
+            global_conf.gas_price(),
+            global_conf.gas_adjustment(),
+        )?,
+        &base_tx_args.tx_options()?,
+        signer_args.private_key(&global_conf)?,
+        account_sequence,
+        |gov_version, proposer| cmd_msg(&ctx, cmd, gov_version, network, proposer),
+    )
+}
+
 pub(crate) fn vote<'a>(
     ctx: impl Context<'a, WasmConfig>,
     cmd: &ProposalCmd,
@@ -162,21 +976,69 @@ pub(crate) fn vote<'a>(
     match cmd {
         ProposalCmd::Vote {
             contract_name,
+            proposal_id,
             option,
+            gov_version,
             base_tx_args,
         } => {
             let BaseTxArgs {
                 network,
                 signer_args,
                 gas_args,
-                timeout_height,
                 account_sequence,
+                ..
             }: &BaseTxArgs = base_tx_args;
 
             super::ops::vote(
                 &ctx,
-                contract_name,
+                contract_name.as_deref(),
+                *proposal_id,
                 option,
+                *gov_version,
+                network,
+                {
+                    let global_conf = ctx.global_config()?;
+                    &Gas::from_args(
+                        gas_args,
+                        global_conf.gas_price(),
+                        global_conf.gas_adjustment(),
+                    )?
+                },
+                &base_tx_args.tx_options()?,
+                signer_args.private_key(&ctx.global_config()?)?,
+                account_sequence,
+            )
+        }
+        _ => unimplemented!(),
+    }
+}
+
+pub(crate) fn deposit<'a>(
+    ctx: impl Context<'a, WasmConfig>,
+    cmd: &ProposalCmd,
+) -> Result<DepositResponse> {
+    match cmd {
+        ProposalCmd::Deposit {
+            contract_name,
+            proposal_id,
+            amount,
+            gov_version,
+            base_tx_args,
+        } => {
+            let BaseTxArgs {
+                network,
+                signer_args,
+                gas_args,
+                account_sequence,
+                ..
+            }: &BaseTxArgs = base_tx_args;
+
+            super::ops::deposit(
+                &ctx,
+                contract_name.as_deref(),
+                *proposal_id,
+                amount.as_deref(),
+                *gov_version,
                 network,
                 {
                     let global_conf = ctx.global_config()?;
@@ -186,7 +1048,7 @@ pub(crate) fn vote<'a>(
                         global_conf.gas_adjustment(),
                     )?
                 },
-                timeout_height,
+                &base_tx_args.tx_options()?,
                 signer_args.private_key(&ctx.global_config()?)?,
                 account_sequence,
             )
@@ -195,6 +1057,127 @@ pub(crate) fn vote<'a>(
     }
 }
 
+/// Wait for the proposal of `cmd` to pass, then run its follow-up with the stored code id.
+pub(crate) fn wait<'a>(
+    ctx: impl Context<'a, WasmConfig>,
+    cmd: &ProposalCmd,
+) -> Result<WaitResponse> {
+    match cmd {
+        ProposalCmd::Wait {
+            contract_name,
+            then,
+            label,
+            raw,
+            admin,
+            funds,
+            interval,
+            gov_version,
+            base_tx_args,
+        } => {
+            let BaseTxArgs {
+                network,
+                signer_args,
+                gas_args,
+                account_sequence,
+                generate_only,
+                dry_run,
+                ..
+            }: &BaseTxArgs = base_tx_args;
+
+            if generate_only.is_some() || *dry_run {
+                bail!("`--generate-only` and `--dry-run` are not supported by `wait`");
+            }
+
+            // resolved before waiting, so that a bad signer or gas setting fails right away
+            // instead of once the proposal has passed
+            let global_conf = ctx.global_config()?;
+            let follow_up = then
+                .as_ref()
+                .map(|then| -> Result<_> {
+                    Ok((
+                        then,
+                        signer_args.private_key(&global_conf)?,
+                        base_tx_args.tx_options()?,
+                        Gas::from_args(
+                            gas_args,
+                            global_conf.gas_price(),
+                            global_conf.gas_adjustment(),
+                        )?,
+                    ))
+                })
+                .transpose()?;
+
+            let wait_response = super::ops::wait(
+                &ctx,
+                contract_name,
+                *gov_version,
+                network,
+                Duration::from_secs(*interval),
+            )?;
+
+            // the code id has just been synced from the proposal
+            let no_proposal_sync = true;
+            match follow_up {
+                Some((FollowUp::Instantiate, signing_key, tx_options, gas)) => {
+                    wasm_ops::instantiate(
+                        &ctx,
+                        contract_name,
+                        label,
+                        raw.as_ref(),
+                        admin.as_ref(),
+                        no_proposal_sync,
+                        true,
+                        funds.as_deref().try_into()?,
+                        &Instantiate2Args::default(),
+                        network,
+                        None,
+                        &tx_options,
+                        &gas,
+                        signing_key,
+                        account_sequence,
+                    )
+                    .map(|_| ())?
+                }
+                Some((FollowUp::Migrate, signing_key, tx_options, gas)) => wasm_ops::migrate(
+                    &ctx,
+                    contract_name,
+                    label,
+                    raw.as_ref(),
+                    no_proposal_sync,
+                    true,
+                    network,
+                    None,
+                    &tx_options,
+                    &gas,
+                    signing_key,
+                    account_sequence,
+                    false,
+                )
+                .map(|_| ())?,
+                None => {}
+            }
+
+            Ok(wait_response)
+        }
+        _ => unimplemented!(),
+    }
+}
+
+pub(crate) fn verify<'a>(
+    ctx: impl Context<'a, WasmConfig>,
+    cmd: &ProposalCmd,
+) -> Result<VerifyResponse> {
+    match cmd {
+        ProposalCmd::Verify {
+            proposal_id,
+            no_rebuild,
+            network,
+            gov_version,
+        } => super::ops::verify(&ctx, *proposal_id, *gov_version, network, *no_rebuild),
+        _ => unimplemented!(),
+    }
+}
+
 pub(crate) fn query<'a>(
     ctx: impl Context<'a, WasmConfig>,
     cmd: &ProposalCmd,
@@ -204,7 +1187,8 @@ pub(crate) fn query<'a>(
             ProposalQueryCmd::StoreCode {
                 contract_name,
                 network,
-            } => super::ops::query_proposal(&ctx, contract_name, network),
+                gov_version,
+            } => super::ops::query_proposal(&ctx, contract_name, *gov_version, network),
         },
         _ => unimplemented!(),
     }
@@ -0,0 +1,207 @@
+use super::vote::target_proposal_id;
+use crate::attrs_format;
+use crate::support::coin::Coins;
+use crate::support::cosmos::{ResponseValuePicker, TxOptions};
+use crate::support::future::block;
+use crate::support::gas::Gas;
+use crate::support::gov::{
+    self, resolve_gov_version, GovVersion, MSG_DEPOSIT_V1BETA1_TYPE_URL, MSG_DEPOSIT_V1_TYPE_URL,
+};
+use crate::support::ops_response::OpResponseDisplay;
+use crate::{framework::Context, modules::wasm::WasmConfig, support::cosmos::Client};
+use anyhow::{bail, Context as _, Result};
+use cosmos_sdk_proto::cosmos::base::v1beta1::Coin as ProtoCoin;
+use cosmrs::crypto::secp256k1::SigningKey;
+use cosmrs::tx::MessageExt;
+use cosmrs::{AccountId, Any};
+use serde::Serialize;
+use std::vec;
+
+#[allow(clippy::too_many_arguments)]
+pub fn deposit<'a, Ctx: Context<'a, WasmConfig>>(
+    ctx: &Ctx,
+    contract_name: Option<&str>,
+    proposal_id: Option<u64>,
+    amount: Option<&str>,
+    gov_version: Option<GovVersion>,
+    network: &str,
+    gas: &Gas,
+    tx_options: &TxOptions,
+    signing_key: SigningKey,
+    account_sequence: &Option<u64>,
+) -> Result<DepositResponse> {
+    let global_config = ctx.global_config()?;
+
+    let network_info = global_config
+        .networks()
+        .get(network)
+        .with_context(|| format!("Unable to find network config: {network}"))?
+        .to_owned();
+
+    let client = Client::new(network_info, &ctx.root()?, &global_config);
+    let gov_version = resolve_gov_version(&client, gov_version)?;
+    let client = client.to_signing_client(signing_key);
+
+    let msg_deposit = deposit_msg(
+        ctx,
+        contract_name,
+        proposal_id,
+        amount,
+        gov_version,
+        network,
+        &client.signer_account_id(),
+    )?;
+
+    block(async {
+        let response = client
+            .sign_and_broadcast(vec![msg_deposit], gas, tx_options, account_sequence)
+            .await?;
+
+        let deposit_response = DepositResponse {
+            proposal_id: response.pick("proposal_deposit", "proposal_id").parse()?,
+            amount: response.pick("proposal_deposit", "amount"),
+        };
+
+        deposit_response.log();
+
+        Ok(deposit_response)
+    })
+}
+
+/// Build the `MsgDeposit` of `depositor` on `proposal_id`, or on the contract's store code proposal.
+/// Without `amount`, deposits what is left to reach the chain's `min_deposit`.
+#[allow(clippy::too_many_arguments)]
+pub fn deposit_msg<'a, Ctx: Context<'a, WasmConfig>>(
+    ctx: &Ctx,
+    contract_name: Option<&str>,
+    proposal_id: Option<u64>,
+    amount: Option<&str>,
+    gov_version: GovVersion,
+    network: &str,
+    depositor: &AccountId,
+) -> Result<Any> {
+    let proposal_id = target_proposal_id(ctx, contract_name, proposal_id, network)?;
+
+    let amount: Vec<ProtoCoin> = match amount {
+        Some(amount) => amount.parse::<Coins>()?.into(),
+        None => {
+            let global_config = ctx.global_config()?;
+            let network_info = global_config
+                .networks()
+                .get(network)
+                .with_context(|| format!("Unable to find network config: {network}"))?
+                .to_owned();
+            let client = Client::new(network_info, &ctx.root()?, &global_config);
+
+            block(async {
+                let min_deposit = client.gov_params_deposit().await?.min_deposit;
+                let total_deposit = match gov_version {
+                    GovVersion::V1 => client.proposal_v1(&proposal_id).await?.total_deposit,
+                    GovVersion::V1beta1 => client.proposal(&proposal_id).await?.total_deposit,
+                };
+
+                let remaining = remaining_deposit(&min_deposit, &total_deposit)?;
+                if remaining.is_empty() {
+                    bail!("Proposal {proposal_id} has already reached the minimum deposit");
+                }
+                Ok(remaining)
+            })?
+        }
+    };
+
+    Ok(match gov_version {
+        GovVersion::V1 => Any {
+            type_url: MSG_DEPOSIT_V1_TYPE_URL.to_owned(),
+            value: gov::MsgDeposit {
+                proposal_id,
+                depositor: depositor.to_string(),
+                amount,
+            }
+            .to_bytes()?,
+        },
+        GovVersion::V1beta1 => Any {
+            type_url: MSG_DEPOSIT_V1BETA1_TYPE_URL.to_owned(),
+            value: cosmos_sdk_proto::cosmos::gov::v1beta1::MsgDeposit {
+                proposal_id,
+                depositor: depositor.to_string(),
+                amount,
+            }
+            .to_bytes()?,
+        },
+    })
+}
+
+/// Coins still missing from `total_deposit` to reach `min_deposit`.
+fn remaining_deposit(
+    min_deposit: &[ProtoCoin],
+    total_deposit: &[ProtoCoin],
+) -> Result<Vec<ProtoCoin>> {
+    let mut remaining = vec![];
+    for min in min_deposit {
+        let deposited = total_deposit
+            .iter()
+            .filter(|c| c.denom == min.denom)
+            .map(|c| c.amount.parse::<u128>())
+            .sum::<Result<u128, _>>()?;
+        let required = min.amount.parse::<u128>()?;
+
+        if required > deposited {
+            remaining.push(ProtoCoin {
+                denom: min.denom.clone(),
+                amount: (required - deposited).to_string(),
+            });
+        }
+    }
+    Ok(remaining)
+}
+
+#[derive(Serialize)]
+pub struct DepositResponse {
+    pub proposal_id: u64,
+    pub amount: String,
+}
+
+impl OpResponseDisplay for DepositResponse {
+    fn headline() -> &'static str {
+        "Deposited successfully!! 🎉"
+    }
+    fn attrs(&self) -> Vec<String> {
+        attrs_format! { self | proposal_id, amount }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn coin(amount: &str, denom: &str) -> ProtoCoin {
+        ProtoCoin {
+            denom: denom.to_owned(),
+            amount: amount.to_owned(),
+        }
+    }
+
+    #[test]
+    fn remaining_deposit_tops_up_to_min_deposit() {
+        assert_eq!(
+            remaining_deposit(
+                &[coin("1000", "uosmo"), coin("50", "uion")],
+                &[
+                    coin("400", "uosmo"),
+                    coin("50", "uion"),
+                    coin("10", "uatom")
+                ]
+            )
+            .unwrap(),
+            vec![coin("600", "uosmo")]
+        );
+        assert_eq!(
+            remaining_deposit(&[coin("1000", "uosmo")], &[coin("1500", "uosmo")]).unwrap(),
+            vec![]
+        );
+        assert_eq!(
+            remaining_deposit(&[coin("1000", "uosmo")], &[]).unwrap(),
+            vec![coin("1000", "uosmo")]
+        );
+    }
+}
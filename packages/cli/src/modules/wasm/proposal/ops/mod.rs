@@ -1,7 +1,14 @@
+pub mod deposit;
 pub mod propose;
+pub mod propose_wasm;
 pub mod query;
+pub mod verify;
 pub mod vote;
+pub mod wait;
 
-pub use propose::propose_store_code;
+pub use deposit::deposit;
+pub use propose::{propose, propose_store_code};
 pub use query::query_proposal;
+pub use verify::verify;
 pub use vote::vote;
+pub use wait::wait;
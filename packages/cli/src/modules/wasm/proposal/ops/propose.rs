@@ -1,40 +1,44 @@
+use super::super::proposal_struct::{BuildAttestation, StoreCodeProposal};
 use crate::attrs_format;
+use crate::modules::wasm::ops::build::{optimizer_args, optimizer_image};
+use crate::modules::wasm::ops::code_info::access_config_str;
 use crate::support::coin::Coins;
-use crate::support::cosmos::ResponseValuePicker;
+use crate::support::cosmos::{ResponseValuePicker, TxCommitResponse, TxOptions};
 use crate::support::future::block;
 use crate::support::gas::Gas;
+use crate::support::gov::{
+    self, gov_module_address, resolve_gov_version, GovVersion,
+    MSG_SUBMIT_PROPOSAL_V1BETA1_TYPE_URL, MSG_SUBMIT_PROPOSAL_V1_TYPE_URL,
+};
 use crate::support::ops_response::OpResponseDisplay;
 use crate::support::permission::compute_instantiate_permission;
-use crate::support::state::State;
-use crate::support::wasm::read_wasm;
+use crate::support::state::{ProposalKind, State};
+use crate::support::wasm::{read_wasm, wasm_checksum};
 use crate::{framework::Context, modules::wasm::WasmConfig, support::cosmos::Client};
 use anyhow::{Context as _, Result};
 use cosmos_sdk_proto::cosmos::gov::v1beta1::MsgSubmitProposal;
-use cosmos_sdk_proto::cosmwasm::wasm::v1::StoreCodeProposal;
+use cosmos_sdk_proto::cosmwasm::wasm::v1 as wasm;
 use cosmrs::crypto::secp256k1::SigningKey;
 use cosmrs::tx::MessageExt;
-use cosmrs::Any;
+use cosmrs::{AccountId, Any};
 use serde::Serialize;
+use std::process::Command;
 use std::vec;
 
 #[allow(clippy::too_many_arguments)]
 pub fn propose_store_code<'a, Ctx: Context<'a, WasmConfig>>(
     ctx: &Ctx,
     contract_name: &str,
-    title: &str,
-    description: &str,
-    deposit: Coins,
-    unpin_code: bool,
+    proposal: &StoreCodeProposal,
+    gov_version: Option<GovVersion>,
     network: &str,
     gas: &Gas,
     permit_instantiate_only: &Option<String>,
-    timeout_height: &u32,
+    tx_options: &TxOptions,
     signing_key: SigningKey,
     account_sequence: &Option<u64>,
 ) -> Result<ProposeStoreCodeResponse> {
     let global_config = ctx.global_config()?;
-    let account_prefix = global_config.account_prefix().as_str();
-    let no_wasm_opt = &false;
 
     let network_info = global_config
         .networks()
@@ -42,78 +46,233 @@ pub fn propose_store_code<'a, Ctx: Context<'a, WasmConfig>>(
         .with_context(|| format!("Unable to find network config: {network}"))?
         .to_owned();
 
-    let client = Client::new(network_info.clone()).to_signing_client(signing_key, account_prefix);
+    let client = Client::new(network_info.clone(), &ctx.root()?, &global_config);
+    let gov_version = resolve_gov_version(&client, gov_version)?;
+    let client = client.to_signing_client(signing_key);
+
+    let msg_submit_proposal = propose_store_code_msg(
+        ctx,
+        contract_name,
+        proposal,
+        gov_version,
+        permit_instantiate_only,
+        &client.signer_account_id(),
+    )?;
+    let instantiate_permission = compute_instantiate_permission(
+        permit_instantiate_only,
+        client.signer_account_id(),
+        &global_config,
+    )?;
+
+    block(async {
+        let response = client
+            .sign_and_broadcast(vec![msg_submit_proposal], gas, tx_options, account_sequence)
+            .await?;
+
+        let ProposeResponse {
+            proposal_id,
+            deposit_amount,
+        } = ProposeResponse::from_response(&response)?;
+
+        let propose_store_code_response = ProposeStoreCodeResponse {
+            proposal_id,
+            deposit_amount,
+            instantiate_permission: access_config_str(
+                instantiate_permission.map(Into::into).as_ref(),
+            ),
+        };
+
+        State::update_state_file(
+            network_info.network_variant(),
+            ctx.root()?,
+            &|s: &State| -> State {
+                s.update_proposal_store_code_id(network, contract_name, &proposal_id)
+            },
+        )?;
+        propose_store_code_response.log();
+
+        Ok(propose_store_code_response)
+    })
+}
+
+/// Build the `MsgSubmitProposal` for storing the contract's wasm, proposed by `proposer`.
+pub fn propose_store_code_msg<'a, Ctx: Context<'a, WasmConfig>>(
+    ctx: &Ctx,
+    contract_name: &str,
+    proposal: &StoreCodeProposal,
+    gov_version: GovVersion,
+    permit_instantiate_only: &Option<String>,
+    proposer: &AccountId,
+) -> Result<Any> {
+    let no_wasm_opt = &false;
 
     let wasm = read_wasm(
         ctx.root()?,
         contract_name.replace('-', "_").as_str(), // Handles file name mismatch
         no_wasm_opt,
     )?;
-    let instantiate_permission =
-        compute_instantiate_permission(permit_instantiate_only, client.signer_account_id())?;
+    let instantiate_permission = compute_instantiate_permission(
+        permit_instantiate_only,
+        proposer.clone(),
+        &ctx.global_config()?,
+    )?
+    .map(|ac| ac.into());
+    let proposal = &StoreCodeProposal {
+        description: proposal.description_with_metadata(&build_attestation(ctx, &wasm)?),
+        ..proposal.clone()
+    };
+
+    let msg_store_code = wasm::MsgStoreCode {
+        sender: gov_module_address(proposer.prefix())?.to_string(),
+        wasm_byte_code: wasm.clone(),
+        instantiate_permission: instantiate_permission.clone(),
+    };
 
-    let store_code_proposal = StoreCodeProposal {
-        title: title.to_string(),
-        description: description.to_string(),
-        run_as: client.signer_account_id().to_string(),
+    let store_code_proposal = wasm::StoreCodeProposal {
+        title: proposal.title.clone(),
+        description: proposal.description.clone(),
+        run_as: proposer.to_string(),
         wasm_byte_code: wasm,
-        instantiate_permission: instantiate_permission.clone().map(|ac| ac.into()),
-        unpin_code,
+        instantiate_permission,
+        unpin_code: proposal.unpin_code,
     };
 
-    let msg_submit_proposal = MsgSubmitProposal {
-        content: Some(Any {
+    submit_proposal_msg(
+        gov_version,
+        proposal,
+        proposer,
+        Any {
+            type_url: "/cosmwasm.wasm.v1.MsgStoreCode".to_owned(),
+            value: msg_store_code.to_bytes()?,
+        },
+        Any {
             type_url: "/cosmwasm.wasm.v1.StoreCodeProposal".to_owned(),
             value: store_code_proposal.to_bytes()?,
-        }),
-        initial_deposit: deposit.into(),
-        proposer: client.signer_account_id().to_string(),
-    };
+        },
+    )
+}
+
+/// Attestation of the build of `wasm` from the workspace, as done by `beaker wasm build`.
+pub fn build_attestation<'a, Ctx: Context<'a, WasmConfig>>(
+    ctx: &Ctx,
+    wasm: &[u8],
+) -> Result<BuildAttestation> {
+    let root = ctx.root()?;
+    let optimizer = optimizer_image(&ctx.config()?.optimizer_version, &false);
 
-    let msg_submit_proposal = Any {
-        type_url: "/cosmos.gov.v1beta1.MsgSubmitProposal".to_owned(),
-        value: msg_submit_proposal.to_bytes()?,
+    let git = |args: &[&str]| {
+        Command::new("git")
+            .args(args)
+            .current_dir(&root)
+            .output()
+            .ok()
+            .filter(|o| o.status.success())
+            .map(|o| String::from_utf8_lossy(&o.stdout).trim().to_string())
     };
+    let git_commit = match git(&["rev-parse", "HEAD"]) {
+        Some(commit) if git(&["status", "--porcelain"]).is_some_and(|s| !s.is_empty()) => {
+            format!("{commit} (dirty)")
+        }
+        Some(commit) => commit,
+        None => "-".to_string(),
+    };
+
+    let build_command = format!(
+        "docker {}",
+        optimizer_args("\"$(pwd)\"", "\"$(basename \"$(pwd)\")\"", &optimizer).join(" ")
+    );
+
+    Ok(BuildAttestation {
+        checksum: wasm_checksum(wasm),
+        optimizer,
+        git_commit,
+        build_command,
+    })
+}
+
+/// Build the `MsgSubmitProposal` of `proposer`. With gov `v1` the proposal carries `msg`,
+/// which must be sent by the gov module, with `v1beta1` it carries `legacy_content` instead.
+pub fn submit_proposal_msg(
+    gov_version: GovVersion,
+    proposal: &StoreCodeProposal,
+    proposer: &AccountId,
+    msg: Any,
+    legacy_content: Any,
+) -> Result<Any> {
+    let deposit: Coins = proposal.deposit.as_deref().try_into()?;
+
+    Ok(match gov_version {
+        GovVersion::V1 => Any {
+            type_url: MSG_SUBMIT_PROPOSAL_V1_TYPE_URL.to_owned(),
+            value: gov::MsgSubmitProposal {
+                messages: vec![msg],
+                initial_deposit: deposit.into(),
+                proposer: proposer.to_string(),
+                metadata: proposal.metadata.clone(),
+                title: proposal.title.clone(),
+                summary: proposal.description.clone(),
+                expedited: proposal.expedited,
+            }
+            .to_bytes()?,
+        },
+        GovVersion::V1beta1 => Any {
+            type_url: MSG_SUBMIT_PROPOSAL_V1BETA1_TYPE_URL.to_owned(),
+            value: MsgSubmitProposal {
+                content: Some(legacy_content),
+                initial_deposit: deposit.into(),
+                proposer: proposer.to_string(),
+            }
+            .to_bytes()?,
+        },
+    })
+}
+
+/// Submit the proposal built by `proposal_msg` for the signer and the chain's gov version,
+/// then record its id as the contract's `kind` proposal.
+#[allow(clippy::too_many_arguments)]
+pub fn propose<'a, Ctx: Context<'a, WasmConfig>>(
+    ctx: &Ctx,
+    contract_name: &str,
+    kind: ProposalKind,
+    gov_version: Option<GovVersion>,
+    network: &str,
+    gas: &Gas,
+    tx_options: &TxOptions,
+    signing_key: SigningKey,
+    account_sequence: &Option<u64>,
+    proposal_msg: impl FnOnce(GovVersion, &AccountId) -> Result<Any>,
+) -> Result<ProposeResponse> {
+    let global_config = ctx.global_config()?;
+
+    let network_info = global_config
+        .networks()
+        .get(network)
+        .with_context(|| format!("Unable to find network config: {network}"))?
+        .to_owned();
+
+    let client = Client::new(network_info.clone(), &ctx.root()?, &global_config);
+    let gov_version = resolve_gov_version(&client, gov_version)?;
+    let client = client.to_signing_client(signing_key);
+
+    let msg_submit_proposal = proposal_msg(gov_version, &client.signer_account_id())?;
 
     block(async {
         let response = client
-            .sign_and_broadcast(
-                vec![msg_submit_proposal],
-                gas,
-                "",
-                timeout_height,
-                account_sequence,
-            )
+            .sign_and_broadcast(vec![msg_submit_proposal], gas, tx_options, account_sequence)
             .await?;
 
-        let proposal_id: u64 = response.pick("submit_proposal", "proposal_id").parse()?;
-
-        // TODO: ProposalStoreCodeResponse::from(response)
-        let deposit_amount: String = response.pick("proposal_deposit", "amount");
-        let deposit_amount = if deposit_amount.is_empty() {
-            "-".to_string()
-        } else {
-            deposit_amount
-        };
-
-        let propose_store_code_response = ProposeStoreCodeResponse {
-            proposal_id,
-            deposit_amount,
-            instantiate_permission: instantiate_permission
-                .map(|p| format!("only_addresss | {:?}", p.addresses))
-                .unwrap_or_else(|| "–".to_string()),
-        };
+        let propose_response = ProposeResponse::from_response(&response)?;
 
         State::update_state_file(
             network_info.network_variant(),
             ctx.root()?,
             &|s: &State| -> State {
-                s.update_proposal_store_code_id(network, contract_name, &proposal_id)
+                s.update_proposal_id(network, contract_name, &kind, &propose_response.proposal_id)
             },
         )?;
-        propose_store_code_response.log();
+        propose_response.log();
 
-        Ok(propose_store_code_response)
+        Ok(propose_response)
     })
 }
 
@@ -132,3 +291,36 @@ impl OpResponseDisplay for ProposeStoreCodeResponse {
         attrs_format! { self | proposal_id, deposit_amount, instantiate_permission }
     }
 }
+
+#[derive(Serialize)]
+pub struct ProposeResponse {
+    pub proposal_id: u64,
+    pub deposit_amount: String,
+}
+
+impl ProposeResponse {
+    fn from_response(response: &TxCommitResponse) -> Result<Self> {
+        let proposal_id: u64 = response.pick("submit_proposal", "proposal_id").parse()?;
+
+        let deposit_amount: String = response.pick("proposal_deposit", "amount");
+        let deposit_amount = if deposit_amount.is_empty() {
+            "-".to_string()
+        } else {
+            deposit_amount
+        };
+
+        Ok(ProposeResponse {
+            proposal_id,
+            deposit_amount,
+        })
+    }
+}
+
+impl OpResponseDisplay for ProposeResponse {
+    fn headline() -> &'static str {
+        "Proposal has been submitted!! 🎉"
+    }
+    fn attrs(&self) -> Vec<String> {
+        attrs_format! { self | proposal_id, deposit_amount }
+    }
+}
@@ -0,0 +1,433 @@
+use super::super::proposal_struct::StoreCodeProposal;
+use super::propose::{build_attestation, submit_proposal_msg};
+use crate::modules::wasm::ops::clear_admin::clear_admin_msg;
+use crate::modules::wasm::ops::instantiate::{instantiate_msg, read_instantiate_msg};
+use crate::modules::wasm::ops::migrate::migrate_msg;
+use crate::modules::wasm::ops::update_admin::update_admin_msg;
+use crate::support::coin::Coins;
+use crate::support::gov::{gov_module_address, GovVersion};
+use crate::support::permission::{compute_admin, compute_instantiate_permission};
+use crate::support::state::State;
+use crate::support::wasm::read_wasm;
+use crate::{framework::Context, modules::wasm::WasmConfig};
+use anyhow::{Context as _, Result};
+use cosmos_sdk_proto::cosmos::base::v1beta1::Coin;
+use cosmos_sdk_proto::cosmwasm::wasm::v1 as wasm;
+use cosmrs::tx::MessageExt;
+use cosmrs::{AccountId, Any};
+
+/// Build the `MsgSubmitProposal` for instantiating the contract's latest code id.
+#[allow(clippy::too_many_arguments)]
+pub fn propose_instantiate_msg<'a, Ctx: Context<'a, WasmConfig>>(
+    ctx: &Ctx,
+    contract_name: &str,
+    label: &str,
+    raw: Option<&String>,
+    admin: Option<&String>,
+    no_proposal_sync: bool,
+    yes: bool,
+    update_state: bool,
+    funds: Coins,
+    proposal: &StoreCodeProposal,
+    gov_version: GovVersion,
+    network: &str,
+    proposer: &AccountId,
+) -> Result<Any> {
+    let msg: wasm::MsgInstantiateContract = instantiate_msg(
+        ctx,
+        contract_name,
+        label,
+        raw,
+        admin,
+        no_proposal_sync,
+        yes,
+        update_state,
+        funds,
+        network,
+        proposer,
+    )?
+    .into();
+
+    let content = wasm::InstantiateContractProposal {
+        title: proposal.title.clone(),
+        description: proposal.description.clone(),
+        run_as: proposer.to_string(),
+        admin: msg.admin.clone(),
+        code_id: msg.code_id,
+        label: msg.label.clone(),
+        msg: msg.msg.clone(),
+        funds: msg.funds.clone(),
+    };
+
+    let msg = wasm::MsgInstantiateContract {
+        sender: gov_module_address(proposer.prefix())?.to_string(),
+        ..msg
+    };
+
+    submit_proposal_msg(
+        gov_version,
+        proposal,
+        proposer,
+        Any {
+            type_url: "/cosmwasm.wasm.v1.MsgInstantiateContract".to_owned(),
+            value: msg.to_bytes()?,
+        },
+        Any {
+            type_url: "/cosmwasm.wasm.v1.InstantiateContractProposal".to_owned(),
+            value: content.to_bytes()?,
+        },
+    )
+}
+
+/// Build the `MsgSubmitProposal` for migrating the contract to its latest code id.
+#[allow(clippy::too_many_arguments)]
+pub fn propose_migrate_msg<'a, Ctx: Context<'a, WasmConfig>>(
+    ctx: &Ctx,
+    contract_name: &str,
+    label: &str,
+    raw: Option<&String>,
+    no_proposal_sync: bool,
+    yes: bool,
+    update_state: bool,
+    proposal: &StoreCodeProposal,
+    gov_version: GovVersion,
+    network: &str,
+    proposer: &AccountId,
+    force: bool,
+) -> Result<Any> {
+    let msg = migrate_msg(
+        ctx,
+        contract_name,
+        label,
+        raw,
+        no_proposal_sync,
+        yes,
+        update_state,
+        network,
+        &gov_module_address(proposer.prefix())?,
+        force,
+    )?;
+
+    let content = wasm::MigrateContractProposal {
+        title: proposal.title.clone(),
+        description: proposal.description.clone(),
+        contract: msg.contract.clone(),
+        code_id: msg.code_id,
+        msg: msg.msg.clone(),
+    };
+
+    submit_proposal_msg(
+        gov_version,
+        proposal,
+        proposer,
+        Any {
+            type_url: "/cosmwasm.wasm.v1.MsgMigrateContract".to_owned(),
+            value: msg.to_bytes()?,
+        },
+        Any {
+            type_url: "/cosmwasm.wasm.v1.MigrateContractProposal".to_owned(),
+            value: content.to_bytes()?,
+        },
+    )
+}
+
+/// Build the `MsgSubmitProposal` for updating the contract's admin.
+#[allow(clippy::too_many_arguments)]
+pub fn propose_update_admin_msg<'a, Ctx: Context<'a, WasmConfig>>(
+    ctx: &Ctx,
+    contract_name: &str,
+    label: &str,
+    new_admin: &str,
+    proposal: &StoreCodeProposal,
+    gov_version: GovVersion,
+    network: &str,
+    proposer: &AccountId,
+) -> Result<Any> {
+    let msg = update_admin_msg(
+        ctx,
+        contract_name,
+        label,
+        network,
+        new_admin,
+        &gov_module_address(proposer.prefix())?,
+    )?;
+
+    let content = wasm::UpdateAdminProposal {
+        title: proposal.title.clone(),
+        description: proposal.description.clone(),
+        new_admin: msg.new_admin.clone(),
+        contract: msg.contract.clone(),
+    };
+
+    submit_proposal_msg(
+        gov_version,
+        proposal,
+        proposer,
+        Any {
+            type_url: "/cosmwasm.wasm.v1.MsgUpdateAdmin".to_owned(),
+            value: msg.to_bytes()?,
+        },
+        Any {
+            type_url: "/cosmwasm.wasm.v1.UpdateAdminProposal".to_owned(),
+            value: content.to_bytes()?,
+        },
+    )
+}
+
+/// Build the `MsgSubmitProposal` for clearing the contract's admin.
+pub fn propose_clear_admin_msg<'a, Ctx: Context<'a, WasmConfig>>(
+    ctx: &Ctx,
+    contract_name: &str,
+    label: &str,
+    proposal: &StoreCodeProposal,
+    gov_version: GovVersion,
+    network: &str,
+    proposer: &AccountId,
+) -> Result<Any> {
+    let msg = clear_admin_msg(
+        ctx,
+        contract_name,
+        label,
+        network,
+        &gov_module_address(proposer.prefix())?,
+    )?;
+
+    let content = wasm::ClearAdminProposal {
+        title: proposal.title.clone(),
+        description: proposal.description.clone(),
+        contract: msg.contract.clone(),
+    };
+
+    submit_proposal_msg(
+        gov_version,
+        proposal,
+        proposer,
+        Any {
+            type_url: "/cosmwasm.wasm.v1.MsgClearAdmin".to_owned(),
+            value: msg.to_bytes()?,
+        },
+        Any {
+            type_url: "/cosmwasm.wasm.v1.ClearAdminProposal".to_owned(),
+            value: content.to_bytes()?,
+        },
+    )
+}
+
+/// Build the `MsgSubmitProposal` for pinning `code_ids` in the wasm cache, or unpinning them
+/// if `unpin` is set. Defaults to the contract's stored code id if `code_ids` is empty.
+#[allow(clippy::too_many_arguments)]
+pub fn propose_pin_codes_msg<'a, Ctx: Context<'a, WasmConfig>>(
+    ctx: &Ctx,
+    contract_name: &str,
+    code_ids: &[u64],
+    unpin: bool,
+    proposal: &StoreCodeProposal,
+    gov_version: GovVersion,
+    network: &str,
+    proposer: &AccountId,
+) -> Result<Any> {
+    let code_ids = if code_ids.is_empty() {
+        let network_info = ctx
+            .global_config()?
+            .networks()
+            .get(network)
+            .with_context(|| format!("Unable to find network config: {network}"))?
+            .to_owned();
+
+        let state = State::load_by_network(network_info, ctx.root()?)?;
+        let code_id = state
+            .get_ref(network, contract_name)?
+            .code_id()
+            .with_context(|| format!("Unable to retrieve code_id for {contract_name}"))?;
+        vec![code_id]
+    } else {
+        code_ids.to_vec()
+    };
+
+    let msg = MsgPinCodes {
+        authority: gov_module_address(proposer.prefix())?.to_string(),
+        code_ids: code_ids.clone(),
+    };
+    let content = wasm::PinCodesProposal {
+        title: proposal.title.clone(),
+        description: proposal.description.clone(),
+        code_ids,
+    };
+
+    let (msg_type_url, content_type_url) = if unpin {
+        (
+            "/cosmwasm.wasm.v1.MsgUnpinCodes",
+            "/cosmwasm.wasm.v1.UnpinCodesProposal",
+        )
+    } else {
+        (
+            "/cosmwasm.wasm.v1.MsgPinCodes",
+            "/cosmwasm.wasm.v1.PinCodesProposal",
+        )
+    };
+
+    submit_proposal_msg(
+        gov_version,
+        proposal,
+        proposer,
+        Any {
+            type_url: msg_type_url.to_owned(),
+            value: msg.to_bytes()?,
+        },
+        Any {
+            type_url: content_type_url.to_owned(),
+            value: content.to_bytes()?,
+        },
+    )
+}
+
+/// Build the `MsgSubmitProposal` for storing the contract's wasm and instantiating it
+/// once the proposal passes.
+#[allow(clippy::too_many_arguments)]
+pub fn propose_store_and_instantiate_msg<'a, Ctx: Context<'a, WasmConfig>>(
+    ctx: &Ctx,
+    contract_name: &str,
+    label: &str,
+    raw: Option<&String>,
+    admin: Option<&String>,
+    funds: Coins,
+    permit_instantiate_only: &Option<String>,
+    proposal: &StoreCodeProposal,
+    gov_version: GovVersion,
+    proposer: &AccountId,
+) -> Result<Any> {
+    let wasm_byte_code = read_wasm(
+        ctx.root()?,
+        contract_name.replace('-', "_").as_str(), // Handles file name mismatch
+        &false,
+    )?;
+    let instantiate_permission = compute_instantiate_permission(
+        permit_instantiate_only,
+        proposer.clone(),
+        &ctx.global_config()?,
+    )?
+    .map(|ac| ac.into());
+    let admin = compute_admin(admin, proposer.clone())?
+        .map(|a| a.to_string())
+        .unwrap_or_default();
+    let msg = read_instantiate_msg(ctx, contract_name, label, raw)?;
+    let funds: Vec<Coin> = funds.into();
+    let proposal = &StoreCodeProposal {
+        description: proposal.description_with_metadata(&build_attestation(ctx, &wasm_byte_code)?),
+        ..proposal.clone()
+    };
+
+    let content = StoreAndInstantiateContractProposal {
+        title: proposal.title.clone(),
+        description: proposal.description.clone(),
+        run_as: proposer.to_string(),
+        wasm_byte_code: wasm_byte_code.clone(),
+        instantiate_permission: instantiate_permission.clone(),
+        unpin_code: proposal.unpin_code,
+        admin: admin.clone(),
+        label: label.to_string(),
+        msg: msg.clone(),
+        funds: funds.clone(),
+        source: String::new(),
+        builder: String::new(),
+        code_hash: vec![],
+    };
+
+    let msg = MsgStoreAndInstantiateContract {
+        authority: gov_module_address(proposer.prefix())?.to_string(),
+        wasm_byte_code,
+        instantiate_permission,
+        unpin_code: proposal.unpin_code,
+        admin,
+        label: label.to_string(),
+        msg,
+        funds,
+        source: String::new(),
+        builder: String::new(),
+        code_hash: vec![],
+    };
+
+    submit_proposal_msg(
+        gov_version,
+        proposal,
+        proposer,
+        Any {
+            type_url: "/cosmwasm.wasm.v1.MsgStoreAndInstantiateContract".to_owned(),
+            value: msg.to_bytes()?,
+        },
+        Any {
+            type_url: "/cosmwasm.wasm.v1.StoreAndInstantiateContractProposal".to_owned(),
+            value: content.to_bytes()?,
+        },
+    )
+}
+
+/// `cosmwasm.wasm.v1.MsgPinCodes` from wasmd v0.40, not available in the generated protos.
+/// `MsgUnpinCodes` shares the same fields.
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct MsgPinCodes {
+    #[prost(string, tag = "1")]
+    pub authority: String,
+    #[prost(uint64, repeated, tag = "2")]
+    pub code_ids: Vec<u64>,
+}
+
+/// `cosmwasm.wasm.v1.MsgStoreAndInstantiateContract` from wasmd v0.40,
+/// not available in the generated protos.
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct MsgStoreAndInstantiateContract {
+    #[prost(string, tag = "1")]
+    pub authority: String,
+    #[prost(bytes = "vec", tag = "3")]
+    pub wasm_byte_code: Vec<u8>,
+    #[prost(message, optional, tag = "4")]
+    pub instantiate_permission: Option<wasm::AccessConfig>,
+    #[prost(bool, tag = "5")]
+    pub unpin_code: bool,
+    #[prost(string, tag = "6")]
+    pub admin: String,
+    #[prost(string, tag = "7")]
+    pub label: String,
+    #[prost(bytes = "vec", tag = "8")]
+    pub msg: Vec<u8>,
+    #[prost(message, repeated, tag = "9")]
+    pub funds: Vec<Coin>,
+    #[prost(string, tag = "10")]
+    pub source: String,
+    #[prost(string, tag = "11")]
+    pub builder: String,
+    #[prost(bytes = "vec", tag = "12")]
+    pub code_hash: Vec<u8>,
+}
+
+/// `cosmwasm.wasm.v1.StoreAndInstantiateContractProposal` from wasmd v0.30,
+/// not available in the generated protos.
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct StoreAndInstantiateContractProposal {
+    #[prost(string, tag = "1")]
+    pub title: String,
+    #[prost(string, tag = "2")]
+    pub description: String,
+    #[prost(string, tag = "3")]
+    pub run_as: String,
+    #[prost(bytes = "vec", tag = "4")]
+    pub wasm_byte_code: Vec<u8>,
+    #[prost(message, optional, tag = "5")]
+    pub instantiate_permission: Option<wasm::AccessConfig>,
+    #[prost(bool, tag = "6")]
+    pub unpin_code: bool,
+    #[prost(string, tag = "7")]
+    pub admin: String,
+    #[prost(string, tag = "8")]
+    pub label: String,
+    #[prost(bytes = "vec", tag = "9")]
+    pub msg: Vec<u8>,
+    #[prost(message, repeated, tag = "10")]
+    pub funds: Vec<Coin>,
+    #[prost(string, tag = "11")]
+    pub source: String,
+    #[prost(string, tag = "12")]
+    pub builder: String,
+    #[prost(bytes = "vec", tag = "13")]
+    pub code_hash: Vec<u8>,
+}
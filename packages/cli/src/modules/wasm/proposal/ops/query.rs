@@ -1,18 +1,22 @@
 use crate::support::future::block;
+use crate::support::gov::{self, resolve_gov_version, GovVersion};
 use crate::support::state::State;
 use crate::vars_format;
 use crate::{framework::Context, modules::wasm::WasmConfig, support::cosmos::Client};
-use anyhow::{Context as _, Result};
-use cosmos_sdk_proto::cosmos::gov::v1beta1::{Proposal, ProposalStatus, TallyResult};
+use anyhow::{bail, Context as _, Result};
+use cosmos_sdk_proto::cosmos::base::v1beta1::Coin as ProtoCoin;
+use cosmos_sdk_proto::cosmos::gov::v1beta1::{Proposal, ProposalStatus, TallyParams, TallyResult};
+use cosmos_sdk_proto::cosmwasm::wasm::v1::StoreCodeProposal;
 use cosmos_sdk_proto::traits::Message;
-use cosmrs::bip32::secp256k1::pkcs8::der::DateTime;
+use cosmrs::tendermint::Time;
+use prost_types::Timestamp;
 use serde::Serialize;
-use std::time::Duration;
 use std::vec;
 
 pub fn query_proposal<'a, Ctx: Context<'a, WasmConfig>>(
     ctx: &Ctx,
     contract_name: &str,
+    gov_version: Option<GovVersion>,
     network: &str,
 ) -> Result<QueryProposalResponse> {
     let global_config = ctx.global_config()?;
@@ -23,57 +27,54 @@ pub fn query_proposal<'a, Ctx: Context<'a, WasmConfig>>(
         .with_context(|| format!("Unable to find network config: {network}"))?
         .to_owned();
 
-    let client = Client::new(network_info.clone());
+    let client = Client::new(network_info.clone(), &ctx.root()?, &global_config);
 
     let state = State::load_by_network(network_info, ctx.root()?)?;
     let wasm_ref = state.get_ref(network, contract_name)?;
 
-    block(async {
-        let res = client
-            .proposal(&wasm_ref.proposal().store_code().with_context(|| {
-                format!(
+    let proposal_id = wasm_ref.proposal().store_code().with_context(|| {
+        format!(
             "Proposal store code not found for contract `{contract_name}` on network `{network}`"
         )
-            })?)
-            .await?;
-
-        use cosmos_sdk_proto::cosmwasm::wasm::v1::StoreCodeProposal;
+    })?;
+    let gov_version = resolve_gov_version(&client, gov_version)?;
 
-        let Proposal {
+    block(async {
+        let ProposalFields {
             proposal_id,
-            content,
+            title,
+            description,
+            run_as,
             status,
             total_deposit,
-            final_tally_result,
+            final_tally_result:
+                TallyResult {
+                    yes,
+                    abstain,
+                    no,
+                    no_with_veto,
+                },
             submit_time,
             deposit_end_time,
             voting_start_time,
             voting_end_time,
-        } = res.clone();
-
-        let status = ProposalStatus::from_i32(status).unwrap();
-        let status = match status {
-            ProposalStatus::DepositPeriod => "DepositPeriod",
-            ProposalStatus::Unspecified => "Unspecified",
-            ProposalStatus::VotingPeriod => "VotingPeriod",
-            ProposalStatus::Passed => "Passed",
-            ProposalStatus::Rejected => "Rejected",
-            ProposalStatus::Failed => "Failed",
+        } = match gov_version {
+            GovVersion::V1 => client.proposal_v1(&proposal_id).await?.try_into()?,
+            GovVersion::V1beta1 => client.proposal(&proposal_id).await?.try_into()?,
         };
 
-        let TallyResult {
-            yes,
-            abstain,
-            no,
-            no_with_veto,
-        } = final_tally_result.unwrap();
+        let status = proposal_status(status);
 
-        let StoreCodeProposal {
-            title,
-            description,
-            run_as,
-            ..
-        } = StoreCodeProposal::decode(content.unwrap().value.as_slice())?;
+        // `final_tally_result` is only set once the voting period ends
+        let live_tally = if status == "VotingPeriod" {
+            Some(project_tally(
+                &client.tally_result(&proposal_id).await?,
+                client.bonded_tokens().await?,
+                &client.gov_params_tallying().await?,
+            )?)
+        } else {
+            None
+        };
 
         let total_deposit_coins = total_deposit
             .iter()
@@ -100,35 +101,14 @@ pub fn query_proposal<'a, Ctx: Context<'a, WasmConfig>>(
 
         let total_deposit = format!("{total_deposit} (min_deposit: {min_deposit})");
 
-        let datetime_str = |seconds: i64, nanos: i32| {
-            if let Ok(d) = DateTime::from_unix_duration(Duration::new(seconds as u64, nanos as u32))
-            {
-                format!("{d}")
-            } else {
-                "–".to_string()
-            }
-        };
-
-        let submit_time = {
-            let ts = submit_time.unwrap();
-            datetime_str(ts.seconds, ts.nanos)
-        };
-        let deposit_end_time = {
-            let ts = deposit_end_time.unwrap();
-            datetime_str(ts.seconds, ts.nanos)
-        };
-        let voting_start_time = {
-            let ts = voting_start_time.unwrap();
-            datetime_str(ts.seconds, ts.nanos)
-        };
-        let voting_end_time = {
-            let ts = voting_end_time.unwrap();
-            datetime_str(ts.seconds, ts.nanos)
-        };
+        let submit_time = datetime_str(submit_time);
+        let deposit_end_time = datetime_str(deposit_end_time);
+        let voting_start_time = datetime_str(voting_start_time);
+        let voting_end_time = datetime_str(voting_end_time);
 
         println!(
             "{}",
-            vec![
+            [
                 vars_format!(
                     "Proposal found!",
                     proposal_id,
@@ -138,7 +118,35 @@ pub fn query_proposal<'a, Ctx: Context<'a, WasmConfig>>(
                     total_deposit,
                     status
                 ),
-                vars_format!("Final Tally Result", yes, no, no_with_veto, abstain),
+                match &live_tally {
+                    Some(LiveTally {
+                        yes,
+                        no,
+                        no_with_veto,
+                        abstain,
+                        turnout,
+                        quorum,
+                        yes_ratio,
+                        threshold,
+                        veto_ratio,
+                        veto_threshold,
+                        outcome,
+                    }) => [
+                        vars_format!("Current Tally", yes, no, no_with_veto, abstain),
+                        vars_format!(
+                            "Projected Outcome",
+                            turnout,
+                            quorum,
+                            yes_ratio,
+                            threshold,
+                            veto_ratio,
+                            veto_threshold,
+                            outcome
+                        ),
+                    ]
+                    .concat(),
+                    None => vars_format!("Final Tally Result", yes, no, no_with_veto, abstain),
+                },
                 vars_format!(
                     "Time",
                     submit_time,
@@ -164,6 +172,7 @@ pub fn query_proposal<'a, Ctx: Context<'a, WasmConfig>>(
                 no,
                 no_with_veto,
             },
+            live_tally,
             submit_time,
             deposit_end_time,
             voting_start_time,
@@ -172,6 +181,207 @@ pub fn query_proposal<'a, Ctx: Context<'a, WasmConfig>>(
     })
 }
 
+/// Current tally of a proposal in voting period, against the chain's tally params.
+/// Follows the gov module's tally: quorum is reached with enough of the bonded tokens voting,
+/// the proposal is vetoed with enough `no_with_veto` votes, otherwise it passes with enough
+/// `yes` votes among the non abstaining ones.
+fn project_tally(
+    tally: &TallyResult,
+    bonded_tokens: u128,
+    params: &TallyParams,
+) -> Result<LiveTally> {
+    let votes = |v: &String| {
+        v.parse::<u128>()
+            .with_context(|| format!("Invalid tally votes: {v}"))
+    };
+    let (yes, no, no_with_veto, abstain) = (
+        votes(&tally.yes)?,
+        votes(&tally.no)?,
+        votes(&tally.no_with_veto)?,
+        votes(&tally.abstain)?,
+    );
+    let ratio = |a: u128, b: u128| if b == 0 { 0.0 } else { a as f64 / b as f64 };
+
+    let total = yes + no + no_with_veto + abstain;
+    let turnout = ratio(total, bonded_tokens);
+    let yes_ratio = ratio(yes, total - abstain);
+    let veto_ratio = ratio(no_with_veto, total);
+
+    let quorum = dec_from_bytes(&params.quorum)?;
+    let threshold = dec_from_bytes(&params.threshold)?;
+    let veto_threshold = dec_from_bytes(&params.veto_threshold)?;
+
+    let outcome = if turnout < quorum {
+        "Rejected, quorum not reached"
+    } else if total == abstain {
+        "Rejected, all votes abstain"
+    } else if veto_ratio > veto_threshold {
+        "Rejected, vetoed"
+    } else if yes_ratio > threshold {
+        "Passed"
+    } else {
+        "Rejected, threshold not reached"
+    };
+
+    let percent = |r: f64| format!("{:.2}%", r * 100.0);
+
+    Ok(LiveTally {
+        yes: tally.yes.clone(),
+        no: tally.no.clone(),
+        no_with_veto: tally.no_with_veto.clone(),
+        abstain: tally.abstain.clone(),
+        turnout: percent(turnout),
+        quorum: percent(quorum),
+        yes_ratio: percent(yes_ratio),
+        threshold: percent(threshold),
+        veto_ratio: percent(veto_ratio),
+        veto_threshold: percent(veto_threshold),
+        outcome: outcome.to_string(),
+    })
+}
+
+/// `Dec` of the legacy tally params, encoded as its 18 decimals fixed point integer.
+fn dec_from_bytes(bytes: &[u8]) -> Result<f64> {
+    let dec = String::from_utf8(bytes.to_vec())?;
+    if dec.contains('.') {
+        return Ok(dec.parse()?);
+    }
+    Ok(dec
+        .parse::<u128>()
+        .with_context(|| format!("Invalid tally param: {dec}"))? as f64
+        / 1e18)
+}
+
+/// Formatted proposal time, gov v1 leaves the voting times unset until the voting period starts.
+pub fn datetime_str(ts: Option<Timestamp>) -> String {
+    match ts.map(|ts| Time::from_unix_timestamp(ts.seconds, ts.nanos as u32)) {
+        Some(Ok(t)) => t.to_string(),
+        _ => "–".to_string(),
+    }
+}
+
+/// Name of a proposal status, which has the same values across gov versions.
+pub fn proposal_status(status: i32) -> &'static str {
+    match ProposalStatus::from_i32(status).unwrap_or_default() {
+        ProposalStatus::DepositPeriod => "DepositPeriod",
+        ProposalStatus::Unspecified => "Unspecified",
+        ProposalStatus::VotingPeriod => "VotingPeriod",
+        ProposalStatus::Passed => "Passed",
+        ProposalStatus::Rejected => "Rejected",
+        ProposalStatus::Failed => "Failed",
+    }
+}
+
+/// Fields of a store code proposal, common to gov `v1beta1` and `v1`.
+struct ProposalFields {
+    proposal_id: u64,
+    title: String,
+    description: String,
+    run_as: String,
+    status: i32,
+    total_deposit: Vec<ProtoCoin>,
+    final_tally_result: TallyResult,
+    submit_time: Option<Timestamp>,
+    deposit_end_time: Option<Timestamp>,
+    voting_start_time: Option<Timestamp>,
+    voting_end_time: Option<Timestamp>,
+}
+
+impl TryFrom<Proposal> for ProposalFields {
+    type Error = anyhow::Error;
+
+    fn try_from(proposal: Proposal) -> Result<Self> {
+        let StoreCodeProposal {
+            title,
+            description,
+            run_as,
+            ..
+        } = StoreCodeProposal::decode(
+            proposal
+                .content
+                .with_context(|| "Proposal content not found")?
+                .value
+                .as_slice(),
+        )?;
+
+        Ok(ProposalFields {
+            proposal_id: proposal.proposal_id,
+            title,
+            description,
+            run_as,
+            status: proposal.status,
+            total_deposit: proposal.total_deposit,
+            final_tally_result: proposal.final_tally_result.unwrap_or_default(),
+            submit_time: proposal.submit_time,
+            deposit_end_time: proposal.deposit_end_time,
+            voting_start_time: proposal.voting_start_time,
+            voting_end_time: proposal.voting_end_time,
+        })
+    }
+}
+
+impl TryFrom<gov::Proposal> for ProposalFields {
+    type Error = anyhow::Error;
+
+    fn try_from(proposal: gov::Proposal) -> Result<Self> {
+        use cosmos_sdk_proto::cosmwasm::wasm::v1::MsgStoreCode;
+
+        let msg = proposal
+            .messages
+            .first()
+            .with_context(|| "Proposal has no messages")?;
+
+        // proposals submitted through v1beta1 carry their content in `MsgExecLegacyContent`
+        let (title, description, run_as) = match msg.type_url.as_str() {
+            "/cosmwasm.wasm.v1.MsgStoreCode" => (
+                proposal.title,
+                proposal.summary,
+                MsgStoreCode::decode(msg.value.as_slice())?.sender,
+            ),
+            "/cosmos.gov.v1.MsgExecLegacyContent" => {
+                let StoreCodeProposal {
+                    title,
+                    description,
+                    run_as,
+                    ..
+                } = StoreCodeProposal::decode(
+                    gov::MsgExecLegacyContent::decode(msg.value.as_slice())?
+                        .content
+                        .with_context(|| "Proposal content not found")?
+                        .value
+                        .as_slice(),
+                )?;
+                (title, description, run_as)
+            }
+            type_url => bail!(
+                "Proposal {} is not a store code proposal: {type_url}",
+                proposal.id
+            ),
+        };
+
+        let tally = proposal.final_tally_result.unwrap_or_default();
+
+        Ok(ProposalFields {
+            proposal_id: proposal.id,
+            title,
+            description,
+            run_as,
+            status: proposal.status,
+            total_deposit: proposal.total_deposit,
+            final_tally_result: TallyResult {
+                yes: tally.yes_count,
+                abstain: tally.abstain_count,
+                no: tally.no_count,
+                no_with_veto: tally.no_with_veto_count,
+            },
+            submit_time: proposal.submit_time,
+            deposit_end_time: proposal.deposit_end_time,
+            voting_start_time: proposal.voting_start_time,
+            voting_end_time: proposal.voting_end_time,
+        })
+    }
+}
+
 #[derive(Serialize)]
 pub struct QueryProposalResponse {
     pub proposal_id: u64,
@@ -181,6 +391,7 @@ pub struct QueryProposalResponse {
     pub total_deposit: Vec<Coin>,
     pub status: String,
     pub final_tally_result: TallyResultReponse,
+    pub live_tally: Option<LiveTally>,
     pub submit_time: String,
     pub deposit_end_time: String,
     pub voting_start_time: String,
@@ -195,8 +406,86 @@ pub struct TallyResultReponse {
     pub no_with_veto: String,
 }
 
+/// Tally of a proposal in voting period, with the percentages deciding its outcome.
+#[derive(Serialize, Debug, PartialEq)]
+pub struct LiveTally {
+    pub yes: String,
+    pub no: String,
+    pub no_with_veto: String,
+    pub abstain: String,
+    pub turnout: String,
+    pub quorum: String,
+    pub yes_ratio: String,
+    pub threshold: String,
+    pub veto_ratio: String,
+    pub veto_threshold: String,
+    pub outcome: String,
+}
+
 #[derive(Serialize)]
 pub struct Coin {
     pub amount: u128,
     pub denom: String,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn tally(yes: u128, no: u128, no_with_veto: u128, abstain: u128) -> TallyResult {
+        TallyResult {
+            yes: yes.to_string(),
+            abstain: abstain.to_string(),
+            no: no.to_string(),
+            no_with_veto: no_with_veto.to_string(),
+        }
+    }
+
+    fn params() -> TallyParams {
+        TallyParams {
+            quorum: b"200000000000000000".to_vec(),
+            threshold: b"500000000000000000".to_vec(),
+            veto_threshold: b"334000000000000000".to_vec(),
+        }
+    }
+
+    #[test]
+    fn project_passing_tally() {
+        let live_tally = project_tally(&tally(60, 20, 10, 10), 400, &params()).unwrap();
+
+        assert_eq!(live_tally.turnout, "25.00%");
+        assert_eq!(live_tally.quorum, "20.00%");
+        assert_eq!(live_tally.yes_ratio, "66.67%");
+        assert_eq!(live_tally.threshold, "50.00%");
+        assert_eq!(live_tally.veto_ratio, "10.00%");
+        assert_eq!(live_tally.veto_threshold, "33.40%");
+        assert_eq!(live_tally.outcome, "Passed");
+    }
+
+    #[test]
+    fn project_rejected_tally() {
+        let outcome = |t: TallyResult, bonded_tokens: u128| {
+            project_tally(&t, bonded_tokens, &params()).unwrap().outcome
+        };
+
+        assert_eq!(
+            outcome(tally(60, 20, 10, 10), 1000),
+            "Rejected, quorum not reached"
+        );
+        assert_eq!(
+            outcome(tally(0, 0, 0, 100), 100),
+            "Rejected, all votes abstain"
+        );
+        assert_eq!(outcome(tally(60, 0, 40, 0), 100), "Rejected, vetoed");
+        assert_eq!(
+            outcome(tally(50, 50, 0, 0), 100),
+            "Rejected, threshold not reached"
+        );
+    }
+
+    #[test]
+    fn dec_from_bytes_accepts_both_encodings() {
+        assert_eq!(dec_from_bytes(b"334000000000000000").unwrap(), 0.334);
+        assert_eq!(dec_from_bytes(b"0.334").unwrap(), 0.334);
+    }
+}
@@ -0,0 +1,185 @@
+use super::propose_wasm::{MsgStoreAndInstantiateContract, StoreAndInstantiateContractProposal};
+use crate::attrs_format;
+use crate::modules::wasm::ops::build;
+use crate::support::future::block;
+use crate::support::gov::{resolve_gov_version, GovVersion, MsgExecLegacyContent};
+use crate::support::ops_response::OpResponseDisplay;
+use crate::support::wasm::wasm_checksum;
+use crate::{framework::Context, modules::wasm::WasmConfig, support::cosmos::Client};
+use anyhow::{Context as _, Result};
+use cosmos_sdk_proto::cosmwasm::wasm::v1 as wasm;
+use cosmos_sdk_proto::traits::Message;
+use cosmrs::Any;
+use flate2::read::GzDecoder;
+use serde::Serialize;
+use std::fs;
+use std::io::Read;
+
+/// Rebuild the workspace's contracts and find the artifact matching the code stored by `proposal_id`.
+pub fn verify<'a, Ctx: Context<'a, WasmConfig>>(
+    ctx: &Ctx,
+    proposal_id: u64,
+    gov_version: Option<GovVersion>,
+    network: &str,
+    no_rebuild: bool,
+) -> Result<VerifyResponse> {
+    let global_config = ctx.global_config()?;
+
+    let network_info = global_config
+        .networks()
+        .get(network)
+        .with_context(|| format!("Unable to find network config: {network}"))?
+        .to_owned();
+
+    let client = Client::new(network_info, &ctx.root()?, &global_config);
+    let gov_version = resolve_gov_version(&client, gov_version)?;
+
+    let proposed_wasm = block(async {
+        let wasm = match gov_version {
+            GovVersion::V1 => client
+                .proposal_v1(&proposal_id)
+                .await?
+                .messages
+                .iter()
+                .map(proposed_wasm)
+                .find_map(Result::transpose)
+                .transpose()?,
+            GovVersion::V1beta1 => client
+                .proposal(&proposal_id)
+                .await?
+                .content
+                .map(|content| proposed_wasm(&content))
+                .transpose()?
+                .flatten(),
+        };
+        wasm.with_context(|| format!("Proposal {proposal_id} does not store code"))
+    })?;
+    let checksum = wasm_checksum(&unzip(proposed_wasm)?);
+
+    if !no_rebuild {
+        build(ctx, &false, &false)?;
+    }
+
+    let artifacts = ctx.root()?.join("artifacts");
+    let artifact = fs::read_dir(&artifacts)
+        .with_context(|| format!("Unable to read artifacts in `{}`", artifacts.display()))?
+        .filter_map(|entry| entry.ok().map(|e| e.path()))
+        .filter(|path| path.extension().is_some_and(|ext| ext == "wasm"))
+        .find(|path| fs::read(path).is_ok_and(|wasm| wasm_checksum(&wasm) == checksum))
+        .with_context(|| {
+            format!("No local artifact matches checksum `{checksum}` of proposal {proposal_id}")
+        })?;
+
+    let verify_response = VerifyResponse {
+        proposal_id,
+        checksum,
+        artifact: artifact
+            .file_name()
+            .map(|f| f.to_string_lossy().to_string())
+            .unwrap_or_default(),
+    };
+    verify_response.log();
+
+    Ok(verify_response)
+}
+
+/// Wasm stored by a proposal content or message, `None` for those not storing code.
+fn proposed_wasm(any: &Any) -> Result<Option<Vec<u8>>> {
+    let value = any.value.as_slice();
+
+    Ok(match any.type_url.as_str() {
+        "/cosmwasm.wasm.v1.StoreCodeProposal" => {
+            Some(wasm::StoreCodeProposal::decode(value)?.wasm_byte_code)
+        }
+        "/cosmwasm.wasm.v1.StoreAndInstantiateContractProposal" => {
+            Some(StoreAndInstantiateContractProposal::decode(value)?.wasm_byte_code)
+        }
+        "/cosmwasm.wasm.v1.MsgStoreCode" => Some(wasm::MsgStoreCode::decode(value)?.wasm_byte_code),
+        "/cosmwasm.wasm.v1.MsgStoreAndInstantiateContract" => {
+            Some(MsgStoreAndInstantiateContract::decode(value)?.wasm_byte_code)
+        }
+        "/cosmos.gov.v1.MsgExecLegacyContent" => match MsgExecLegacyContent::decode(value)?.content
+        {
+            Some(content) => proposed_wasm(&content)?,
+            None => None,
+        },
+        _ => None,
+    })
+}
+
+/// Wasm as stored on chain, which unzips the code gzipped by the chains' cli.
+fn unzip(wasm: Vec<u8>) -> Result<Vec<u8>> {
+    if !wasm.starts_with(&[0x1f, 0x8b]) {
+        return Ok(wasm);
+    }
+
+    let mut unzipped = vec![];
+    GzDecoder::new(wasm.as_slice()).read_to_end(&mut unzipped)?;
+    Ok(unzipped)
+}
+
+#[derive(Serialize)]
+pub struct VerifyResponse {
+    pub proposal_id: u64,
+    pub checksum: String,
+    pub artifact: String,
+}
+
+impl OpResponseDisplay for VerifyResponse {
+    fn headline() -> &'static str {
+        "Proposed code matches the local build!! 🎉"
+    }
+    fn attrs(&self) -> Vec<String> {
+        attrs_format! { self | proposal_id, checksum, artifact }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::support::proto::MessageExt;
+    use flate2::{write::GzEncoder, Compression};
+    use std::io::Write;
+
+    #[test]
+    fn proposed_wasm_of_legacy_content_in_v1_message() {
+        let content = Any {
+            type_url: "/cosmwasm.wasm.v1.StoreCodeProposal".to_owned(),
+            value: wasm::StoreCodeProposal {
+                wasm_byte_code: b"\0asm".to_vec(),
+                ..Default::default()
+            }
+            .to_bytes()
+            .unwrap(),
+        };
+        let msg = Any {
+            type_url: "/cosmos.gov.v1.MsgExecLegacyContent".to_owned(),
+            value: MsgExecLegacyContent {
+                content: Some(content),
+                authority: String::new(),
+            }
+            .to_bytes()
+            .unwrap(),
+        };
+
+        assert_eq!(proposed_wasm(&msg).unwrap(), Some(b"\0asm".to_vec()));
+        assert_eq!(
+            proposed_wasm(&Any {
+                type_url: "/cosmwasm.wasm.v1.MsgMigrateContract".to_owned(),
+                value: vec![],
+            })
+            .unwrap(),
+            None
+        );
+    }
+
+    #[test]
+    fn unzip_gzipped_wasm() {
+        let mut encoder = GzEncoder::new(vec![], Compression::default());
+        encoder.write_all(b"\0asm").unwrap();
+        let gzipped = encoder.finish().unwrap();
+
+        assert_eq!(unzip(gzipped).unwrap(), b"\0asm".to_vec());
+        assert_eq!(unzip(b"\0asm".to_vec()).unwrap(), b"\0asm".to_vec());
+    }
+}
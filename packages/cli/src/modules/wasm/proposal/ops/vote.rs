@@ -1,7 +1,11 @@
 use crate::attrs_format;
-use crate::support::cosmos::ResponseValuePicker;
+use crate::support::cosmos::{ResponseValuePicker, TxOptions};
 use crate::support::future::block;
 use crate::support::gas::Gas;
+use crate::support::gov::{
+    self, resolve_gov_version, GovVersion, MSG_VOTE_V1BETA1_TYPE_URL, MSG_VOTE_V1_TYPE_URL,
+    MSG_VOTE_WEIGHTED_V1BETA1_TYPE_URL, MSG_VOTE_WEIGHTED_V1_TYPE_URL,
+};
 use crate::support::ops_response::OpResponseDisplay;
 use crate::support::state::State;
 use crate::{framework::Context, modules::wasm::WasmConfig, support::cosmos::Client};
@@ -9,7 +13,7 @@ use anyhow::anyhow;
 use anyhow::{Context as _, Result};
 use cosmrs::crypto::secp256k1::SigningKey;
 use cosmrs::tx::MessageExt;
-use cosmrs::Any;
+use cosmrs::{AccountId, Any};
 use serde::Serialize;
 use std::str::FromStr;
 use std::vec;
@@ -17,16 +21,17 @@ use std::vec;
 #[allow(clippy::too_many_arguments)]
 pub fn vote<'a, Ctx: Context<'a, WasmConfig>>(
     ctx: &Ctx,
-    contract_name: &str,
+    contract_name: Option<&str>,
+    proposal_id: Option<u64>,
     option: &str,
+    gov_version: Option<GovVersion>,
     network: &str,
     gas: &Gas,
-    timeout_height: &u32,
+    tx_options: &TxOptions,
     signing_key: SigningKey,
     account_sequence: &Option<u64>,
 ) -> Result<VoteResponse> {
     let global_config = ctx.global_config()?;
-    let account_prefix = global_config.account_prefix().as_str();
 
     let network_info = global_config
         .networks()
@@ -34,32 +39,23 @@ pub fn vote<'a, Ctx: Context<'a, WasmConfig>>(
         .with_context(|| format!("Unable to find network config: {network}"))?
         .to_owned();
 
-    let client = Client::new(network_info.clone()).to_signing_client(signing_key, account_prefix);
+    let client = Client::new(network_info.clone(), &ctx.root()?, &global_config);
+    let gov_version = resolve_gov_version(&client, gov_version)?;
+    let client = client.to_signing_client(signing_key);
 
-    let state = State::load_by_network(network_info, ctx.root()?)?;
-    let proposal_id = state
-        .get_ref(network, contract_name)?
-        .proposal()
-        .store_code()
-        .with_context(|| format!("Unable to retrieve proposal_id for {contract_name}"))?;
-
-    let option = option.parse::<VoteOptionImpl>()?;
-    let option = cosmos_sdk_proto::cosmos::gov::v1beta1::VoteOption::from(option);
-
-    let msg_vote = cosmos_sdk_proto::cosmos::gov::v1beta1::MsgVote {
+    let msg_vote = vote_msg(
+        ctx,
+        contract_name,
         proposal_id,
-        voter: client.signer_account_id().to_string(),
-        option: option.into(),
-    };
-
-    let msg_vote = Any {
-        type_url: "/cosmos.gov.v1beta1.MsgVote".to_owned(),
-        value: msg_vote.to_bytes()?,
-    };
+        option,
+        gov_version,
+        network,
+        &client.signer_account_id(),
+    )?;
 
     block(async {
         let response = client
-            .sign_and_broadcast(vec![msg_vote], gas, "", timeout_height, account_sequence)
+            .sign_and_broadcast(vec![msg_vote], gas, tx_options, account_sequence)
             .await?;
 
         let proposal_id: u64 = response.pick("proposal_vote", "proposal_id").parse()?;
@@ -72,6 +68,178 @@ pub fn vote<'a, Ctx: Context<'a, WasmConfig>>(
     })
 }
 
+/// Build the `MsgVote` cast by `voter` on `proposal_id`, or on the contract's store code proposal.
+/// A weighted `option`, eg. `yes=0.6,no=0.4`, builds a `MsgVoteWeighted` instead.
+#[allow(clippy::too_many_arguments)]
+pub fn vote_msg<'a, Ctx: Context<'a, WasmConfig>>(
+    ctx: &Ctx,
+    contract_name: Option<&str>,
+    proposal_id: Option<u64>,
+    option: &str,
+    gov_version: GovVersion,
+    network: &str,
+    voter: &AccountId,
+) -> Result<Any> {
+    let proposal_id = target_proposal_id(ctx, contract_name, proposal_id, network)?;
+
+    if option.contains('=') {
+        let options = option.parse::<WeightedVoteOptions>()?;
+        return weighted_vote_msg(proposal_id, options, gov_version, voter);
+    }
+
+    let option = option.parse::<VoteOptionImpl>()?;
+    let option = cosmos_sdk_proto::cosmos::gov::v1beta1::VoteOption::from(option);
+
+    // vote options share the same values across gov versions
+    Ok(match gov_version {
+        GovVersion::V1 => Any {
+            type_url: MSG_VOTE_V1_TYPE_URL.to_owned(),
+            value: gov::MsgVote {
+                proposal_id,
+                voter: voter.to_string(),
+                option: option.into(),
+                metadata: String::new(),
+            }
+            .to_bytes()?,
+        },
+        GovVersion::V1beta1 => Any {
+            type_url: MSG_VOTE_V1BETA1_TYPE_URL.to_owned(),
+            value: cosmos_sdk_proto::cosmos::gov::v1beta1::MsgVote {
+                proposal_id,
+                voter: voter.to_string(),
+                option: option.into(),
+            }
+            .to_bytes()?,
+        },
+    })
+}
+
+fn weighted_vote_msg(
+    proposal_id: u64,
+    options: WeightedVoteOptions,
+    gov_version: GovVersion,
+    voter: &AccountId,
+) -> Result<Any> {
+    let WeightedVoteOptions(options) = options;
+
+    Ok(match gov_version {
+        GovVersion::V1 => Any {
+            type_url: MSG_VOTE_WEIGHTED_V1_TYPE_URL.to_owned(),
+            value: gov::MsgVoteWeighted {
+                proposal_id,
+                voter: voter.to_string(),
+                options: options
+                    .iter()
+                    .map(|(option, weight)| gov::WeightedVoteOption {
+                        option: (*option).into(),
+                        weight: decimal_string(*weight),
+                    })
+                    .collect(),
+                metadata: String::new(),
+            }
+            .to_bytes()?,
+        },
+        // legacy `Dec` is encoded as its 18 decimals fixed point integer
+        GovVersion::V1beta1 => Any {
+            type_url: MSG_VOTE_WEIGHTED_V1BETA1_TYPE_URL.to_owned(),
+            value: cosmos_sdk_proto::cosmos::gov::v1beta1::MsgVoteWeighted {
+                proposal_id,
+                voter: voter.to_string(),
+                options: options
+                    .iter()
+                    .map(|(option, weight)| {
+                        cosmos_sdk_proto::cosmos::gov::v1beta1::WeightedVoteOption {
+                            option: (*option).into(),
+                            weight: weight.to_string(),
+                        }
+                    })
+                    .collect(),
+            }
+            .to_bytes()?,
+        },
+    })
+}
+
+/// `proposal_id` if given, otherwise the store code proposal recorded for the contract.
+pub fn target_proposal_id<'a, Ctx: Context<'a, WasmConfig>>(
+    ctx: &Ctx,
+    contract_name: Option<&str>,
+    proposal_id: Option<u64>,
+    network: &str,
+) -> Result<u64> {
+    if let Some(proposal_id) = proposal_id {
+        return Ok(proposal_id);
+    }
+
+    let contract_name =
+        contract_name.with_context(|| "Either contract name or proposal id is required")?;
+    let network_info = ctx
+        .global_config()?
+        .networks()
+        .get(network)
+        .with_context(|| format!("Unable to find network config: {network}"))?
+        .to_owned();
+
+    let state = State::load_by_network(network_info, ctx.root()?)?;
+    state
+        .get_ref(network, contract_name)?
+        .proposal()
+        .store_code()
+        .with_context(|| format!("Unable to retrieve proposal_id for {contract_name}"))
+}
+
+/// One in 18 decimals fixed point, the precision of the sdk's `Dec`.
+const DEC_ONE: u128 = 1_000_000_000_000_000_000;
+
+/// Vote options with their weights in 18 decimals fixed point, parsed from eg. `yes=0.6,no=0.4`.
+#[derive(Debug, PartialEq, Eq)]
+struct WeightedVoteOptions(Vec<(cosmos_sdk_proto::cosmos::gov::v1beta1::VoteOption, u128)>);
+
+impl FromStr for WeightedVoteOptions {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let options = s
+            .split(',')
+            .map(|o| {
+                let (option, weight) = o
+                    .split_once('=')
+                    .with_context(|| format!("Invalid weighted vote option: {o}"))?;
+                let VoteOptionImpl(option) = option.trim().parse()?;
+                anyhow::Ok((option, parse_decimal(weight.trim())?))
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        if options.iter().map(|(_, w)| w).sum::<u128>() != DEC_ONE {
+            return Err(anyhow!("Weights of the vote options must add up to 1: {s}"));
+        }
+
+        Ok(WeightedVoteOptions(options))
+    }
+}
+
+fn parse_decimal(s: &str) -> Result<u128> {
+    let (int, frac) = s.split_once('.').unwrap_or((s, ""));
+    if int.is_empty() && frac.is_empty() || frac.len() > 18 {
+        return Err(anyhow!("Invalid weight: {s}"));
+    }
+
+    let int: u128 = if int.is_empty() { 0 } else { int.parse()? };
+    let frac: u128 = if frac.is_empty() {
+        0
+    } else {
+        format!("{frac:0<18}").parse()?
+    };
+
+    int.checked_mul(DEC_ONE)
+        .and_then(|i| i.checked_add(frac))
+        .with_context(|| format!("Weight out of range: {s}"))
+}
+
+fn decimal_string(dec: u128) -> String {
+    format!("{}.{:018}", dec / DEC_ONE, dec % DEC_ONE)
+}
+
 struct VoteOptionImpl(cosmos_sdk_proto::cosmos::gov::v1beta1::VoteOption);
 
 impl From<cosmos_sdk_proto::cosmos::gov::v1beta1::VoteOption> for VoteOptionImpl {
@@ -116,3 +284,40 @@ impl OpResponseDisplay for VoteResponse {
         attrs_format! { self | proposal_id }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use cosmos_sdk_proto::cosmos::gov::v1beta1::VoteOption;
+
+    #[test]
+    fn parse_weighted_vote_options() {
+        assert_eq!(
+            "yes=0.6, no=.3,abstain=0.1"
+                .parse::<WeightedVoteOptions>()
+                .unwrap(),
+            WeightedVoteOptions(vec![
+                (VoteOption::Yes, 600_000_000_000_000_000),
+                (VoteOption::No, 300_000_000_000_000_000),
+                (VoteOption::Abstain, 100_000_000_000_000_000),
+            ])
+        );
+        assert_eq!(
+            "no_with_veto=1".parse::<WeightedVoteOptions>().unwrap(),
+            WeightedVoteOptions(vec![(VoteOption::NoWithVeto, DEC_ONE)])
+        );
+
+        assert!("yes=0.6,no=0.3".parse::<WeightedVoteOptions>().is_err());
+        assert!("yes=0.5,maybe=0.5".parse::<WeightedVoteOptions>().is_err());
+        assert!("yes".parse::<WeightedVoteOptions>().is_err());
+    }
+
+    #[test]
+    fn decimal_string_has_18_decimals() {
+        assert_eq!(decimal_string(DEC_ONE), "1.000000000000000000");
+        assert_eq!(
+            decimal_string(parse_decimal("0.25").unwrap()),
+            "0.250000000000000000"
+        );
+    }
+}
@@ -0,0 +1,122 @@
+use super::query::{datetime_str, proposal_status};
+use crate::attrs_format;
+use crate::support::future::block;
+use crate::support::gov::{resolve_gov_version, GovVersion};
+use crate::support::ops_response::OpResponseDisplay;
+use crate::support::state::State;
+use crate::support::wasm::get_code_id;
+use crate::{framework::Context, modules::wasm::WasmConfig, support::cosmos::Client};
+use anyhow::{bail, Context as _, Result};
+use cosmos_sdk_proto::cosmos::gov::v1beta1::{ProposalStatus, TallyResult};
+use serde::Serialize;
+use std::time::Duration;
+
+/// Poll the contract's latest code upload proposal every `interval` until its voting ends,
+/// then record the code id it stored. Fails if the proposal does not pass.
+pub fn wait<'a, Ctx: Context<'a, WasmConfig>>(
+    ctx: &Ctx,
+    contract_name: &str,
+    gov_version: Option<GovVersion>,
+    network: &str,
+    interval: Duration,
+) -> Result<WaitResponse> {
+    let global_config = ctx.global_config()?;
+
+    let network_info = global_config
+        .networks()
+        .get(network)
+        .with_context(|| format!("Unable to find network config: {network}"))?
+        .to_owned();
+
+    let client = Client::new(network_info.clone(), &ctx.root()?, &global_config);
+
+    let state = State::load_by_network(network_info.clone(), ctx.root()?)?;
+    let proposal_id = state
+        .get_ref(network, contract_name)?
+        .proposal()
+        .latest_code_upload()
+        .with_context(|| {
+            format!(
+                "Proposal store code not found for contract `{contract_name}` on network `{network}`"
+            )
+        })?;
+    let gov_version = resolve_gov_version(&client, gov_version)?;
+
+    block(async {
+        let status = loop {
+            let (status, voting_end_time) = match gov_version {
+                GovVersion::V1 => {
+                    let proposal = client.proposal_v1(&proposal_id).await?;
+                    (proposal.status, proposal.voting_end_time)
+                }
+                GovVersion::V1beta1 => {
+                    let proposal = client.proposal(&proposal_id).await?;
+                    (proposal.status, proposal.voting_end_time)
+                }
+            };
+
+            match ProposalStatus::from_i32(status).unwrap_or_default() {
+                ProposalStatus::DepositPeriod | ProposalStatus::VotingPeriod => {}
+                status => break status,
+            }
+
+            let TallyResult {
+                yes,
+                abstain,
+                no,
+                no_with_veto,
+            } = client.tally_result(&proposal_id).await?;
+
+            println!(
+                "  proposal {proposal_id} · {} · yes: {yes}, no: {no}, no_with_veto: {no_with_veto}, abstain: {abstain} · voting_end_time: {}",
+                proposal_status(status),
+                datetime_str(voting_end_time)
+            );
+
+            tokio::time::sleep(interval).await;
+        };
+
+        if status != ProposalStatus::Passed {
+            bail!(
+                "Proposal {proposal_id} did not pass: {}",
+                proposal_status(status as i32)
+            );
+        }
+
+        let code_id = get_code_id(network_info.rpc_endpoint(), &proposal_id).await?;
+        let code_id: u64 = code_id
+            .parse()
+            .with_context(|| format!("unable to parse code_id from proposal: {code_id}"))?;
+
+        State::update_state_file(
+            network_info.network_variant(),
+            ctx.root()?,
+            &|s: &State| -> State { s.update_code_id(network, contract_name, &code_id) },
+        )?;
+
+        let wait_response = WaitResponse {
+            proposal_id,
+            status: proposal_status(status as i32).to_string(),
+            code_id,
+        };
+        wait_response.log();
+
+        Ok(wait_response)
+    })
+}
+
+#[derive(Serialize)]
+pub struct WaitResponse {
+    pub proposal_id: u64,
+    pub status: String,
+    pub code_id: u64,
+}
+
+impl OpResponseDisplay for WaitResponse {
+    fn headline() -> &'static str {
+        "Proposal has passed!! 🎉"
+    }
+    fn attrs(&self) -> Vec<String> {
+        attrs_format! { self | proposal_id, status, code_id }
+    }
+}
@@ -1,10 +1,12 @@
-use anyhow::Result;
 use clap::Parser;
 use serde::{Deserialize, Serialize};
+use std::fmt::{self, Display};
 
 const PROPOSAL_GROUP: &str = "detailed-proposal";
 
-#[derive(Serialize, Deserialize, PartialEq, Eq, Debug, Parser)]
+/// Details of a wasm proposal, given through args or a proposal file.
+/// Shared by all the wasm proposals despite its name.
+#[derive(Serialize, Deserialize, PartialEq, Eq, Debug, Clone, Parser)]
 #[clap(group = clap::ArgGroup::new(PROPOSAL_GROUP)
     .multiple(true)
     .conflicts_with("proposal"))]
@@ -23,15 +25,53 @@ pub struct StoreCodeProposal {
     #[clap(long, group = PROPOSAL_GROUP)]
     pub deposit: Option<String>,
 
-    /// Unpin code on upload
+    /// Unpin code on upload, only for proposals storing code
     #[clap(long, group = PROPOSAL_GROUP, default_value="false")]
     #[serde(default)]
     pub unpin_code: bool,
+
+    /// Proposal metadata, eg. a link to its off-chain document. Only for gov v1 proposals
+    #[clap(long, group = PROPOSAL_GROUP, default_value="")]
+    #[serde(default)]
+    pub metadata: String,
+
+    /// Submit as an expedited proposal, with a shorter voting period. Only for gov v1 proposals
+    #[clap(long, group = PROPOSAL_GROUP, default_value="false")]
+    #[serde(default)]
+    pub expedited: bool,
 }
 
 impl StoreCodeProposal {
-    pub fn description_with_metadata(&self) -> Result<String> {
-        Ok(vec![self.description.trim()].join("\n"))
+    /// Description followed by the attestation of the proposed code's build.
+    pub fn description_with_metadata(&self, attestation: &BuildAttestation) -> String {
+        [self.description.trim(), &attestation.to_string()]
+            .join("\n\n")
+            .trim_start()
+            .to_string()
+    }
+}
+
+/// How the proposed wasm was built, for voters to rebuild it and compare its checksum.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BuildAttestation {
+    pub checksum: String,
+    pub optimizer: String,
+    pub git_commit: String,
+    pub build_command: String,
+}
+
+impl Display for BuildAttestation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "---\n\
+            Build attestation\n\
+            - checksum (sha256): {}\n\
+            - optimizer: {}\n\
+            - git commit: {}\n\
+            - build command: `{}`",
+            self.checksum, self.optimizer, self.git_commit, self.build_command
+        )
     }
 }
 
@@ -54,9 +94,57 @@ mod tests {
             ),
             deposit: Some("1000uosmo".to_string()),
             unpin_code: true,
+            metadata: String::new(),
+            expedited: false,
         }
     }
 
+    #[test]
+    fn description_with_build_attestation() {
+        let attestation = BuildAttestation {
+            checksum: "e3b0c442".to_string(),
+            optimizer: "cosmwasm/workspace-optimizer:0.14.0".to_string(),
+            git_commit: "d2a1f3c".to_string(),
+            build_command: "beaker wasm build".to_string(),
+        };
+
+        assert_eq!(
+            proposal_fixture().description_with_metadata(&attestation),
+            trim_indent(
+                r#"
+                A lengthy proposal description
+                goes here
+                we expect this to be many lines...
+
+                ---
+                Build attestation
+                - checksum (sha256): e3b0c442
+                - optimizer: cosmwasm/workspace-optimizer:0.14.0
+                - git commit: d2a1f3c
+                - build command: `beaker wasm build`
+                "#,
+            )
+            .trim_end()
+        );
+    }
+
+    #[test]
+    fn store_code_proposal_v1_fields_yaml() {
+        let yaml = &trim_indent(
+            r#"
+                title: Proposal to allow DappName to be enabled in Osmosis
+                metadata: ipfs://CID
+                expedited: true
+            "#,
+        );
+
+        let prop: StoreCodeProposal = serde_yaml::from_str(yaml).unwrap();
+
+        assert_eq!(prop.metadata, "ipfs://CID");
+        assert!(prop.expedited);
+        assert!(!prop.unpin_code);
+    }
+
     #[test]
     fn store_code_proposal_yaml() {
         let yaml = &trim_indent(
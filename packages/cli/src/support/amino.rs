@@ -0,0 +1,401 @@
+use std::collections::BTreeMap;
+
+use anyhow::{bail, Context, Result};
+use cosmos_sdk_proto::cosmos::authz::v1beta1::MsgExec;
+use cosmos_sdk_proto::cosmos::tx::v1beta1::{AuthInfo, TxBody};
+use cosmos_sdk_proto::cosmwasm::wasm::v1::{
+    AccessConfig, AccessType, MsgClearAdmin, MsgMigrateContract, MsgUpdateAdmin,
+};
+use cosmos_sdk_proto::traits::Message;
+use cosmos_sdk_proto::Any;
+use serde_json::value::{to_raw_value, RawValue};
+use serde_json::{json, Value};
+
+use super::authz::MSG_EXEC_TYPE_URL;
+use super::wasm::{MsgUpdateInstantiateConfig, MSG_UPDATE_INSTANTIATE_CONFIG_TYPE_URL};
+
+/// Sign bytes of `SIGN_MODE_LEGACY_AMINO_JSON`, which is what multisig members sign.
+/// Only the messages that have an amino json representation below are supported.
+pub fn sign_bytes(
+    chain_id: &str,
+    account_number: u64,
+    sequence: u64,
+    body_bytes: &[u8],
+    auth_info_bytes: &[u8],
+) -> Result<Vec<u8>> {
+    let body = TxBody::decode(body_bytes)?;
+    let fee = AuthInfo::decode(auth_info_bytes)?
+        .fee
+        .with_context(|| "Transaction has no fee")?;
+
+    let mut std_fee = json!({
+        "amount": fee
+            .amount
+            .iter()
+            .map(|c| json!({ "amount": c.amount, "denom": c.denom }))
+            .collect::<Vec<_>>(),
+        "gas": fee.gas_limit.to_string(),
+    });
+    if !fee.payer.is_empty() {
+        std_fee["payer"] = fee.payer.into();
+    }
+    if !fee.granter.is_empty() {
+        std_fee["granter"] = fee.granter.into();
+    }
+
+    let mut sign_doc = json!({
+        "account_number": account_number.to_string(),
+        "chain_id": chain_id,
+        "fee": std_fee,
+        "memo": body.memo,
+        "sequence": sequence.to_string(),
+    });
+    if body.timeout_height != 0 {
+        sign_doc["timeout_height"] = body.timeout_height.to_string().into();
+    }
+    let msgs = body
+        .messages
+        .iter()
+        .map(msg_json)
+        .collect::<Result<Vec<_>>>()?;
+    let sign_doc = with_raw_field(sign_doc, "msgs", to_raw_value(&msgs)?)?;
+
+    Ok(go_json_escape(&sort_keys(&sign_doc)?).into_bytes())
+}
+
+fn msg_json(msg: &Any) -> Result<Box<RawValue>> {
+    let (amino_type, value) = match msg.type_url.as_str() {
+        "/cosmwasm.wasm.v1.MsgMigrateContract" => {
+            let m = MsgMigrateContract::decode(msg.value.as_slice())?;
+            (
+                "wasm/MsgMigrateContract",
+                with_raw_field(
+                    json!({
+                        "sender": m.sender,
+                        "contract": m.contract,
+                        "code_id": m.code_id.to_string(),
+                    }),
+                    "msg",
+                    serde_json::from_slice::<&RawValue>(&m.msg)?.to_owned(),
+                )?,
+            )
+        }
+        "/cosmwasm.wasm.v1.MsgUpdateAdmin" => {
+            let m = MsgUpdateAdmin::decode(msg.value.as_slice())?;
+            (
+                "wasm/MsgUpdateAdmin",
+                to_raw_value(&json!({
+                    "sender": m.sender,
+                    "new_admin": m.new_admin,
+                    "contract": m.contract,
+                }))?,
+            )
+        }
+        "/cosmwasm.wasm.v1.MsgClearAdmin" => {
+            let m = MsgClearAdmin::decode(msg.value.as_slice())?;
+            (
+                "wasm/MsgClearAdmin",
+                to_raw_value(&json!({
+                    "sender": m.sender,
+                    "contract": m.contract,
+                }))?,
+            )
+        }
+        MSG_EXEC_TYPE_URL => {
+            let m = MsgExec::decode(msg.value.as_slice())?;
+            let msgs = m.msgs.iter().map(msg_json).collect::<Result<Vec<_>>>()?;
+            (
+                "cosmos-sdk/MsgExec",
+                with_raw_field(
+                    json!({ "grantee": m.grantee }),
+                    "msgs",
+                    to_raw_value(&msgs)?,
+                )?,
+            )
+        }
+        MSG_UPDATE_INSTANTIATE_CONFIG_TYPE_URL => {
+            let m = MsgUpdateInstantiateConfig::decode(msg.value.as_slice())?;
+            let mut value = json!({
+                "sender": m.sender,
+                "code_id": m.code_id.to_string(),
+            });
+            if let Some(permission) = m.new_instantiate_permission {
+                value["new_instantiate_permission"] = access_config_json(permission)?;
+            }
+            ("wasm/MsgUpdateInstantiateConfig", to_raw_value(&value)?)
+        }
+        type_url => bail!("`{type_url}` can not be signed in amino json sign mode"),
+    };
+
+    with_raw_field(json!({ "type": amino_type }), "value", value)
+}
+
+/// `object` with `raw` added under `key`. Raw json can not be put in a `Value`
+/// without parsing it, which would round large numbers in contract msgs to f64.
+fn with_raw_field(object: Value, key: &str, raw: Box<RawValue>) -> Result<Box<RawValue>> {
+    let Value::Object(fields) = object else {
+        bail!("`{object}` is not a json object");
+    };
+
+    let mut fields = fields
+        .into_iter()
+        .map(|(k, v)| Ok((k, to_raw_value(&v)?)))
+        .collect::<Result<BTreeMap<_, _>>>()?;
+    fields.insert(key.to_string(), raw);
+    Ok(to_raw_value(&fields)?)
+}
+
+/// `AccessConfig` with the permission named like wasmd's `AccessType` text marshalling
+/// and empty fields omitted.
+fn access_config_json(config: AccessConfig) -> Result<Value> {
+    let permission = match AccessType::from_i32(config.permission) {
+        Some(AccessType::Nobody) => "Nobody",
+        Some(AccessType::OnlyAddress) => "OnlyAddress",
+        Some(AccessType::Everybody) => "Everybody",
+        Some(AccessType::AnyOfAddresses) => "AnyOfAddresses",
+        _ => bail!("Unknown access type `{}`", config.permission),
+    };
+
+    let mut value = json!({ "permission": permission });
+    if !config.address.is_empty() {
+        value["address"] = config.address.into();
+    }
+    if !config.addresses.is_empty() {
+        value["addresses"] = config.addresses.into();
+    }
+    Ok(value)
+}
+
+/// Rewrite every object with its keys in sorted order, like the sdk's `MustSortJSON`.
+/// Numbers are kept as written instead of going through f64.
+fn sort_keys(raw: &RawValue) -> Result<String> {
+    let json = raw.get().trim();
+    Ok(match json.as_bytes().first() {
+        Some(b'{') => {
+            let fields = serde_json::from_str::<BTreeMap<String, &RawValue>>(json)?
+                .into_iter()
+                .map(|(k, v)| Ok(format!("{}:{}", serde_json::to_string(&k)?, sort_keys(v)?)))
+                .collect::<Result<Vec<_>>>()?;
+            format!("{{{}}}", fields.join(","))
+        }
+        Some(b'[') => {
+            let values = serde_json::from_str::<Vec<&RawValue>>(json)?
+                .into_iter()
+                .map(sort_keys)
+                .collect::<Result<Vec<_>>>()?;
+            format!("[{}]", values.join(","))
+        }
+        Some(b'"') => serde_json::to_string(&serde_json::from_str::<String>(json)?)?,
+        _ => json.to_string(),
+    })
+}
+
+/// Go's `json.Marshal` escapes html characters and line separators, serde_json does not.
+fn go_json_escape(s: &str) -> String {
+    s.replace('<', "\\u003c")
+        .replace('>', "\\u003e")
+        .replace('&', "\\u0026")
+        .replace('\u{2028}', "\\u2028")
+        .replace('\u{2029}', "\\u2029")
+}
+
+#[cfg(test)]
+mod tests {
+    use cosmos_sdk_proto::cosmos::base::v1beta1::Coin;
+    use cosmos_sdk_proto::cosmos::tx::v1beta1::Fee;
+
+    use super::*;
+    use crate::support::proto::MessageExt;
+
+    #[test]
+    fn sign_bytes_are_sorted_and_escaped() {
+        let body = TxBody {
+            messages: vec![Any {
+                type_url: "/cosmwasm.wasm.v1.MsgMigrateContract".to_string(),
+                value: MsgMigrateContract {
+                    sender: "osmo1sender".to_string(),
+                    contract: "osmo1contract".to_string(),
+                    code_id: 5,
+                    msg: br#"{"z":1,"a":"<b>"}"#.to_vec(),
+                }
+                .to_bytes()
+                .unwrap(),
+            }],
+            memo: "".to_string(),
+            ..Default::default()
+        };
+        let auth_info = AuthInfo {
+            fee: Some(Fee {
+                amount: vec![Coin {
+                    denom: "uosmo".to_string(),
+                    amount: "1000".to_string(),
+                }],
+                gas_limit: 200000,
+                ..Default::default()
+            }),
+            ..Default::default()
+        };
+
+        let sign_bytes = sign_bytes(
+            "osmosis-1",
+            7,
+            3,
+            &body.to_bytes().unwrap(),
+            &auth_info.to_bytes().unwrap(),
+        )
+        .unwrap();
+
+        assert_eq!(
+            String::from_utf8(sign_bytes).unwrap(),
+            concat!(
+                r#"{"account_number":"7","chain_id":"osmosis-1","#,
+                r#""fee":{"amount":[{"amount":"1000","denom":"uosmo"}],"gas":"200000"},"memo":"","#,
+                r#""msgs":[{"type":"wasm/MsgMigrateContract","value":{"code_id":"5","contract":"osmo1contract","#,
+                r#""msg":{"a":"\u003cb\u003e","z":1},"sender":"osmo1sender"}}],"sequence":"3"}"#
+            )
+        );
+    }
+
+    #[test]
+    fn sign_bytes_keep_large_numbers_of_contract_msg() {
+        let body = TxBody {
+            messages: vec![Any {
+                type_url: "/cosmwasm.wasm.v1.MsgMigrateContract".to_string(),
+                value: MsgMigrateContract {
+                    sender: "osmo1sender".to_string(),
+                    contract: "osmo1contract".to_string(),
+                    code_id: 5,
+                    msg: br#"{"supply": 340282366920938463463374607431768211455, "decimals": 6}"#
+                        .to_vec(),
+                }
+                .to_bytes()
+                .unwrap(),
+            }],
+            ..Default::default()
+        };
+        let auth_info = AuthInfo {
+            fee: Some(Default::default()),
+            ..Default::default()
+        };
+
+        let sign_bytes = sign_bytes(
+            "osmosis-1",
+            0,
+            0,
+            &body.to_bytes().unwrap(),
+            &auth_info.to_bytes().unwrap(),
+        )
+        .unwrap();
+
+        assert!(String::from_utf8(sign_bytes)
+            .unwrap()
+            .contains(r#""msg":{"decimals":6,"supply":340282366920938463463374607431768211455}"#));
+    }
+
+    #[test]
+    fn sign_bytes_of_update_instantiate_config() {
+        let body = TxBody {
+            messages: vec![Any {
+                type_url: MSG_UPDATE_INSTANTIATE_CONFIG_TYPE_URL.to_string(),
+                value: MsgUpdateInstantiateConfig {
+                    sender: "osmo1sender".to_string(),
+                    code_id: 5,
+                    new_instantiate_permission: Some(AccessConfig {
+                        permission: AccessType::AnyOfAddresses as i32,
+                        address: "".to_string(),
+                        addresses: vec!["osmo1a".to_string(), "osmo1b".to_string()],
+                    }),
+                }
+                .to_bytes()
+                .unwrap(),
+            }],
+            ..Default::default()
+        };
+        let auth_info = AuthInfo {
+            fee: Some(Default::default()),
+            ..Default::default()
+        };
+
+        let sign_bytes = sign_bytes(
+            "osmosis-1",
+            0,
+            0,
+            &body.to_bytes().unwrap(),
+            &auth_info.to_bytes().unwrap(),
+        )
+        .unwrap();
+
+        assert!(String::from_utf8(sign_bytes).unwrap().contains(concat!(
+            r#"{"type":"wasm/MsgUpdateInstantiateConfig","value":{"code_id":"5","#,
+            r#""new_instantiate_permission":{"addresses":["osmo1a","osmo1b"],"permission":"AnyOfAddresses"},"#,
+            r#""sender":"osmo1sender"}}"#
+        )));
+    }
+
+    #[test]
+    fn sign_bytes_of_exec_wrap_the_granted_msg() {
+        let msg = Any {
+            type_url: "/cosmwasm.wasm.v1.MsgClearAdmin".to_string(),
+            value: MsgClearAdmin {
+                sender: "osmo1granter".to_string(),
+                contract: "osmo1contract".to_string(),
+            }
+            .to_bytes()
+            .unwrap(),
+        };
+        let body = TxBody {
+            messages: vec![Any {
+                type_url: MSG_EXEC_TYPE_URL.to_string(),
+                value: MsgExec {
+                    grantee: "osmo1grantee".to_string(),
+                    msgs: vec![msg],
+                }
+                .to_bytes()
+                .unwrap(),
+            }],
+            ..Default::default()
+        };
+        let auth_info = AuthInfo {
+            fee: Some(Default::default()),
+            ..Default::default()
+        };
+
+        let sign_bytes = sign_bytes(
+            "osmosis-1",
+            0,
+            0,
+            &body.to_bytes().unwrap(),
+            &auth_info.to_bytes().unwrap(),
+        )
+        .unwrap();
+
+        assert!(String::from_utf8(sign_bytes).unwrap().contains(concat!(
+            r#"{"type":"cosmos-sdk/MsgExec","value":{"grantee":"osmo1grantee","#,
+            r#""msgs":[{"type":"wasm/MsgClearAdmin","value":{"contract":"osmo1contract","sender":"osmo1granter"}}]}}"#
+        )));
+    }
+
+    #[test]
+    fn sign_bytes_reject_unsupported_msg() {
+        let body = TxBody {
+            messages: vec![Any {
+                type_url: "/cosmos.bank.v1beta1.MsgSend".to_string(),
+                value: vec![],
+            }],
+            ..Default::default()
+        };
+        let auth_info = AuthInfo {
+            fee: Some(Default::default()),
+            ..Default::default()
+        };
+
+        assert!(sign_bytes(
+            "osmosis-1",
+            0,
+            0,
+            &body.to_bytes().unwrap(),
+            &auth_info.to_bytes().unwrap()
+        )
+        .is_err());
+    }
+}
@@ -0,0 +1,153 @@
+use anyhow::{Context, Result};
+use cosmos_sdk_proto::cosmos::authz::v1beta1::{MsgExec, MsgExecResponse};
+use cosmos_sdk_proto::cosmos::base::v1beta1::Coin;
+use cosmos_sdk_proto::traits::Message;
+use cosmrs::{AccountId, Any};
+
+use super::proto::MessageExt;
+
+pub const MSG_EXEC_TYPE_URL: &str = "/cosmos.authz.v1beta1.MsgExec";
+pub const MSG_EXECUTE_CONTRACT_TYPE_URL: &str = "/cosmwasm.wasm.v1.MsgExecuteContract";
+
+/// Wrap `msg` in a `MsgExec` executed by `grantee` if it is sent on behalf of `granter`,
+/// in which case `granter` must be the sender of `msg`.
+pub fn exec_on_behalf(granter: Option<&AccountId>, grantee: &AccountId, msg: Any) -> Result<Any> {
+    if granter.is_none() {
+        return Ok(msg);
+    }
+
+    let msg_exec = MsgExec {
+        grantee: grantee.to_string(),
+        msgs: vec![msg],
+    };
+
+    Ok(Any {
+        type_url: MSG_EXEC_TYPE_URL.to_owned(),
+        value: msg_exec.to_bytes()?,
+    })
+}
+
+/// Response of the msg executed through `MsgExec`, or the response as is if it is not from one.
+pub fn unwrap_exec_response(msg_response: &Any) -> Result<Vec<u8>> {
+    // legacy msg data is typed by the msg instead of its response
+    if msg_response.type_url.trim_end_matches("Response") != MSG_EXEC_TYPE_URL {
+        return Ok(msg_response.value.clone());
+    }
+
+    MsgExecResponse::decode(&msg_response.value[..])?
+        .results
+        .into_iter()
+        .next()
+        .with_context(|| "Unable to find the result of the msg executed on behalf of the granter")
+}
+
+pub const CONTRACT_EXECUTION_AUTHORIZATION_TYPE_URL: &str =
+    "/cosmwasm.wasm.v1.ContractExecutionAuthorization";
+pub const MAX_CALLS_LIMIT_TYPE_URL: &str = "/cosmwasm.wasm.v1.MaxCallsLimit";
+pub const MAX_FUNDS_LIMIT_TYPE_URL: &str = "/cosmwasm.wasm.v1.MaxFundsLimit";
+pub const COMBINED_LIMIT_TYPE_URL: &str = "/cosmwasm.wasm.v1.CombinedLimit";
+pub const ALLOW_ALL_MESSAGES_FILTER_TYPE_URL: &str = "/cosmwasm.wasm.v1.AllowAllMessagesFilter";
+pub const ACCEPTED_MESSAGE_KEYS_FILTER_TYPE_URL: &str =
+    "/cosmwasm.wasm.v1.AcceptedMessageKeysFilter";
+
+// wasmd's authz messages are not exported by cosmos-sdk-proto, they are defined here.
+
+/// `cosmwasm.wasm.v1.ContractExecutionAuthorization`
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct ContractExecutionAuthorization {
+    #[prost(message, repeated, tag = "1")]
+    pub grants: Vec<ContractGrant>,
+}
+
+/// `cosmwasm.wasm.v1.ContractGrant`, `limit` and `filter` are any of the limits and filters below.
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct ContractGrant {
+    #[prost(string, tag = "1")]
+    pub contract: String,
+    #[prost(message, optional, tag = "2")]
+    pub limit: Option<Any>,
+    #[prost(message, optional, tag = "3")]
+    pub filter: Option<Any>,
+}
+
+/// `cosmwasm.wasm.v1.MaxCallsLimit`
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct MaxCallsLimit {
+    #[prost(uint64, tag = "1")]
+    pub remaining: u64,
+}
+
+/// `cosmwasm.wasm.v1.MaxFundsLimit`
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct MaxFundsLimit {
+    #[prost(message, repeated, tag = "1")]
+    pub amounts: Vec<Coin>,
+}
+
+/// `cosmwasm.wasm.v1.CombinedLimit`
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct CombinedLimit {
+    #[prost(uint64, tag = "1")]
+    pub calls_remaining: u64,
+    #[prost(message, repeated, tag = "2")]
+    pub amounts: Vec<Coin>,
+}
+
+/// `cosmwasm.wasm.v1.AllowAllMessagesFilter`
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct AllowAllMessagesFilter {}
+
+/// `cosmwasm.wasm.v1.AcceptedMessageKeysFilter`
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct AcceptedMessageKeysFilter {
+    #[prost(string, repeated, tag = "1")]
+    pub keys: Vec<String>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn exec_on_behalf_wraps_only_with_granter() {
+        let grantee: AccountId = "osmo1cyyzpxplxdzkeea7kwsydadg87357qnahakaks"
+            .parse()
+            .unwrap();
+        let msg = Any {
+            type_url: MSG_EXECUTE_CONTRACT_TYPE_URL.to_owned(),
+            value: vec![1, 2, 3],
+        };
+
+        assert_eq!(exec_on_behalf(None, &grantee, msg.clone()).unwrap(), msg);
+
+        let wrapped = exec_on_behalf(Some(&grantee), &grantee, msg.clone()).unwrap();
+        assert_eq!(wrapped.type_url, MSG_EXEC_TYPE_URL);
+
+        let msg_exec = MsgExec::decode(&wrapped.value[..]).unwrap();
+        assert_eq!(msg_exec.grantee, grantee.to_string());
+        assert_eq!(msg_exec.msgs, vec![msg]);
+    }
+
+    #[test]
+    fn unwrap_exec_response_result() {
+        let response = MsgExecResponse {
+            results: vec![vec![4, 2]],
+        };
+        let legacy = Any {
+            type_url: MSG_EXEC_TYPE_URL.to_owned(),
+            value: response.to_bytes().unwrap(),
+        };
+        let current = Any {
+            type_url: format!("{MSG_EXEC_TYPE_URL}Response"),
+            ..legacy.clone()
+        };
+        let plain = Any {
+            type_url: MSG_EXECUTE_CONTRACT_TYPE_URL.to_owned(),
+            value: vec![7],
+        };
+
+        assert_eq!(unwrap_exec_response(&legacy).unwrap(), vec![4, 2]);
+        assert_eq!(unwrap_exec_response(&current).unwrap(), vec![4, 2]);
+        assert_eq!(unwrap_exec_response(&plain).unwrap(), vec![7]);
+    }
+}
@@ -0,0 +1,188 @@
+pub mod node;
+mod probestack;
+pub mod simulated;
+mod vm;
+
+use std::path::Path;
+
+use anyhow::Result;
+use cosmos_sdk_proto::cosmos::auth::v1beta1::BaseAccount;
+use cosmos_sdk_proto::cosmos::authz::v1beta1::GrantAuthorization;
+use cosmos_sdk_proto::cosmos::gov::v1beta1::{Proposal, QueryParamsResponse, TallyResult};
+use cosmos_sdk_proto::cosmos::staking::v1beta1::Pool;
+use cosmos_sdk_proto::cosmwasm::wasm::v1::{
+    ContractCodeHistoryEntry, ContractInfo, QueryCodeResponse,
+};
+use cosmrs::abci::GasInfo;
+use cosmrs::{tx, Any};
+
+use crate::framework::config::{GlobalConfig, Network, NetworkVariant};
+
+use self::node::NodeBackend;
+use self::simulated::SimulatedBackend;
+use super::cosmos::TxCommitResponse;
+use super::gov::{self, GovVersion};
+
+/// Outcome of simulating a tx, nothing is committed to the chain.
+#[derive(Clone, Debug)]
+pub struct Simulation {
+    pub gas_info: GasInfo,
+    /// Events emitted by the tx, with decoded attributes
+    pub events: Vec<cosmwasm_std::Event>,
+    /// Responses of the tx's messages, in the order of the messages
+    pub msg_responses: Vec<Any>,
+}
+
+/// Chain that a `Client` talks to, picked from the network variant.
+/// `Node` reaches a running node through grpc and rpc endpoints,
+/// `Simulated` runs everything in-process on top of cw-multi-test.
+#[derive(Clone, Debug)]
+pub enum ChainBackend {
+    Node(NodeBackend),
+    Simulated(Box<SimulatedBackend>),
+}
+
+impl ChainBackend {
+    pub fn new(network: &Network, root: &Path, global_config: &GlobalConfig) -> Self {
+        match network.network_variant() {
+            NetworkVariant::Simulated => ChainBackend::Simulated(Box::new(SimulatedBackend::new(
+                network.chain_id(),
+                root,
+                global_config,
+            ))),
+            NetworkVariant::Local | NetworkVariant::Shared => {
+                ChainBackend::Node(NodeBackend::new(network.clone()))
+            }
+        }
+    }
+
+    pub async fn account(&self, address: &str) -> Result<BaseAccount> {
+        match self {
+            ChainBackend::Node(b) => b.account(address).await,
+            ChainBackend::Simulated(b) => b.account(address),
+        }
+    }
+
+    pub async fn simulate(&self, tx_bytes: Vec<u8>) -> Result<Simulation> {
+        match self {
+            ChainBackend::Node(b) => b.simulate(tx_bytes).await,
+            ChainBackend::Simulated(b) => b.simulate(tx_bytes),
+        }
+    }
+
+    pub async fn broadcast_commit(&self, tx_raw: tx::Raw) -> Result<TxCommitResponse> {
+        match self {
+            ChainBackend::Node(b) => b.broadcast_commit(tx_raw).await,
+            ChainBackend::Simulated(b) => b.broadcast_commit(tx_raw),
+        }
+    }
+
+    pub async fn latest_height(&self) -> Result<u64> {
+        match self {
+            ChainBackend::Node(b) => b.latest_height().await,
+            ChainBackend::Simulated(b) => b.latest_height(),
+        }
+    }
+
+    pub async fn block_time(&self, height: u64) -> Result<tendermint::Time> {
+        match self {
+            ChainBackend::Node(b) => b.block_time(height).await,
+            ChainBackend::Simulated(b) => b.block_time(height),
+        }
+    }
+
+    pub async fn query_smart(&self, address: String, query_data: Vec<u8>) -> Result<Vec<u8>> {
+        match self {
+            ChainBackend::Node(b) => b.query_smart(address, query_data).await,
+            ChainBackend::Simulated(b) => b.query_smart(address, query_data),
+        }
+    }
+
+    pub async fn query_raw(&self, address: String, key: Vec<u8>) -> Result<Option<Vec<u8>>> {
+        match self {
+            ChainBackend::Node(b) => b.query_raw(address, key).await,
+            ChainBackend::Simulated(b) => b.query_raw(address, key),
+        }
+    }
+
+    pub async fn all_contract_state(&self, address: String) -> Result<Vec<(Vec<u8>, Vec<u8>)>> {
+        match self {
+            ChainBackend::Node(b) => b.all_contract_state(address).await,
+            ChainBackend::Simulated(b) => b.all_contract_state(address),
+        }
+    }
+
+    pub async fn contract_info(&self, address: String) -> Result<ContractInfo> {
+        match self {
+            ChainBackend::Node(b) => b.contract_info(address).await,
+            ChainBackend::Simulated(b) => b.contract_info(address),
+        }
+    }
+
+    pub async fn contract_history(&self, address: String) -> Result<Vec<ContractCodeHistoryEntry>> {
+        match self {
+            ChainBackend::Node(b) => b.contract_history(address).await,
+            ChainBackend::Simulated(b) => b.contract_history(address),
+        }
+    }
+
+    pub async fn code(&self, code_id: &u64) -> Result<QueryCodeResponse> {
+        match self {
+            ChainBackend::Node(b) => b.code(code_id).await,
+            ChainBackend::Simulated(b) => b.code(code_id),
+        }
+    }
+
+    pub async fn proposal(&self, proposal_id: &u64) -> Result<Proposal> {
+        match self {
+            ChainBackend::Node(b) => b.proposal(proposal_id).await,
+            ChainBackend::Simulated(b) => b.proposal(proposal_id),
+        }
+    }
+
+    pub async fn tally_result(&self, proposal_id: &u64) -> Result<TallyResult> {
+        match self {
+            ChainBackend::Node(b) => b.tally_result(proposal_id).await,
+            ChainBackend::Simulated(b) => b.tally_result(proposal_id),
+        }
+    }
+
+    pub async fn proposal_v1(&self, proposal_id: &u64) -> Result<gov::Proposal> {
+        match self {
+            ChainBackend::Node(b) => b.proposal_v1(proposal_id).await,
+            ChainBackend::Simulated(b) => b.proposal_v1(proposal_id),
+        }
+    }
+
+    pub async fn gov_version(&self) -> Result<GovVersion> {
+        match self {
+            ChainBackend::Node(b) => b.gov_version().await,
+            ChainBackend::Simulated(b) => b.gov_version(),
+        }
+    }
+
+    pub async fn staking_pool(&self) -> Result<Pool> {
+        match self {
+            ChainBackend::Node(b) => b.staking_pool().await,
+            ChainBackend::Simulated(b) => b.staking_pool(),
+        }
+    }
+
+    pub async fn gov_params(&self, params_type: &str) -> Result<QueryParamsResponse> {
+        match self {
+            ChainBackend::Node(b) => b.gov_params(params_type).await,
+            ChainBackend::Simulated(b) => b.gov_params(params_type),
+        }
+    }
+
+    pub async fn authz_grants(
+        &self,
+        granter: Option<&str>,
+        grantee: Option<&str>,
+    ) -> Result<Vec<GrantAuthorization>> {
+        match self {
+            ChainBackend::Node(b) => b.authz_grants(granter, grantee).await,
+            ChainBackend::Simulated(b) => b.authz_grants(granter, grantee),
+        }
+    }
+}
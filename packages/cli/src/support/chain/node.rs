@@ -0,0 +1,450 @@
+use crate::framework::config::Network;
+use crate::support::cosmos::TxCommitResponse;
+use crate::support::gov::{self, GovVersion};
+use anyhow::{anyhow, bail, Context, Result};
+use cosmos_sdk_proto::cosmos::auth::v1beta1::BaseAccount;
+use cosmos_sdk_proto::cosmos::authz::v1beta1::GrantAuthorization;
+use cosmos_sdk_proto::cosmos::gov::v1beta1::{Proposal, QueryParamsResponse, TallyResult};
+use cosmos_sdk_proto::cosmos::staking::v1beta1::Pool;
+use cosmos_sdk_proto::cosmwasm::wasm::v1::{
+    ContractCodeHistoryEntry, ContractInfo, QueryCodeResponse,
+};
+use cosmos_sdk_proto::traits::Message;
+use cosmrs::rpc::endpoint::abci_query::AbciQuery;
+use cosmrs::{dev, rpc, tx, Any};
+
+use super::Simulation;
+
+/// Backend for a running node, reached through its grpc and rpc endpoints.
+#[derive(Clone, Debug)]
+pub struct NodeBackend {
+    network: Network,
+}
+
+impl NodeBackend {
+    pub fn new(network: Network) -> Self {
+        NodeBackend { network }
+    }
+
+    pub async fn account(&self, address: &str) -> Result<BaseAccount> {
+        use cosmos_sdk_proto::cosmos::auth::v1beta1::*;
+        let grpc_endpoint = self.network.grpc_endpoint();
+
+        let mut c = query_client::QueryClient::connect(self.network.grpc_endpoint().clone())
+            .await
+            .context(format!("Unable to connect to {grpc_endpoint}"))?;
+
+        let res = c
+            .account(QueryAccountRequest {
+                address: address.into(),
+            })
+            .await?
+            .into_inner()
+            .account
+            .context("Account not found")?;
+
+        BaseAccount::decode(res.value.as_slice()).context("Unable to decode BaseAccount")
+    }
+
+    #[allow(deprecated)]
+    pub async fn simulate(&self, tx_bytes: Vec<u8>) -> Result<Simulation> {
+        use cosmos_sdk_proto::cosmos::base::abci::v1beta1::TxMsgData;
+        use cosmos_sdk_proto::cosmos::tx::v1beta1::*;
+        let grpc_endpoint = self.network.grpc_endpoint();
+
+        let mut c = service_client::ServiceClient::connect(self.network.grpc_endpoint().clone())
+            .await
+            .context(format!("Unable to connect to {grpc_endpoint}"))?;
+
+        let res = c
+            .simulate(SimulateRequest { tx: None, tx_bytes })
+            .await?
+            .into_inner();
+
+        let gas_info = res
+            .gas_info
+            .with_context(|| "Unable to extract gas info")?
+            .try_into()
+            .map_err(|e: cosmrs::ErrorReport| anyhow!(e))?;
+
+        let result = res.result.unwrap_or_default();
+
+        // chains before sdk v0.46 only fill the msg data
+        let msg_responses = if result.msg_responses.is_empty() {
+            TxMsgData::decode(result.data.as_slice())?
+                .data
+                .into_iter()
+                .map(|d| Any {
+                    type_url: d.msg_type,
+                    value: d.data,
+                })
+                .collect()
+        } else {
+            result.msg_responses
+        };
+
+        let events = result
+            .events
+            .into_iter()
+            .map(|e| {
+                cosmwasm_std::Event::new(e.r#type).add_attributes(
+                    e.attributes
+                        .into_iter()
+                        .map(|a| (lossy_string(a.key), lossy_string(a.value))),
+                )
+            })
+            .collect();
+
+        Ok(Simulation {
+            gas_info,
+            events,
+            msg_responses,
+        })
+    }
+
+    pub async fn broadcast_commit(&self, tx_raw: tx::Raw) -> Result<TxCommitResponse> {
+        let rpc_client = rpc::HttpClient::new(self.network.rpc_endpoint().as_str()).unwrap();
+        dev::poll_for_first_block(&rpc_client).await;
+
+        let tx_commit_response = tx_raw.broadcast_commit(&rpc_client).await.unwrap();
+
+        // dev::poll_for_tx(&rpc_client, tx_commit_response.hash).await;
+
+        Ok(tx_commit_response)
+    }
+
+    pub async fn latest_height(&self) -> Result<u64> {
+        use cosmrs::rpc::Client as _;
+        let rpc_endpoint = self.network.rpc_endpoint();
+
+        let rpc_client = rpc::HttpClient::new(rpc_endpoint.as_str())
+            .with_context(|| format!("Unable to connect to {rpc_endpoint}"))?;
+        let res = rpc_client.latest_block().await?;
+
+        Ok(res.block.header.height.value())
+    }
+
+    pub async fn block_time(&self, height: u64) -> Result<tendermint::Time> {
+        use cosmrs::rpc::Client as _;
+        let rpc_endpoint = self.network.rpc_endpoint();
+
+        let rpc_client = rpc::HttpClient::new(rpc_endpoint.as_str())
+            .with_context(|| format!("Unable to connect to {rpc_endpoint}"))?;
+        let res = rpc_client.block(u32::try_from(height)?).await?;
+
+        Ok(res.block.header.time)
+    }
+
+    /// Query the grpc method at `path` through rpc, the response code is left to the caller.
+    async fn abci_query(&self, path: &str, data: Vec<u8>) -> Result<AbciQuery> {
+        use cosmrs::rpc::Client as _;
+        let rpc_endpoint = self.network.rpc_endpoint();
+
+        let rpc_client = rpc::HttpClient::new(rpc_endpoint.as_str())
+            .with_context(|| format!("Unable to connect to {rpc_endpoint}"))?;
+        let res = rpc_client
+            .abci_query(Some(path.to_string()), data, None, false)
+            .await?;
+
+        Ok(res)
+    }
+
+    pub async fn query_smart(&self, address: String, query_data: Vec<u8>) -> Result<Vec<u8>> {
+        use cosmos_sdk_proto::cosmwasm::wasm::v1::*;
+        let grpc_endpoint = self.network.grpc_endpoint();
+
+        let mut c = query_client::QueryClient::connect(self.network.grpc_endpoint().clone())
+            .await
+            .context(format!("Unable to connect to {grpc_endpoint}"))?;
+
+        let res = c
+            .smart_contract_state(QuerySmartContractStateRequest {
+                address,
+                query_data,
+            })
+            .await?
+            .into_inner()
+            .data;
+
+        Ok(res)
+    }
+
+    pub async fn query_raw(&self, address: String, key: Vec<u8>) -> Result<Option<Vec<u8>>> {
+        use cosmos_sdk_proto::cosmwasm::wasm::v1::*;
+        let grpc_endpoint = self.network.grpc_endpoint();
+
+        let mut c = query_client::QueryClient::connect(self.network.grpc_endpoint().clone())
+            .await
+            .context(format!("Unable to connect to {grpc_endpoint}"))?;
+
+        let res = c
+            .raw_contract_state(QueryRawContractStateRequest {
+                address,
+                query_data: key,
+            })
+            .await?
+            .into_inner()
+            .data;
+
+        Ok(Some(res).filter(|data| !data.is_empty()))
+    }
+
+    pub async fn all_contract_state(&self, address: String) -> Result<Vec<(Vec<u8>, Vec<u8>)>> {
+        use cosmos_sdk_proto::cosmos::base::query::v1beta1::PageRequest;
+        use cosmos_sdk_proto::cosmwasm::wasm::v1::*;
+        let grpc_endpoint = self.network.grpc_endpoint();
+
+        let mut c = query_client::QueryClient::connect(self.network.grpc_endpoint().clone())
+            .await
+            .context(format!("Unable to connect to {grpc_endpoint}"))?;
+
+        let mut models = vec![];
+        let mut next_key = vec![];
+        loop {
+            let res = c
+                .all_contract_state(QueryAllContractStateRequest {
+                    address: address.clone(),
+                    pagination: Some(PageRequest {
+                        key: next_key,
+                        ..Default::default()
+                    }),
+                })
+                .await?
+                .into_inner();
+
+            models.extend(res.models.into_iter().map(|m| (m.key, m.value)));
+
+            match res.pagination {
+                Some(page) if !page.next_key.is_empty() => next_key = page.next_key,
+                _ => break,
+            }
+        }
+
+        Ok(models)
+    }
+
+    pub async fn contract_info(&self, address: String) -> Result<ContractInfo> {
+        use cosmos_sdk_proto::cosmwasm::wasm::v1::*;
+        let grpc_endpoint = self.network.grpc_endpoint();
+
+        let mut c = query_client::QueryClient::connect(self.network.grpc_endpoint().clone())
+            .await
+            .context(format!("Unable to connect to {grpc_endpoint}"))?;
+
+        let res = c
+            .contract_info(QueryContractInfoRequest {
+                address: address.clone(),
+            })
+            .await?
+            .into_inner()
+            .contract_info;
+
+        res.with_context(|| format!("Contract `{address}` not found"))
+    }
+
+    pub async fn contract_history(&self, address: String) -> Result<Vec<ContractCodeHistoryEntry>> {
+        use cosmos_sdk_proto::cosmos::base::query::v1beta1::PageRequest;
+        use cosmos_sdk_proto::cosmwasm::wasm::v1::*;
+        let grpc_endpoint = self.network.grpc_endpoint();
+
+        let mut c = query_client::QueryClient::connect(self.network.grpc_endpoint().clone())
+            .await
+            .context(format!("Unable to connect to {grpc_endpoint}"))?;
+
+        let mut entries = vec![];
+        let mut next_key = vec![];
+        loop {
+            let res = c
+                .contract_history(QueryContractHistoryRequest {
+                    address: address.clone(),
+                    pagination: Some(PageRequest {
+                        key: next_key,
+                        ..Default::default()
+                    }),
+                })
+                .await?
+                .into_inner();
+
+            entries.extend(res.entries);
+
+            match res.pagination {
+                Some(page) if !page.next_key.is_empty() => next_key = page.next_key,
+                _ => break,
+            }
+        }
+
+        Ok(entries)
+    }
+
+    pub async fn code(&self, code_id: &u64) -> Result<QueryCodeResponse> {
+        use cosmos_sdk_proto::cosmwasm::wasm::v1::*;
+        let grpc_endpoint = self.network.grpc_endpoint();
+
+        let mut c = query_client::QueryClient::connect(self.network.grpc_endpoint().clone())
+            .await
+            .context(format!("Unable to connect to {grpc_endpoint}"))?;
+
+        let res = c
+            .code(QueryCodeRequest { code_id: *code_id })
+            .await?
+            .into_inner();
+
+        Ok(res)
+    }
+
+    pub async fn proposal(&self, proposal_id: &u64) -> Result<Proposal> {
+        use cosmos_sdk_proto::cosmos::gov::v1beta1::*;
+        let grpc_endpoint = self.network.grpc_endpoint();
+
+        let mut c = query_client::QueryClient::connect(self.network.grpc_endpoint().clone())
+            .await
+            .context(format!("Unable to connect to {grpc_endpoint}"))?;
+
+        let res = c
+            .proposal(QueryProposalRequest {
+                proposal_id: *proposal_id,
+            })
+            .await?
+            .into_inner()
+            .proposal;
+
+        res.with_context(|| format!("Unable to find proposal with id {proposal_id}"))
+    }
+
+    /// Current tally of a proposal, including the votes of the ongoing voting period.
+    pub async fn tally_result(&self, proposal_id: &u64) -> Result<TallyResult> {
+        use cosmos_sdk_proto::cosmos::gov::v1beta1::*;
+        let grpc_endpoint = self.network.grpc_endpoint();
+
+        let mut c = query_client::QueryClient::connect(self.network.grpc_endpoint().clone())
+            .await
+            .context(format!("Unable to connect to {grpc_endpoint}"))?;
+
+        let res = c
+            .tally_result(QueryTallyResultRequest {
+                proposal_id: *proposal_id,
+            })
+            .await?
+            .into_inner()
+            .tally;
+
+        res.with_context(|| format!("Unable to find tally of proposal with id {proposal_id}"))
+    }
+
+    pub async fn proposal_v1(&self, proposal_id: &u64) -> Result<gov::Proposal> {
+        // queried through abci since the generated response lacks the fields of newer sdks
+        let path = "/cosmos.gov.v1.Query/Proposal";
+        let res = self
+            .abci_query(
+                path,
+                gov::QueryProposalRequest {
+                    proposal_id: *proposal_id,
+                }
+                .encode_to_vec(),
+            )
+            .await?;
+
+        if res.code.is_err() {
+            bail!("Query `{path}` failed: {}", res.log);
+        }
+
+        gov::QueryProposalResponse::decode(res.value.as_slice())?
+            .proposal
+            .with_context(|| format!("Unable to find proposal with id {proposal_id}"))
+    }
+
+    /// Latest gov version supported by the node, `v1` is available since sdk v0.46.
+    pub async fn gov_version(&self) -> Result<GovVersion> {
+        let res = self
+            .abci_query(
+                "/cosmos.gov.v1.Query/Params",
+                gov::QueryParamsRequest {
+                    params_type: "deposit".to_string(),
+                }
+                .encode_to_vec(),
+            )
+            .await?;
+
+        Ok(if res.code.is_ok() {
+            GovVersion::V1
+        } else {
+            GovVersion::V1beta1
+        })
+    }
+
+    pub async fn authz_grants(
+        &self,
+        granter: Option<&str>,
+        grantee: Option<&str>,
+    ) -> Result<Vec<GrantAuthorization>> {
+        use cosmos_sdk_proto::cosmos::authz::v1beta1::*;
+        let grpc_endpoint = self.network.grpc_endpoint();
+
+        let mut c = query_client::QueryClient::connect(self.network.grpc_endpoint().clone())
+            .await
+            .context(format!("Unable to connect to {grpc_endpoint}"))?;
+
+        let mut grants = match (granter, grantee) {
+            (Some(granter), _) => {
+                c.granter_grants(QueryGranterGrantsRequest {
+                    granter: granter.to_string(),
+                    pagination: None,
+                })
+                .await?
+                .into_inner()
+                .grants
+            }
+            (None, Some(grantee)) => {
+                c.grantee_grants(QueryGranteeGrantsRequest {
+                    grantee: grantee.to_string(),
+                    pagination: None,
+                })
+                .await?
+                .into_inner()
+                .grants
+            }
+            (None, None) => bail!("Either granter or grantee is required to query grants"),
+        };
+
+        if let (Some(_), Some(grantee)) = (granter, grantee) {
+            grants.retain(|g| g.grantee == grantee);
+        }
+
+        Ok(grants)
+    }
+
+    pub async fn staking_pool(&self) -> Result<Pool> {
+        use cosmos_sdk_proto::cosmos::staking::v1beta1::*;
+        let grpc_endpoint = self.network.grpc_endpoint();
+
+        let mut c = query_client::QueryClient::connect(self.network.grpc_endpoint().clone())
+            .await
+            .context(format!("Unable to connect to {grpc_endpoint}"))?;
+
+        let res = c.pool(QueryPoolRequest {}).await?.into_inner().pool;
+
+        res.with_context(|| "Staking pool is not available")
+    }
+
+    pub async fn gov_params(&self, params_type: &str) -> Result<QueryParamsResponse> {
+        use cosmos_sdk_proto::cosmos::gov::v1beta1::*;
+        let grpc_endpoint = self.network.grpc_endpoint();
+
+        let mut c = query_client::QueryClient::connect(self.network.grpc_endpoint().clone())
+            .await
+            .context(format!("Unable to connect to {grpc_endpoint}"))?;
+
+        let res = c
+            .params(QueryParamsRequest {
+                params_type: params_type.to_string(), // voting, tallying, deposit
+            })
+            .await?
+            .into_inner();
+
+        Ok(res)
+    }
+}
+
+fn lossy_string(bytes: impl AsRef<[u8]>) -> String {
+    String::from_utf8_lossy(bytes.as_ref()).to_string()
+}
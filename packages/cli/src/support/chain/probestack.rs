@@ -0,0 +1,27 @@
+//! `__rust_probestack` for the wasmer runtime of the vm.
+//!
+//! wasmer 4.2 points the stack probes of compiled contracts at `__rust_probestack`, which recent
+//! Rust toolchains no longer ship in `compiler_builtins` as they probe the stack inline. The symbol
+//! is defined weak, so the one of a toolchain that still ships it takes precedence.
+//!
+//! Like the original, it touches every page between the stack pointer and `rsp - rax` so that a
+//! large frame can not skip over the guard page, and leaves every register but `r11` untouched.
+
+#[cfg(all(target_arch = "x86_64", target_os = "linux"))]
+std::arch::global_asm!(
+    ".text",
+    ".weak __rust_probestack",
+    ".type __rust_probestack, @function",
+    "__rust_probestack:",
+    include_str!("probestack.s"),
+    ".size __rust_probestack, . - __rust_probestack",
+);
+
+#[cfg(all(target_arch = "x86_64", target_os = "macos"))]
+std::arch::global_asm!(
+    ".text",
+    ".globl ___rust_probestack",
+    ".weak_definition ___rust_probestack",
+    "___rust_probestack:",
+    include_str!("probestack.s"),
+);
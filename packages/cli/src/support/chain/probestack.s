@@ -0,0 +1,17 @@
+    push rbp
+    mov rbp, rsp
+    mov r11, rax
+    cmp r11, 0x1000
+    jna 3f
+2:
+    sub rsp, 0x1000
+    test qword ptr [rsp + 8], rsp
+    sub r11, 0x1000
+    cmp r11, 0x1000
+    ja 2b
+3:
+    sub rsp, r11
+    test qword ptr [rsp + 8], rsp
+    add rsp, rax
+    leave
+    ret
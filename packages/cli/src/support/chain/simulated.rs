@@ -0,0 +1,1069 @@
+use std::cell::RefCell;
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::rc::Rc;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use anyhow::{anyhow, bail, ensure, Context, Result};
+use cosmos_sdk_proto::cosmos::auth::v1beta1::BaseAccount;
+use cosmos_sdk_proto::cosmos::authz::v1beta1::{GrantAuthorization, MsgExec, MsgExecResponse};
+use cosmos_sdk_proto::cosmos::base::abci::v1beta1::{MsgData, TxMsgData};
+use cosmos_sdk_proto::cosmos::gov::v1beta1::{Proposal, QueryParamsResponse, TallyResult};
+use cosmos_sdk_proto::cosmos::staking::v1beta1::Pool;
+use cosmos_sdk_proto::cosmwasm::wasm::v1::{
+    AbsoluteTxPosition, AccessConfig, AccessType, CodeInfoResponse, ContractCodeHistoryEntry,
+    ContractCodeHistoryOperationType, ContractInfo, MsgClearAdmin, MsgInstantiateContract2,
+    MsgInstantiateContractResponse, MsgStoreCodeResponse, MsgUpdateAdmin, QueryCodeResponse,
+};
+use cosmos_sdk_proto::traits::Message;
+use cosmrs::abci::GasInfo;
+use cosmrs::cosmwasm::{
+    MsgExecuteContract, MsgInstantiateContract, MsgMigrateContract, MsgStoreCode,
+};
+use cosmrs::crypto::secp256k1::SigningKey;
+use cosmrs::tx::{self, Msg, SignerPublicKey};
+use cosmrs::{Any, Tx};
+use cosmwasm_std::{
+    to_json_vec, Addr, Binary, BlockInfo, ContractResult, CosmosMsg, Empty, HexBinary, Order,
+    QueryRequest, Storage, SystemResult, Timestamp, WasmMsg, WasmQuery,
+};
+use cw_multi_test::addons::MockAddressGenerator;
+use cw_multi_test::{
+    next_block, App, AppBuilder, BankKeeper, ChecksumGenerator, Executor, WasmKeeper,
+};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use tendermint::abci::{response::DeliverTx, Event, EventAttribute};
+
+use super::vm::{VmApi, WasmContract};
+use super::Simulation;
+use crate::framework::config::GlobalConfig;
+use crate::support::cosmos::TxCommitResponse;
+use crate::support::gas::GasPrice;
+use crate::support::gov::{self, GovVersion};
+use crate::support::multisig::Multisig;
+use crate::support::proto::MessageExt;
+use crate::support::signer::SigningKeyExt;
+use crate::support::state::STATE_DIR;
+use crate::support::wasm::{MsgUpdateInstantiateConfig, MSG_UPDATE_INSTANTIATE_CONFIG_TYPE_URL};
+
+const SIMULATED_DIR: &str = "simulated";
+const CHAIN_FILE: &str = "chain.json";
+const CODE_DIR: &str = "code";
+/// Balance given to each configured account, in the denom of the gas price, when the chain starts.
+const GENESIS_BALANCE: u128 = 100_000_000_000_000;
+
+type SimulatedApp = App<BankKeeper, VmApi>;
+
+/// Backend for an in-process chain built on cw-multi-test.
+///
+/// Chain state is persisted under `.beaker/simulated/<chain_id>` so that it survives
+/// between beaker invocations. The configured accounts are funded at genesis and account
+/// sequences are tracked, but signatures, sequences, fees and authz grants are not checked.
+#[derive(Clone, Debug)]
+pub struct SimulatedBackend {
+    chain_id: String,
+    dir: PathBuf,
+    global_config: GlobalConfig,
+}
+
+#[derive(Serialize, Deserialize, Default)]
+struct Snapshot {
+    height: u64,
+    time: u64,
+    codes: Vec<StoredCode>,
+    storage: Vec<(Binary, Binary)>,
+    /// Code history of each contract, as cw-multi-test does not keep it.
+    #[serde(default)]
+    history: BTreeMap<String, Vec<HistoryEntry>>,
+    /// Sequence of each account that has signed a committed tx.
+    #[serde(default)]
+    sequences: BTreeMap<String, u64>,
+    /// Checksums of the stored codes, shared with the app's checksum generator.
+    #[serde(skip)]
+    checksums: Rc<RefCell<Vec<HexBinary>>>,
+}
+
+impl Snapshot {
+    fn code(&mut self, code_id: u64) -> Result<&mut StoredCode> {
+        usize::try_from(code_id)
+            .ok()
+            .and_then(|i| self.codes.get_mut(i.checked_sub(1)?))
+            .with_context(|| format!("Code {code_id} not found"))
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+struct StoredCode {
+    creator: String,
+    checksum: String,
+    /// Permission set on store or by `MsgUpdateInstantiateConfig`, everybody if unset.
+    #[serde(default)]
+    instantiate_permission: Option<Permission>,
+}
+
+impl StoredCode {
+    fn code_info(&self, code_id: u64) -> Result<CodeInfoResponse> {
+        let instantiate_permission = match &self.instantiate_permission {
+            Some(p) => AccessConfig {
+                permission: p.permission,
+                address: String::new(),
+                addresses: p.addresses.clone(),
+            },
+            None => AccessConfig {
+                permission: AccessType::Everybody as i32,
+                ..Default::default()
+            },
+        };
+
+        Ok(CodeInfoResponse {
+            code_id,
+            creator: self.creator.clone(),
+            data_hash: HexBinary::from_hex(&self.checksum)?.to_vec(),
+            instantiate_permission: Some(instantiate_permission),
+        })
+    }
+
+    /// Instantiate permissions are not enforced by cw-multi-test, so they are checked here.
+    fn ensure_instantiable_by(&self, code_id: u64, sender: &str) -> Result<()> {
+        let Some(p) = &self.instantiate_permission else {
+            return Ok(());
+        };
+
+        let permitted = match AccessType::from_i32(p.permission) {
+            Some(AccessType::Everybody) => true,
+            Some(AccessType::OnlyAddress | AccessType::AnyOfAddresses) => {
+                p.addresses.iter().any(|a| a == sender)
+            }
+            _ => false,
+        };
+        ensure!(
+            permitted,
+            "`{sender}` is not permitted to instantiate code {code_id}"
+        );
+        Ok(())
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+struct Permission {
+    permission: i32,
+    addresses: Vec<String>,
+}
+
+impl From<AccessConfig> for Permission {
+    fn from(config: AccessConfig) -> Self {
+        let mut addresses = config.addresses;
+        // `address` is only set by chains predating `addresses`
+        if !config.address.is_empty() {
+            addresses.push(config.address);
+        }
+        Permission {
+            permission: config.permission,
+            addresses,
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+struct HistoryEntry {
+    operation: i32,
+    code_id: u64,
+    height: u64,
+    msg: Binary,
+}
+
+impl From<&HistoryEntry> for ContractCodeHistoryEntry {
+    fn from(entry: &HistoryEntry) -> Self {
+        ContractCodeHistoryEntry {
+            operation: entry.operation,
+            code_id: entry.code_id,
+            updated: Some(AbsoluteTxPosition {
+                block_height: entry.height,
+                tx_index: 0,
+            }),
+            msg: entry.msg.to_vec(),
+        }
+    }
+}
+
+/// Checksums of the actual stored code, so that instantiate2 addresses are derived
+/// like on chain instead of from cw-multi-test's placeholder checksums.
+struct CodeChecksums(Rc<RefCell<Vec<HexBinary>>>);
+
+impl ChecksumGenerator for CodeChecksums {
+    fn checksum(&self, _creator: &Addr, code_id: u64) -> HexBinary {
+        let index = usize::try_from(code_id).unwrap_or_default();
+        self.0
+            .borrow()
+            .get(index.wrapping_sub(1))
+            .cloned()
+            .unwrap_or_default()
+    }
+}
+
+impl SimulatedBackend {
+    pub fn new(chain_id: &str, root: &Path, global_config: &GlobalConfig) -> Self {
+        SimulatedBackend {
+            chain_id: chain_id.to_string(),
+            dir: root.join(STATE_DIR).join(SIMULATED_DIR).join(chain_id),
+            global_config: global_config.clone(),
+        }
+    }
+
+    pub fn account(&self, address: &str) -> Result<BaseAccount> {
+        let snapshot = self.snapshot()?;
+        Ok(BaseAccount {
+            address: address.to_string(),
+            pub_key: None,
+            account_number: 0,
+            sequence: snapshot.sequences.get(address).copied().unwrap_or_default(),
+        })
+    }
+
+    pub fn simulate(&self, tx_bytes: Vec<u8>) -> Result<Simulation> {
+        let tx = Tx::from_bytes(&tx_bytes).map_err(|e| anyhow!(e))?;
+
+        // the app is not saved, so the simulated chain is left untouched
+        let (mut app, mut snapshot) = self.load()?;
+        let (events, msg_data) = self.deliver_msgs(&mut app, &mut snapshot, &mut vec![], &tx)?;
+
+        Ok(Simulation {
+            gas_info: GasInfo {
+                gas_wanted: 0,
+                gas_used: 0,
+            },
+            events,
+            msg_responses: msg_data
+                .into_iter()
+                .map(|d| Any {
+                    type_url: d.msg_type,
+                    value: d.data,
+                })
+                .collect(),
+        })
+    }
+
+    pub fn broadcast_commit(&self, tx_raw: tx::Raw) -> Result<TxCommitResponse> {
+        let tx_bytes = tx_raw.to_bytes().map_err(|e| anyhow!(e))?;
+        let tx = Tx::from_bytes(&tx_bytes).map_err(|e| anyhow!(e))?;
+
+        let (mut app, mut snapshot) = self.load()?;
+        let mut new_codes = vec![];
+        let (events, msg_data) = self.deliver_msgs(&mut app, &mut snapshot, &mut new_codes, &tx)?;
+        for signer_info in tx.auth_info.signer_infos.iter() {
+            if let Some(public_key) = &signer_info.public_key {
+                let signer = self.signer_address(public_key)?;
+                *snapshot.sequences.entry(signer).or_default() += 1;
+            }
+        }
+
+        let height = app.block_info().height;
+        app.update_block(next_block);
+        self.save(&app, snapshot, new_codes)?;
+
+        #[allow(deprecated)]
+        let tx_msg_data = TxMsgData {
+            data: msg_data,
+            msg_responses: vec![],
+        };
+
+        Ok(TxCommitResponse {
+            check_tx: Default::default(),
+            deliver_tx: DeliverTx {
+                data: base64::encode(tx_msg_data.to_bytes()?).into_bytes().into(),
+                events: events.into_iter().map(to_abci_event).collect(),
+                ..Default::default()
+            },
+            hash: tendermint::Hash::Sha256(Sha256::digest(&tx_bytes).into()),
+            height: height.try_into()?,
+        })
+    }
+
+    pub fn latest_height(&self) -> Result<u64> {
+        let (app, _) = self.load()?;
+        Ok(app.block_info().height)
+    }
+
+    /// Blocks are only made by `next_block`, which moves the time 5 seconds forward each.
+    pub fn block_time(&self, height: u64) -> Result<tendermint::Time> {
+        let (app, _) = self.load()?;
+        let block = app.block_info();
+        let blocks_since = block
+            .height
+            .checked_sub(height)
+            .with_context(|| format!("Block {height} not found"))?;
+        let time = block.time.minus_seconds(blocks_since * 5);
+
+        Ok(tendermint::Time::from_unix_timestamp(
+            i64::try_from(time.seconds())?,
+            u32::try_from(time.subsec_nanos())?,
+        )?)
+    }
+
+    pub fn query_smart(&self, address: String, query_data: Vec<u8>) -> Result<Vec<u8>> {
+        let (app, _) = self.load()?;
+        let request: QueryRequest<Empty> = QueryRequest::Wasm(WasmQuery::Smart {
+            contract_addr: address,
+            msg: Binary(query_data),
+        });
+
+        match app.wrap().raw_query(&to_json_vec(&request)?) {
+            SystemResult::Ok(ContractResult::Ok(data)) => Ok(data.0),
+            SystemResult::Ok(ContractResult::Err(e)) => bail!("Query failed: {e}"),
+            SystemResult::Err(e) => bail!("Query failed: {e}"),
+        }
+    }
+
+    pub fn query_raw(&self, address: String, key: Vec<u8>) -> Result<Option<Vec<u8>>> {
+        let (app, _) = self.load()?;
+        Ok(app.wrap().query_wasm_raw(address, key)?)
+    }
+
+    pub fn all_contract_state(&self, address: String) -> Result<Vec<(Vec<u8>, Vec<u8>)>> {
+        let (app, _) = self.load()?;
+        Ok(app.dump_wasm_raw(&Addr::unchecked(address)))
+    }
+
+    pub fn contract_info(&self, address: String) -> Result<ContractInfo> {
+        let (app, _) = self.load()?;
+        let contract = app
+            .contract_data(&Addr::unchecked(&address))
+            .with_context(|| format!("Contract `{address}` not found"))?;
+
+        Ok(ContractInfo {
+            code_id: contract.code_id,
+            creator: contract.creator.to_string(),
+            admin: contract.admin.map(|a| a.to_string()).unwrap_or_default(),
+            label: contract.label,
+            created: Some(AbsoluteTxPosition {
+                block_height: contract.created,
+                tx_index: 0,
+            }),
+            ibc_port_id: String::new(),
+            extension: None,
+        })
+    }
+
+    pub fn contract_history(&self, address: String) -> Result<Vec<ContractCodeHistoryEntry>> {
+        let (app, snapshot) = self.load()?;
+        app.contract_data(&Addr::unchecked(&address))
+            .with_context(|| format!("Contract `{address}` not found"))?;
+
+        Ok(snapshot
+            .history
+            .get(&address)
+            .map(|entries| entries.iter().map(Into::into).collect())
+            .unwrap_or_default())
+    }
+
+    pub fn code(&self, code_id: &u64) -> Result<QueryCodeResponse> {
+        let (_, mut snapshot) = self.load()?;
+        let code = snapshot.code(*code_id)?;
+
+        let code_file = self.code_file(*code_id);
+        let data = fs::read(&code_file)
+            .with_context(|| format!("Unable to read from `{}`", code_file.to_string_lossy()))?;
+
+        Ok(QueryCodeResponse {
+            code_info: Some(code.code_info(*code_id)?),
+            data,
+        })
+    }
+
+    pub fn proposal(&self, _proposal_id: &u64) -> Result<Proposal> {
+        bail!(self.unsupported("Governance"))
+    }
+
+    pub fn tally_result(&self, _proposal_id: &u64) -> Result<TallyResult> {
+        bail!(self.unsupported("Governance"))
+    }
+
+    pub fn proposal_v1(&self, _proposal_id: &u64) -> Result<gov::Proposal> {
+        bail!(self.unsupported("Governance"))
+    }
+
+    pub fn gov_version(&self) -> Result<GovVersion> {
+        bail!(self.unsupported("Governance"))
+    }
+
+    pub fn staking_pool(&self) -> Result<Pool> {
+        bail!(self.unsupported("Staking"))
+    }
+
+    pub fn gov_params(&self, _params_type: &str) -> Result<QueryParamsResponse> {
+        bail!(self.unsupported("Governance"))
+    }
+
+    pub fn authz_grants(
+        &self,
+        _granter: Option<&str>,
+        _grantee: Option<&str>,
+    ) -> Result<Vec<GrantAuthorization>> {
+        bail!(self.unsupported("Authz"))
+    }
+
+    fn unsupported(&self, feature: &str) -> String {
+        format!(
+            "{feature} is not supported on simulated network `{}`",
+            self.chain_id
+        )
+    }
+
+    fn deliver_msgs(
+        &self,
+        app: &mut SimulatedApp,
+        snapshot: &mut Snapshot,
+        new_codes: &mut Vec<(u64, Vec<u8>)>,
+        tx: &Tx,
+    ) -> Result<(Vec<cosmwasm_std::Event>, Vec<MsgData>)> {
+        let mut events = vec![];
+        let mut msg_data = vec![];
+
+        for msg in tx.body.messages.iter() {
+            let (msg_events, data) = self.deliver(app, snapshot, new_codes, msg)?;
+            // like the sdk, each message's events start with its `message` event
+            events.push(cosmwasm_std::Event::new("message").add_attribute("action", &msg.type_url));
+            events.extend(msg_events);
+            msg_data.push(MsgData {
+                msg_type: msg.type_url.clone(),
+                data,
+            });
+        }
+
+        Ok((events, msg_data))
+    }
+
+    fn deliver(
+        &self,
+        app: &mut SimulatedApp,
+        snapshot: &mut Snapshot,
+        new_codes: &mut Vec<(u64, Vec<u8>)>,
+        msg: &Any,
+    ) -> Result<(Vec<cosmwasm_std::Event>, Vec<u8>)> {
+        let (sender, wasm_msg) = match msg.type_url.as_str() {
+            "/cosmwasm.wasm.v1.MsgStoreCode" => {
+                let msg = MsgStoreCode::from_any(msg).map_err(|e| anyhow!(e))?;
+                let checksum = Sha256::digest(&msg.wasm_byte_code).to_vec();
+                snapshot
+                    .checksums
+                    .borrow_mut()
+                    .push(HexBinary::from(checksum.clone()));
+                let code_id = app.store_code_with_creator(
+                    Addr::unchecked(msg.sender.to_string()),
+                    Box::new(WasmContract::new(
+                        msg.wasm_byte_code.clone(),
+                        VmApi::new(self.global_config.account_prefix())?,
+                    )),
+                );
+
+                snapshot.codes.push(StoredCode {
+                    creator: msg.sender.to_string(),
+                    checksum: HexBinary::from(checksum.clone()).to_hex(),
+                    instantiate_permission: msg
+                        .instantiate_permission
+                        .map(|p| AccessConfig::from(p).into()),
+                });
+                new_codes.push((code_id, msg.wasm_byte_code));
+
+                let event = cosmwasm_std::Event::new("store_code")
+                    .add_attribute("code_checksum", HexBinary::from(checksum.clone()).to_hex())
+                    .add_attribute("code_id", code_id.to_string());
+                let data = MsgStoreCodeResponse { code_id, checksum }.to_bytes()?;
+
+                return Ok((vec![event], data));
+            }
+            "/cosmwasm.wasm.v1.MsgInstantiateContract" => {
+                let msg = MsgInstantiateContract::from_any(msg).map_err(|e| anyhow!(e))?;
+                snapshot
+                    .code(msg.code_id)?
+                    .ensure_instantiable_by(msg.code_id, msg.sender.as_ref())?;
+                (
+                    msg.sender.to_string(),
+                    WasmMsg::Instantiate {
+                        admin: msg.admin.map(|a| a.to_string()),
+                        code_id: msg.code_id,
+                        msg: Binary(msg.msg),
+                        funds: to_std_coins(msg.funds),
+                        label: msg.label.unwrap_or_default(),
+                    },
+                )
+            }
+            "/cosmwasm.wasm.v1.MsgInstantiateContract2" => {
+                let msg = MsgInstantiateContract2::decode(msg.value.as_slice())?;
+                ensure!(!msg.fix_msg, self.unsupported("`fix_msg`"));
+                snapshot
+                    .code(msg.code_id)?
+                    .ensure_instantiable_by(msg.code_id, &msg.sender)?;
+                (
+                    msg.sender,
+                    WasmMsg::Instantiate2 {
+                        admin: Some(msg.admin).filter(|a| !a.is_empty()),
+                        code_id: msg.code_id,
+                        label: msg.label,
+                        msg: Binary(msg.msg),
+                        funds: msg
+                            .funds
+                            .into_iter()
+                            .map(|c| Ok(cosmwasm_std::Coin::new(c.amount.parse()?, c.denom)))
+                            .collect::<Result<_>>()?,
+                        salt: Binary(msg.salt),
+                    },
+                )
+            }
+            "/cosmwasm.wasm.v1.MsgExecuteContract" => {
+                let msg = MsgExecuteContract::from_any(msg).map_err(|e| anyhow!(e))?;
+                (
+                    msg.sender.to_string(),
+                    WasmMsg::Execute {
+                        contract_addr: msg.contract.to_string(),
+                        msg: Binary(msg.msg),
+                        funds: to_std_coins(msg.funds),
+                    },
+                )
+            }
+            "/cosmwasm.wasm.v1.MsgMigrateContract" => {
+                let msg = MsgMigrateContract::from_any(msg).map_err(|e| anyhow!(e))?;
+                (
+                    msg.sender.to_string(),
+                    WasmMsg::Migrate {
+                        contract_addr: msg.contract.to_string(),
+                        new_code_id: msg.code_id,
+                        msg: Binary(msg.msg),
+                    },
+                )
+            }
+            "/cosmwasm.wasm.v1.MsgUpdateAdmin" => {
+                let msg = MsgUpdateAdmin::decode(msg.value.as_slice())?;
+                (
+                    msg.sender,
+                    WasmMsg::UpdateAdmin {
+                        contract_addr: msg.contract,
+                        admin: msg.new_admin,
+                    },
+                )
+            }
+            "/cosmwasm.wasm.v1.MsgClearAdmin" => {
+                let msg = MsgClearAdmin::decode(msg.value.as_slice())?;
+                (
+                    msg.sender,
+                    WasmMsg::ClearAdmin {
+                        contract_addr: msg.contract,
+                    },
+                )
+            }
+            MSG_UPDATE_INSTANTIATE_CONFIG_TYPE_URL => {
+                let msg = MsgUpdateInstantiateConfig::decode(msg.value.as_slice())?;
+                let permission = msg
+                    .new_instantiate_permission
+                    .context("Instantiate permission is required")?;
+                let code = snapshot.code(msg.code_id)?;
+                ensure!(
+                    code.creator == msg.sender,
+                    "Only the creator of code {} can update its instantiate config",
+                    msg.code_id
+                );
+                code.instantiate_permission = Some(permission.into());
+
+                let event = cosmwasm_std::Event::new("update_code_access_config")
+                    .add_attribute("code_id", msg.code_id.to_string());
+                return Ok((vec![event], vec![]));
+            }
+            // grants are not tracked, the wrapped msgs are delivered as if sent by the granter
+            "/cosmos.authz.v1beta1.MsgExec" => {
+                let msg = MsgExec::decode(msg.value.as_slice())?;
+                let mut events = vec![];
+                let mut results = vec![];
+                for inner in msg.msgs.iter() {
+                    let (inner_events, data) = self.deliver(app, snapshot, new_codes, inner)?;
+                    events.extend(inner_events);
+                    results.push(data);
+                }
+                return Ok((events, MsgExecResponse { results }.to_bytes()?));
+            }
+            type_url => bail!(self.unsupported(&format!("`{type_url}`"))),
+        };
+
+        let response = app.execute(Addr::unchecked(sender), CosmosMsg::Wasm(wasm_msg.clone()))?;
+        let data = response.data.unwrap_or_default().0;
+        record_history(app, snapshot, wasm_msg, &data)?;
+
+        Ok((response.events, data))
+    }
+
+    fn signer_address(&self, public_key: &SignerPublicKey) -> Result<String> {
+        let account_prefix = self.global_config.account_prefix();
+        let account_id = match public_key {
+            SignerPublicKey::Single(pk) => pk.account_id(account_prefix).map_err(|e| anyhow!(e))?,
+            SignerPublicKey::LegacyAminoMultisig(multisig) => Multisig::new(
+                multisig.threshold,
+                multisig
+                    .public_keys
+                    .iter()
+                    .map(|pk| base64::encode(pk.to_bytes()))
+                    .collect(),
+            )?
+            .account_id(account_prefix)?,
+            SignerPublicKey::Any(any) => {
+                bail!(self.unsupported(&format!("Public key `{}`", any.type_url)))
+            }
+        };
+        Ok(account_id.to_string())
+    }
+
+    /// Balances of the configured accounts when the chain starts.
+    fn genesis_balances(&self) -> Result<Vec<(Addr, cosmwasm_std::Coin)>> {
+        let denom = self
+            .global_config
+            .gas_price()
+            .parse::<GasPrice>()?
+            .denom
+            .to_string();
+
+        self.global_config
+            .accounts()
+            .values()
+            .map(|account| {
+                let address =
+                    SigningKey::from_account(account, self.global_config.derivation_path())?
+                        .public_key()
+                        .account_id(self.global_config.account_prefix())
+                        .map_err(|e| anyhow!(e))?;
+                Ok((
+                    Addr::unchecked(address.to_string()),
+                    cosmwasm_std::Coin::new(GENESIS_BALANCE, &denom),
+                ))
+            })
+            .collect()
+    }
+
+    fn chain_file(&self) -> PathBuf {
+        self.dir.join(CHAIN_FILE)
+    }
+
+    fn code_file(&self, code_id: u64) -> PathBuf {
+        self.dir.join(CODE_DIR).join(format!("{code_id}.wasm"))
+    }
+
+    /// Saved chain, or the genesis one if nothing has been committed yet.
+    fn snapshot(&self) -> Result<Snapshot> {
+        let chain_file = self.chain_file();
+        if !chain_file.exists() {
+            return Ok(Snapshot {
+                height: 1,
+                time: SystemTime::now().duration_since(UNIX_EPOCH)?.as_nanos() as u64,
+                ..Default::default()
+            });
+        }
+
+        let content = fs::read_to_string(&chain_file)
+            .with_context(|| format!("Unable to read from `{}`", chain_file.to_string_lossy()))?;
+        serde_json::from_str(&content).with_context(|| {
+            format!(
+                "Unable to deserialize simulated chain `{}`",
+                chain_file.to_string_lossy()
+            )
+        })
+    }
+
+    fn load(&self) -> Result<(SimulatedApp, Snapshot)> {
+        let is_genesis = !self.chain_file().exists();
+        let snapshot = self.snapshot()?;
+
+        snapshot.checksums.replace(
+            snapshot
+                .codes
+                .iter()
+                .map(|c| HexBinary::from_hex(&c.checksum))
+                .collect::<Result<_, _>>()?,
+        );
+
+        let api = VmApi::new(self.global_config.account_prefix())?;
+        let mut app = AppBuilder::new()
+            .with_api(api)
+            .with_wasm(
+                WasmKeeper::new()
+                    .with_address_generator(MockAddressGenerator)
+                    .with_checksum_generator(CodeChecksums(snapshot.checksums.clone())),
+            )
+            .with_block(BlockInfo {
+                height: snapshot.height,
+                time: Timestamp::from_nanos(snapshot.time),
+                chain_id: self.chain_id.clone(),
+            })
+            .build(|_, _, _| {});
+
+        if is_genesis {
+            for (address, balance) in self.genesis_balances()? {
+                app.init_modules(|router, _, storage| {
+                    router.bank.init_balance(storage, &address, vec![balance])
+                })?;
+            }
+        }
+
+        for (i, code) in snapshot.codes.iter().enumerate() {
+            let code_file = self.code_file(i as u64 + 1);
+            let wasm = fs::read(&code_file).with_context(|| {
+                format!("Unable to read from `{}`", code_file.to_string_lossy())
+            })?;
+            app.store_code_with_creator(
+                Addr::unchecked(&code.creator),
+                Box::new(WasmContract::new(wasm, api)),
+            );
+        }
+
+        for (k, v) in snapshot.storage.iter() {
+            app.storage_mut().set(k.as_slice(), v.as_slice());
+        }
+
+        Ok((app, snapshot))
+    }
+
+    fn save(
+        &self,
+        app: &SimulatedApp,
+        snapshot: Snapshot,
+        new_codes: Vec<(u64, Vec<u8>)>,
+    ) -> Result<()> {
+        fs::create_dir_all(self.dir.join(CODE_DIR))?;
+        for (code_id, wasm) in new_codes {
+            let code_file = self.code_file(code_id);
+            fs::write(&code_file, wasm)
+                .with_context(|| format!("Unable to write to `{}`", code_file.to_string_lossy()))?;
+        }
+
+        let block = app.block_info();
+        let snapshot = Snapshot {
+            height: block.height,
+            time: block.time.nanos(),
+            storage: app
+                .storage()
+                .range(None, None, Order::Ascending)
+                .map(|(k, v)| (Binary(k), Binary(v)))
+                .collect(),
+            ..snapshot
+        };
+
+        // write next to the file then rename over it, like the state files
+        let chain_file = self.chain_file();
+        let chain_file_str = chain_file.to_string_lossy();
+        let tmp_file = self
+            .dir
+            .join(format!("{CHAIN_FILE}.{}.tmp", std::process::id()));
+        fs::write(&tmp_file, serde_json::to_string(&snapshot)?)
+            .with_context(|| format!("Unable to write to `{chain_file_str}`"))?;
+        fs::rename(&tmp_file, &chain_file)
+            .with_context(|| format!("Unable to write to `{chain_file_str}`"))
+    }
+}
+
+/// Record the code history entry of instantiations and migrations delivered with `data` as response.
+fn record_history(
+    app: &SimulatedApp,
+    snapshot: &mut Snapshot,
+    wasm_msg: WasmMsg,
+    data: &[u8],
+) -> Result<()> {
+    let (contract, operation, code_id, msg) = match wasm_msg {
+        WasmMsg::Instantiate { code_id, msg, .. } | WasmMsg::Instantiate2 { code_id, msg, .. } => (
+            MsgInstantiateContractResponse::decode(data)?.address,
+            ContractCodeHistoryOperationType::Init,
+            code_id,
+            msg,
+        ),
+        WasmMsg::Migrate {
+            contract_addr,
+            new_code_id,
+            msg,
+        } => (
+            contract_addr,
+            ContractCodeHistoryOperationType::Migrate,
+            new_code_id,
+            msg,
+        ),
+        _ => return Ok(()),
+    };
+
+    snapshot
+        .history
+        .entry(contract)
+        .or_default()
+        .push(HistoryEntry {
+            operation: operation as i32,
+            code_id,
+            height: app.block_info().height,
+            msg,
+        });
+    Ok(())
+}
+
+fn to_std_coins(coins: Vec<cosmrs::Coin>) -> Vec<cosmwasm_std::Coin> {
+    coins
+        .into_iter()
+        .map(|c| cosmwasm_std::Coin::new(c.amount, c.denom.to_string()))
+        .collect()
+}
+
+fn to_abci_event(event: cosmwasm_std::Event) -> Event {
+    Event {
+        kind: event.ty,
+        attributes: event
+            .attributes
+            .into_iter()
+            .map(|a| EventAttribute {
+                key: base64::encode(a.key),
+                value: base64::encode(a.value),
+                index: true,
+            })
+            .collect(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use cosmrs::crypto::secp256k1::SigningKey;
+    use cosmrs::tx::{Fee, SignDoc, SignerInfo};
+
+    use super::*;
+    use crate::support::cosmos::{attribute_value, msg_events};
+
+    /// `cyberpunk` test contract of cosmwasm-vm 1.5.
+    const CYBERPUNK: &[u8] = include_bytes!("testdata/cyberpunk.wasm");
+    const CHAIN_ID: &str = "beaker-simulated";
+
+    fn commit(backend: &SimulatedBackend, key: &SigningKey, msg: Any) -> Result<TxCommitResponse> {
+        let body = tx::Body::new(vec![msg], "", 0u32);
+        let auth_info = SignerInfo::single_direct(Some(key.public_key()), 0).auth_info(
+            Fee::from_amount_and_gas(cosmrs::Coin::new(1, "uosmo").unwrap(), 200_000u64),
+        );
+        let sign_doc = SignDoc::new(&body, &auth_info, &CHAIN_ID.parse().unwrap(), 0).unwrap();
+
+        backend.broadcast_commit(sign_doc.sign(key).unwrap())
+    }
+
+    #[test]
+    fn store_instantiate_execute_roundtrip() {
+        let temp = assert_fs::TempDir::new().unwrap();
+        let backend = SimulatedBackend::new(CHAIN_ID, temp.path(), &GlobalConfig::default());
+        let key = SigningKey::from_slice(&[1; 32]).unwrap();
+        let sender = key.public_key().account_id("osmo").unwrap();
+
+        commit(
+            &backend,
+            &key,
+            MsgStoreCode {
+                sender: sender.clone(),
+                wasm_byte_code: CYBERPUNK.to_vec(),
+                instantiate_permission: None,
+            }
+            .to_any()
+            .unwrap(),
+        )
+        .unwrap();
+        assert_eq!(
+            backend.code(&1).unwrap().code_info.unwrap().data_hash,
+            Sha256::digest(CYBERPUNK).to_vec()
+        );
+
+        let response = commit(
+            &backend,
+            &key,
+            MsgInstantiateContract {
+                sender: sender.clone(),
+                admin: Some(sender.clone()),
+                code_id: 1,
+                label: Some("cyberpunk".to_string()),
+                msg: b"{}".to_vec(),
+                funds: vec![],
+            }
+            .to_any()
+            .unwrap(),
+        )
+        .unwrap();
+        let contract = msg_events(&response)[0]
+            .iter()
+            .find(|e| e.kind == "instantiate")
+            .and_then(|e| attribute_value(e, "_contract_address"))
+            .unwrap();
+
+        let response = commit(
+            &backend,
+            &key,
+            MsgExecuteContract {
+                sender: sender.clone(),
+                contract: contract.parse().unwrap(),
+                msg: br#"{"mirror_env":{}}"#.to_vec(),
+                funds: vec![],
+            }
+            .to_any()
+            .unwrap(),
+        )
+        .unwrap();
+        assert!(msg_events(&response)[0]
+            .iter()
+            .any(|e| attribute_value(e, "_contract_address").as_ref() == Some(&contract)));
+
+        let latest_height = backend.latest_height().unwrap();
+        assert_eq!(latest_height, response.height.value() + 1);
+        assert_eq!(
+            backend
+                .block_time(latest_height)
+                .unwrap()
+                .duration_since(backend.block_time(response.height.value()).unwrap())
+                .unwrap()
+                .as_secs(),
+            5
+        );
+        assert!(backend.block_time(latest_height + 1).is_err());
+
+        let info = backend.contract_info(contract.clone()).unwrap();
+        assert_eq!(info.code_id, 1);
+        assert_eq!(info.admin, sender.to_string());
+
+        let history = backend.contract_history(contract).unwrap();
+        assert_eq!(history.len(), 1);
+        assert_eq!(
+            history[0].operation,
+            ContractCodeHistoryOperationType::Init as i32
+        );
+        assert_eq!(history[0].msg, b"{}".to_vec());
+    }
+
+    #[test]
+    fn instantiate_respects_instantiate_config() {
+        let temp = assert_fs::TempDir::new().unwrap();
+        let backend = SimulatedBackend::new(CHAIN_ID, temp.path(), &GlobalConfig::default());
+        let key = SigningKey::from_slice(&[1; 32]).unwrap();
+        let sender = key.public_key().account_id("osmo").unwrap();
+        let instantiate = MsgInstantiateContract {
+            sender: sender.clone(),
+            admin: None,
+            code_id: 1,
+            label: Some("cyberpunk".to_string()),
+            msg: b"{}".to_vec(),
+            funds: vec![],
+        }
+        .to_any()
+        .unwrap();
+
+        commit(
+            &backend,
+            &key,
+            MsgStoreCode {
+                sender: sender.clone(),
+                wasm_byte_code: CYBERPUNK.to_vec(),
+                instantiate_permission: None,
+            }
+            .to_any()
+            .unwrap(),
+        )
+        .unwrap();
+        commit(
+            &backend,
+            &key,
+            Any {
+                type_url: MSG_UPDATE_INSTANTIATE_CONFIG_TYPE_URL.to_string(),
+                value: MsgUpdateInstantiateConfig {
+                    sender: sender.to_string(),
+                    code_id: 1,
+                    new_instantiate_permission: Some(AccessConfig {
+                        permission: AccessType::Nobody as i32,
+                        ..Default::default()
+                    }),
+                }
+                .to_bytes()
+                .unwrap(),
+            },
+        )
+        .unwrap();
+
+        assert_eq!(
+            backend
+                .code(&1)
+                .unwrap()
+                .code_info
+                .unwrap()
+                .instantiate_permission
+                .as_ref()
+                .unwrap()
+                .permission,
+            AccessType::Nobody as i32
+        );
+        assert!(commit(&backend, &key, instantiate).is_err());
+    }
+
+    #[test]
+    fn configured_accounts_are_funded_and_sequenced() {
+        let temp = assert_fs::TempDir::new().unwrap();
+        let global_config = GlobalConfig::default();
+        let backend = SimulatedBackend::new(CHAIN_ID, temp.path(), &global_config);
+        let key = SigningKey::from_account(
+            &global_config.accounts()["test1"],
+            global_config.derivation_path(),
+        )
+        .unwrap();
+        let sender = key.public_key().account_id("osmo").unwrap();
+        let funds = cosmrs::Coin::new(1_000, "uosmo").unwrap();
+
+        commit(
+            &backend,
+            &key,
+            MsgStoreCode {
+                sender: sender.clone(),
+                wasm_byte_code: CYBERPUNK.to_vec(),
+                instantiate_permission: None,
+            }
+            .to_any()
+            .unwrap(),
+        )
+        .unwrap();
+        let response = commit(
+            &backend,
+            &key,
+            MsgInstantiateContract {
+                sender: sender.clone(),
+                admin: None,
+                code_id: 1,
+                label: Some("cyberpunk".to_string()),
+                msg: b"{}".to_vec(),
+                funds: vec![funds.clone()],
+            }
+            .to_any()
+            .unwrap(),
+        )
+        .unwrap();
+        let contract = msg_events(&response)[0]
+            .iter()
+            .find(|e| e.kind == "instantiate")
+            .and_then(|e| attribute_value(e, "_contract_address"))
+            .unwrap();
+
+        let (app, _) = backend.load().unwrap();
+        assert_eq!(
+            app.wrap()
+                .query_balance(contract, "uosmo")
+                .unwrap()
+                .amount
+                .u128(),
+            funds.amount
+        );
+        assert_eq!(
+            app.wrap()
+                .query_balance(sender.to_string(), "uosmo")
+                .unwrap()
+                .amount
+                .u128(),
+            GENESIS_BALANCE - funds.amount
+        );
+        assert_eq!(backend.account(sender.as_ref()).unwrap().sequence, 2);
+        assert!(!temp
+            .path()
+            .join(STATE_DIR)
+            .join(SIMULATED_DIR)
+            .join(CHAIN_ID)
+            .read_dir()
+            .unwrap()
+            .any(|e| e.unwrap().path().extension() == Some("tmp".as_ref())));
+    }
+}
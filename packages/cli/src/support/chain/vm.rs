@@ -0,0 +1,287 @@
+use anyhow::{anyhow, ensure, Result};
+use cosmrs::AccountId;
+use cosmwasm_std::testing::MockApi;
+use cosmwasm_std::{
+    Addr, Api, Binary, CanonicalAddr, ContractResult, Deps, DepsMut, Empty, Env, MessageInfo,
+    Order, Record, RecoverPubkeyError, Reply, Response, StdError, StdResult, Storage,
+    VerificationError,
+};
+use cosmwasm_vm::testing::{MockQuerier, MockStorage};
+use cosmwasm_vm::{
+    call_execute, call_instantiate, call_migrate, call_query, call_reply, call_sudo, Backend,
+    BackendApi, BackendError, BackendResult, GasInfo, Instance, InstanceOptions,
+    Storage as VmStorage, VmResult,
+};
+use cw_multi_test::Contract;
+
+const GAS_LIMIT: u64 = 500_000_000_000_000;
+const MEMORY_LIMIT_MIB: usize = 32;
+/// Longest human readable part allowed by bech32.
+const MAX_PREFIX_LEN: usize = 83;
+
+type VmInstance = Instance<VmApi, MockStorage, MockQuerier>;
+
+/// Address handling for the vm and cw-multi-test, using bech32 with the configured account prefix.
+///
+/// The prefix is kept inline as the vm requires its api to be `Copy`.
+#[derive(Clone, Copy)]
+pub struct VmApi {
+    prefix: [u8; MAX_PREFIX_LEN],
+    prefix_len: usize,
+}
+
+impl VmApi {
+    pub fn new(prefix: &str) -> Result<Self> {
+        ensure!(
+            !prefix.is_empty() && prefix.len() <= MAX_PREFIX_LEN && prefix.is_ascii(),
+            "Invalid account prefix `{prefix}`"
+        );
+
+        let mut inline = [0; MAX_PREFIX_LEN];
+        inline[..prefix.len()].copy_from_slice(prefix.as_bytes());
+        Ok(VmApi {
+            prefix: inline,
+            prefix_len: prefix.len(),
+        })
+    }
+
+    fn prefix(&self) -> &str {
+        std::str::from_utf8(&self.prefix[..self.prefix_len]).unwrap_or_default()
+    }
+
+    fn canonicalize(&self, human: &str) -> Result<Vec<u8>, String> {
+        let id = human.parse::<AccountId>().map_err(|e| e.to_string())?;
+        if id.prefix() == self.prefix() {
+            Ok(id.to_bytes())
+        } else {
+            Err(format!(
+                "Invalid address prefix for `{human}`, expected `{}`",
+                self.prefix()
+            ))
+        }
+    }
+
+    fn humanize(&self, canonical: &[u8]) -> Result<String, String> {
+        AccountId::new(self.prefix(), canonical)
+            .map(|id| id.to_string())
+            .map_err(|e| e.to_string())
+    }
+}
+
+impl BackendApi for VmApi {
+    fn canonical_address(&self, human: &str) -> BackendResult<Vec<u8>> {
+        (
+            self.canonicalize(human).map_err(BackendError::user_err),
+            GasInfo::free(),
+        )
+    }
+
+    fn human_address(&self, canonical: &[u8]) -> BackendResult<String> {
+        (
+            self.humanize(canonical).map_err(BackendError::user_err),
+            GasInfo::free(),
+        )
+    }
+}
+
+impl Api for VmApi {
+    fn addr_validate(&self, human: &str) -> StdResult<Addr> {
+        self.addr_humanize(&self.addr_canonicalize(human)?)
+    }
+
+    fn addr_canonicalize(&self, human: &str) -> StdResult<CanonicalAddr> {
+        self.canonicalize(human)
+            .map(CanonicalAddr::from)
+            .map_err(StdError::generic_err)
+    }
+
+    fn addr_humanize(&self, canonical: &CanonicalAddr) -> StdResult<Addr> {
+        self.humanize(canonical.as_slice())
+            .map(Addr::unchecked)
+            .map_err(StdError::generic_err)
+    }
+
+    fn secp256k1_verify(
+        &self,
+        message_hash: &[u8],
+        signature: &[u8],
+        public_key: &[u8],
+    ) -> Result<bool, VerificationError> {
+        MockApi::default().secp256k1_verify(message_hash, signature, public_key)
+    }
+
+    fn secp256k1_recover_pubkey(
+        &self,
+        message_hash: &[u8],
+        signature: &[u8],
+        recovery_param: u8,
+    ) -> Result<Vec<u8>, RecoverPubkeyError> {
+        MockApi::default().secp256k1_recover_pubkey(message_hash, signature, recovery_param)
+    }
+
+    fn ed25519_verify(
+        &self,
+        message: &[u8],
+        signature: &[u8],
+        public_key: &[u8],
+    ) -> Result<bool, VerificationError> {
+        MockApi::default().ed25519_verify(message, signature, public_key)
+    }
+
+    fn ed25519_batch_verify(
+        &self,
+        messages: &[&[u8]],
+        signatures: &[&[u8]],
+        public_keys: &[&[u8]],
+    ) -> Result<bool, VerificationError> {
+        MockApi::default().ed25519_batch_verify(messages, signatures, public_keys)
+    }
+
+    fn debug(&self, message: &str) {
+        MockApi::default().debug(message)
+    }
+}
+
+/// Wasm byte code runnable by cw-multi-test.
+///
+/// Every entry point spins up a fresh vm instance over a copy of the contract's storage
+/// and writes the resulting records back for mutating calls.
+/// Queries from the contract to other contracts or modules are not supported.
+pub struct WasmContract {
+    code: Vec<u8>,
+    api: VmApi,
+}
+
+impl WasmContract {
+    pub fn new(code: Vec<u8>, api: VmApi) -> Self {
+        WasmContract { code, api }
+    }
+
+    fn instance(&self, storage: &dyn Storage) -> Result<VmInstance> {
+        let mut vm_storage = MockStorage::new();
+        for (k, v) in storage.range(None, None, Order::Ascending) {
+            vm_storage.set(&k, &v).0.map_err(|e| anyhow!(e))?;
+        }
+
+        let backend = Backend {
+            api: self.api,
+            storage: vm_storage,
+            querier: MockQuerier::new(&[]),
+        };
+        let options = InstanceOptions {
+            gas_limit: GAS_LIMIT,
+            print_debug: false,
+        };
+
+        Instance::from_code(
+            &self.code,
+            backend,
+            options,
+            Some(cosmwasm_vm::Size::mebi(MEMORY_LIMIT_MIB)),
+        )
+        .map_err(|e| anyhow!(e))
+    }
+
+    fn run_mut(
+        &self,
+        storage: &mut dyn Storage,
+        call: impl FnOnce(&mut VmInstance) -> VmResult<ContractResult<Response>>,
+    ) -> Result<Response> {
+        let mut instance = self.instance(storage)?;
+        let response = call(&mut instance)
+            .map_err(|e| anyhow!(e))?
+            .into_result()
+            .map_err(|e| anyhow!(e))?;
+
+        let mut vm_storage = instance
+            .recycle()
+            .ok_or_else(|| anyhow!("Unable to recover contract storage from vm"))?
+            .storage;
+        let records = all_records(&mut vm_storage)?;
+
+        let stale_keys: Vec<Vec<u8>> = storage
+            .range(None, None, Order::Ascending)
+            .map(|(k, _)| k)
+            .collect();
+        for k in stale_keys {
+            storage.remove(&k);
+        }
+        for (k, v) in records {
+            storage.set(&k, &v);
+        }
+
+        Ok(response)
+    }
+}
+
+fn all_records(storage: &mut MockStorage) -> Result<Vec<Record>> {
+    let iterator_id = storage
+        .scan(None, None, Order::Ascending)
+        .0
+        .map_err(|e| anyhow!(e))?;
+    storage.all(iterator_id).0.map_err(|e| anyhow!(e))
+}
+
+impl Contract<Empty> for WasmContract {
+    fn execute(
+        &self,
+        deps: DepsMut,
+        env: Env,
+        info: MessageInfo,
+        msg: Vec<u8>,
+    ) -> Result<Response> {
+        self.run_mut(deps.storage, |i| call_execute(i, &env, &info, &msg))
+    }
+
+    fn instantiate(
+        &self,
+        deps: DepsMut,
+        env: Env,
+        info: MessageInfo,
+        msg: Vec<u8>,
+    ) -> Result<Response> {
+        self.run_mut(deps.storage, |i| call_instantiate(i, &env, &info, &msg))
+    }
+
+    fn query(&self, deps: Deps, env: Env, msg: Vec<u8>) -> Result<Binary> {
+        let mut instance = self.instance(deps.storage)?;
+        call_query(&mut instance, &env, &msg)
+            .map_err(|e| anyhow!(e))?
+            .into_result()
+            .map_err(|e| anyhow!(e))
+    }
+
+    fn sudo(&self, deps: DepsMut, env: Env, msg: Vec<u8>) -> Result<Response> {
+        self.run_mut(deps.storage, |i| call_sudo(i, &env, &msg))
+    }
+
+    fn reply(&self, deps: DepsMut, env: Env, msg: Reply) -> Result<Response> {
+        self.run_mut(deps.storage, |i| call_reply(i, &env, &msg))
+    }
+
+    fn migrate(&self, deps: DepsMut, env: Env, msg: Vec<u8>) -> Result<Response> {
+        self.run_mut(deps.storage, |i| call_migrate(i, &env, &msg))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_vm_api_address_roundtrip() {
+        let api = VmApi::new("osmo").unwrap();
+        let human = "osmo1cyyzpxplxdzkeea7kwsydadg87357qnahakaks";
+
+        let canonical = api.canonical_address(human).0.unwrap();
+        assert_eq!(api.human_address(&canonical).0.unwrap(), human);
+    }
+
+    #[test]
+    fn test_vm_api_rejects_other_prefix() {
+        let api = VmApi::new("juno").unwrap();
+        let human = "osmo1cyyzpxplxdzkeea7kwsydadg87357qnahakaks";
+
+        assert!(api.canonical_address(human).0.is_err());
+    }
+}
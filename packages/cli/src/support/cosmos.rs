@@ -1,17 +1,31 @@
-use crate::framework::config::Network;
-use anyhow::{anyhow, Ok};
+use std::collections::HashMap;
+use std::path::Path;
+use std::sync::{Mutex, OnceLock};
+
+use crate::framework::config::{GlobalConfig, Network};
+use anyhow::anyhow;
 use anyhow::{Context, Result};
 use cosmos_sdk_proto::cosmos::auth::v1beta1::BaseAccount;
-use cosmos_sdk_proto::cosmos::gov::v1beta1::Proposal;
+use cosmos_sdk_proto::cosmos::authz::v1beta1::GrantAuthorization;
+use cosmos_sdk_proto::cosmos::gov::v1beta1::{Proposal, TallyResult};
+use cosmos_sdk_proto::cosmos::tx::v1beta1::TxRaw;
+use cosmos_sdk_proto::cosmwasm::wasm::v1::{
+    CodeInfoResponse, ContractCodeHistoryEntry, ContractInfo,
+};
 use cosmrs::abci::GasInfo;
 use cosmrs::crypto::secp256k1::SigningKey;
 
-use cosmos_sdk_proto::traits::Message;
 use cosmrs::tx::{self, SignDoc, SignerInfo};
-use cosmrs::{dev, AccountId, Coin};
 use cosmrs::{rpc, tx::Fee, Any};
+use cosmrs::{AccountId, Coin};
+use regex::Regex;
+use tendermint::abci::Event;
 
-use super::gas::Gas;
+use super::chain::{ChainBackend, Simulation};
+use super::gas::{Gas, GasPrice};
+use super::gov::{self, GovVersion};
+use super::proto::MessageExt;
+use super::tx::UnsignedTx;
 
 pub type TxCommitResponse = rpc::endpoint::broadcast::tx_commit::Response;
 
@@ -39,129 +53,185 @@ impl ResponseValuePicker for TxCommitResponse {
     }
 }
 
+/// Decoded value of the event's attribute, attributes are base64 encoded by the node.
+pub fn attribute_value(event: &Event, attribute: &str) -> Option<String> {
+    let value = &event
+        .attributes
+        .iter()
+        .find(|a| a.key == base64::encode(attribute))?
+        .value;
+
+    String::from_utf8(base64::decode(value).ok()?).ok()
+}
+
+/// Events of each message of the tx, in the order of the messages.
+/// Events are grouped by their `msg_index` attribute when the chain sets it,
+/// otherwise each message's events start with the `message` event carrying its `action`.
+pub fn msg_events(response: &TxCommitResponse) -> Vec<Vec<Event>> {
+    group_msg_events(&response.deliver_tx.events)
+}
+
+fn group_msg_events(events: &[Event]) -> Vec<Vec<Event>> {
+    let mut msg_events: Vec<Vec<Event>> = vec![];
+
+    if events
+        .iter()
+        .any(|e| attribute_value(e, "msg_index").is_some())
+    {
+        for event in events {
+            if let Some(index) = attribute_value(event, "msg_index").and_then(|i| i.parse().ok()) {
+                if msg_events.len() <= index {
+                    msg_events.resize(index + 1, vec![]);
+                }
+                msg_events[index].push(event.clone());
+            }
+        }
+    } else {
+        for event in events {
+            if event.kind == "message" && attribute_value(event, "action").is_some() {
+                msg_events.push(vec![]);
+            }
+            if let Some(current) = msg_events.last_mut() {
+                current.push(event.clone());
+            }
+        }
+    }
+
+    msg_events
+}
+
+/// Tx fields besides its messages and fee amount.
+#[derive(Clone, Debug, Default)]
+pub struct TxOptions {
+    pub memo: String,
+    /// Absolute block height after which the tx is no longer valid, 0 for no timeout
+    pub timeout_height: u32,
+    /// Number of blocks after the latest one, overrides `timeout_height` if set
+    pub timeout_blocks: Option<u32>,
+    /// Account whose fee grant to the signer pays the fee
+    pub fee_granter: Option<AccountId>,
+    /// Account paying the fee instead of the signer, it must sign the tx as well
+    pub fee_payer: Option<AccountId>,
+}
+
+impl TxOptions {
+    fn apply_to_fee(&self, fee: Fee) -> Fee {
+        Fee {
+            granter: self.fee_granter.clone(),
+            payer: self.fee_payer.clone(),
+            ..fee
+        }
+    }
+}
+
 #[derive(Clone, Debug)]
 pub struct Client {
     network: Network,
+    account_prefix: String,
+    backend: ChainBackend,
 }
 
 impl Client {
-    pub fn new(network: Network) -> Self {
-        Client { network }
+    pub fn new(network: Network, root: &Path, global_config: &GlobalConfig) -> Self {
+        Client {
+            backend: ChainBackend::new(&network, root, global_config),
+            network,
+            account_prefix: global_config.account_prefix().to_string(),
+        }
     }
 
-    pub fn to_signing_client(
-        &self,
-        signing_key: SigningKey,
-        account_prefix: &str,
-    ) -> SigningClient {
+    pub fn to_signing_client(&self, signing_key: SigningKey) -> SigningClient {
         SigningClient {
             inner: self.clone(),
             signing_key,
-            account_prefix: account_prefix.to_string(),
+            account_prefix: self.account_prefix.clone(),
         }
     }
 
     pub async fn account(&self, address: &str) -> Result<BaseAccount> {
-        use cosmos_sdk_proto::cosmos::auth::v1beta1::*;
-        let grpc_endpoint = self.network.grpc_endpoint();
-
-        let mut c = query_client::QueryClient::connect(self.network.grpc_endpoint().clone())
-            .await
-            .context(format!("Unable to connect to {grpc_endpoint}"))?;
-
-        let res = c
-            .account(QueryAccountRequest {
-                address: address.into(),
-            })
-            .await?
-            .into_inner()
-            .account
-            .context("Account not found")?;
-
-        BaseAccount::decode(res.value.as_slice()).context("Unable to decode BaseAccount")
+        self.backend.account(address).await
     }
 
-    #[allow(deprecated)]
     pub async fn simulate(&self, tx_bytes: Vec<u8>) -> Result<GasInfo> {
-        use cosmos_sdk_proto::cosmos::tx::v1beta1::*;
-        let grpc_endpoint = self.network.grpc_endpoint();
+        Ok(self.backend.simulate(tx_bytes).await?.gas_info)
+    }
 
-        let mut c = service_client::ServiceClient::connect(self.network.grpc_endpoint().clone())
-            .await
-            .context(format!("Unable to connect to {grpc_endpoint}"))?;
+    pub async fn query_smart(&self, address: String, query_data: Vec<u8>) -> Result<Vec<u8>> {
+        self.backend.query_smart(address, query_data).await
+    }
 
-        let res = c
-            .simulate(SimulateRequest { tx: None, tx_bytes })
-            .await?
-            .into_inner()
-            .gas_info;
+    /// Raw value stored under `key` by the contract, `None` if the key is not set.
+    pub async fn query_raw(&self, address: String, key: Vec<u8>) -> Result<Option<Vec<u8>>> {
+        self.backend.query_raw(address, key).await
+    }
 
-        let gas_info = res.with_context(|| "Unable to extract gas info")?;
+    /// All key value pairs stored by the contract, across every page.
+    pub async fn all_contract_state(&self, address: String) -> Result<Vec<(Vec<u8>, Vec<u8>)>> {
+        self.backend.all_contract_state(address).await
+    }
 
-        gas_info
-            .try_into()
-            .map_err(|e: cosmrs::ErrorReport| anyhow!(e))
+    pub async fn contract_info(&self, address: String) -> Result<ContractInfo> {
+        self.backend.contract_info(address).await
     }
 
-    pub async fn query_smart(&self, address: String, query_data: Vec<u8>) -> Result<Vec<u8>> {
-        use cosmos_sdk_proto::cosmwasm::wasm::v1::*;
-        let grpc_endpoint = self.network.grpc_endpoint();
+    /// Code changes of the contract, from its instantiation to its latest migration.
+    pub async fn contract_history(&self, address: String) -> Result<Vec<ContractCodeHistoryEntry>> {
+        self.backend.contract_history(address).await
+    }
 
-        let mut c = query_client::QueryClient::connect(self.network.grpc_endpoint().clone())
-            .await
-            .context(format!("Unable to connect to {grpc_endpoint}"))?;
+    /// Time of the block at `height`.
+    pub async fn block_time(&self, height: u64) -> Result<tendermint::Time> {
+        self.backend.block_time(height).await
+    }
 
-        let res = c
-            .smart_contract_state(QuerySmartContractStateRequest {
-                address,
-                query_data,
-            })
+    pub async fn code_info(&self, code_id: &u64) -> Result<CodeInfoResponse> {
+        self.backend
+            .code(code_id)
             .await?
-            .into_inner()
-            .data;
+            .code_info
+            .with_context(|| format!("Code {code_id} not found"))
+    }
 
-        Ok(res)
+    /// Wasm byte code stored under `code_id`.
+    pub async fn code_wasm(&self, code_id: &u64) -> Result<Vec<u8>> {
+        Ok(self.backend.code(code_id).await?.data)
     }
 
     pub async fn proposal(&self, proposal_id: &u64) -> Result<Proposal> {
-        use cosmos_sdk_proto::cosmos::gov::v1beta1::*;
-        let grpc_endpoint = self.network.grpc_endpoint();
+        self.backend.proposal(proposal_id).await
+    }
 
-        let mut c = query_client::QueryClient::connect(self.network.grpc_endpoint().clone())
-            .await
-            .context(format!("Unable to connect to {grpc_endpoint}"))?;
+    /// Current tally of the proposal, `final_tally_result` is only set once voting ends.
+    pub async fn tally_result(&self, proposal_id: &u64) -> Result<TallyResult> {
+        self.backend.tally_result(proposal_id).await
+    }
 
-        let res = c
-            .proposal(QueryProposalRequest {
-                proposal_id: *proposal_id,
-            })
-            .await?
-            .into_inner()
-            .proposal;
+    /// Proposal queried through gov `v1`, which includes proposals submitted through `v1beta1`.
+    pub async fn proposal_v1(&self, proposal_id: &u64) -> Result<gov::Proposal> {
+        self.backend.proposal_v1(proposal_id).await
+    }
 
-        res.with_context(|| format!("Unable to find proposal with id {proposal_id}"))
+    /// Latest gov version supported by the chain.
+    pub async fn gov_version(&self) -> Result<GovVersion> {
+        self.backend.gov_version().await
+    }
+
+    /// Authz grants given by `granter` and / or to `grantee`
+    pub async fn authz_grants(
+        &self,
+        granter: Option<&str>,
+        grantee: Option<&str>,
+    ) -> Result<Vec<GrantAuthorization>> {
+        self.backend.authz_grants(granter, grantee).await
     }
 
     async fn gov_params(
         &self,
         params_type: &str,
     ) -> Result<cosmos_sdk_proto::cosmos::gov::v1beta1::QueryParamsResponse> {
-        use cosmos_sdk_proto::cosmos::gov::v1beta1::*;
-        let grpc_endpoint = self.network.grpc_endpoint();
-
-        let mut c = query_client::QueryClient::connect(self.network.grpc_endpoint().clone())
-            .await
-            .context(format!("Unable to connect to {grpc_endpoint}"))?;
-
-        let res = c
-            .params(QueryParamsRequest {
-                params_type: params_type.to_string(), // voting, tallying, deposit
-            })
-            .await?
-            .into_inner();
-
-        Ok(res)
+        self.backend.gov_params(params_type).await
     }
+
     pub async fn gov_params_deposit(
         &self,
     ) -> Result<cosmos_sdk_proto::cosmos::gov::v1beta1::DepositParams> {
@@ -170,6 +240,210 @@ impl Client {
             .deposit_params
             .with_context(|| "Deposit params is not available")
     }
+
+    pub async fn gov_params_tallying(
+        &self,
+    ) -> Result<cosmos_sdk_proto::cosmos::gov::v1beta1::TallyParams> {
+        self.gov_params("tallying")
+            .await?
+            .tally_params
+            .with_context(|| "Tally params is not available")
+    }
+
+    /// Tokens bonded to validators, the voting power that counts towards the quorum.
+    pub async fn bonded_tokens(&self) -> Result<u128> {
+        let bonded_tokens = self.backend.staking_pool().await?.bonded_tokens;
+        bonded_tokens
+            .parse()
+            .with_context(|| format!("Invalid bonded tokens: {bonded_tokens}"))
+    }
+
+    /// Build the unsigned tx of `msgs` for `signer`, without access to the signer's key.
+    pub async fn generate_unsigned(
+        &self,
+        signer: &AccountId,
+        msgs: Vec<Any>,
+        gas: &Gas,
+        tx_options: &TxOptions,
+        account_sequence: &Option<u64>,
+    ) -> Result<UnsignedTx> {
+        let mut acc = self
+            .account(signer.as_ref())
+            .await
+            .with_context(|| "Account can't be initialized")?;
+
+        // manually set sequence if provided
+        acc.sequence = account_sequence.unwrap_or(acc.sequence);
+
+        let body_bytes = self
+            .tx_body(msgs, tx_options)
+            .await?
+            .into_bytes()
+            .map_err(|e| anyhow!(e))?;
+
+        let fee = match gas {
+            Gas::Specified(fee) => fee.clone(),
+            Gas::Auto {
+                gas_price,
+                gas_adjustment,
+            } => {
+                let simulation = self
+                    .simulate_unsigned(body_bytes.clone(), acc.sequence, zero_fee(gas_price))
+                    .await?;
+
+                fee_from_gas_used(
+                    simulation.gas_info.gas_used,
+                    gas_price.clone(),
+                    *gas_adjustment,
+                )
+            }
+        };
+
+        let auth_info = tx::AuthInfo {
+            signer_infos: vec![],
+            fee: tx_options.apply_to_fee(fee),
+        };
+
+        Ok(UnsignedTx {
+            chain_id: self.network.chain_id().to_string(),
+            account_number: acc.account_number,
+            sequence: acc.sequence,
+            signer: signer.to_string(),
+            body: base64::encode(body_bytes),
+            auth_info: base64::encode(auth_info.into_bytes().map_err(|e| anyhow!(e))?),
+            multisig: None,
+        })
+    }
+
+    /// Simulate `msgs` sent by `signer` without broadcasting, nor access to the signer's key.
+    /// Returns the simulation along with the fee the tx would be sent with.
+    pub async fn dry_run(
+        &self,
+        signer: &AccountId,
+        msgs: Vec<Any>,
+        gas: &Gas,
+        tx_options: &TxOptions,
+        account_sequence: &Option<u64>,
+    ) -> Result<(Simulation, Fee)> {
+        let acc = self
+            .account(signer.as_ref())
+            .await
+            .with_context(|| "Account can't be initialized")?;
+
+        let body_bytes = self
+            .tx_body(msgs, tx_options)
+            .await?
+            .into_bytes()
+            .map_err(|e| anyhow!(e))?;
+
+        let simulation_fee = match gas {
+            Gas::Specified(fee) => fee.clone(),
+            Gas::Auto { gas_price, .. } => zero_fee(gas_price),
+        };
+        let simulation = self
+            .simulate_unsigned(
+                body_bytes,
+                account_sequence.unwrap_or(acc.sequence),
+                simulation_fee,
+            )
+            .await?;
+
+        let fee = match gas {
+            Gas::Specified(fee) => fee.clone(),
+            Gas::Auto {
+                gas_price,
+                gas_adjustment,
+            } => fee_from_gas_used(
+                simulation.gas_info.gas_used,
+                gas_price.clone(),
+                *gas_adjustment,
+            ),
+        };
+
+        Ok((simulation, tx_options.apply_to_fee(fee)))
+    }
+
+    /// Build the tx body of `msgs`, resolving a relative timeout against the latest block.
+    pub async fn tx_body(&self, msgs: Vec<Any>, tx_options: &TxOptions) -> Result<tx::Body> {
+        let timeout_height = match tx_options.timeout_blocks {
+            Some(blocks) => u32::try_from(self.backend.latest_height().await? + u64::from(blocks))
+                .with_context(|| "Timeout height is out of range")?,
+            None => tx_options.timeout_height,
+        };
+
+        Ok(tx::Body::new(
+            msgs,
+            tx_options.memo.as_str(),
+            timeout_height,
+        ))
+    }
+
+    /// Simulate the tx without public key and with an empty signature,
+    /// the chain uses a placeholder key for the signature verification.
+    async fn simulate_unsigned(
+        &self,
+        body_bytes: Vec<u8>,
+        sequence: u64,
+        fee: Fee,
+    ) -> Result<Simulation> {
+        let auth_info = SignerInfo::single_direct(None, sequence).auth_info(fee);
+        let tx_raw = TxRaw {
+            body_bytes,
+            auth_info_bytes: auth_info.into_bytes().map_err(|e| anyhow!(e))?,
+            signatures: vec![vec![]],
+        };
+
+        self.backend.simulate(tx_raw.to_bytes()?).await
+    }
+
+    pub async fn broadcast(&self, tx_raw: tx::Raw) -> Result<TxCommitResponse> {
+        let tx_commit_response = self.backend.broadcast_commit(tx_raw).await?;
+
+        let check_tx = &tx_commit_response.check_tx;
+        if check_tx.code.is_err() {
+            return Err(TxRejected {
+                stage: "check_tx",
+                code: check_tx.code.value(),
+                codespace: check_tx.codespace.clone(),
+                response: format!("{check_tx:?}"),
+            }
+            .into());
+        }
+
+        let deliver_tx = &tx_commit_response.deliver_tx;
+        if deliver_tx.code.is_err() {
+            return Err(TxRejected {
+                stage: "deliver_tx",
+                code: deliver_tx.code.value(),
+                codespace: deliver_tx.codespace.clone(),
+                response: format!("{deliver_tx:?}"),
+            }
+            .into());
+        }
+
+        Ok(tx_commit_response)
+    }
+}
+
+fn zero_fee(gas_price: &GasPrice) -> Fee {
+    Fee::from_amount_and_gas(
+        Coin {
+            denom: gas_price.denom.clone(),
+            amount: 0u8.into(),
+        },
+        0u64,
+    )
+}
+
+fn fee_from_gas_used(gas_used: u64, gas_price: GasPrice, gas_adjustment: f64) -> Fee {
+    let gas_limit = ((gas_used as f64) * gas_adjustment).ceil();
+
+    let amount = Coin {
+        denom: gas_price.denom,
+        amount: ((gas_limit * gas_price.amount).ceil() as u64).into(),
+    };
+
+    Fee::from_amount_and_gas(amount, gas_limit as u64)
 }
 
 pub struct SigningClient {
@@ -200,13 +474,7 @@ impl SigningClient {
                     Some(self.signing_key.public_key()),
                     account.sequence,
                 );
-                let auth_info = signer_info.auth_info(Fee::from_amount_and_gas(
-                    Coin {
-                        denom: gas_price.denom.clone(),
-                        amount: 0u8.into(),
-                    },
-                    0u64,
-                ));
+                let auth_info = signer_info.auth_info(zero_fee(&gas_price));
                 let sign_doc = SignDoc::new(
                     &tx_body,
                     &auth_info,
@@ -216,15 +484,12 @@ impl SigningClient {
                 .unwrap();
                 let tx_raw = sign_doc.sign(&self.signing_key).unwrap();
                 let gas_info = self.inner.simulate(tx_raw.to_bytes().unwrap()).await?;
-                let gas_limit: u64 = gas_info.gas_used;
-                let gas_limit = ((gas_limit as f64) * gas_adjustment).ceil();
-
-                let amount = Coin {
-                    denom: gas_price.denom,
-                    amount: ((gas_limit * gas_price.amount).ceil() as u64).into(),
-                };
 
-                Ok(Fee::from_amount_and_gas(amount, gas_limit as u64))
+                Ok(fee_from_gas_used(
+                    gas_info.gas_used,
+                    gas_price,
+                    gas_adjustment,
+                ))
             }
         }
     }
@@ -233,31 +498,84 @@ impl SigningClient {
         &self,
         msgs: Vec<Any>,
         gas: &Gas,
-        memo: &str,
-        timeout_height: &u32,
+        tx_options: &TxOptions,
         account_sequence: &Option<u64>,
     ) -> Result<TxCommitResponse> {
+        let signer = self.signer_account_id();
         let mut acc = self
             .inner
-            .account(self.signer_account_id().as_ref())
+            .account(signer.as_ref())
             .await
             .with_context(|| "Account can't be initialized")?;
 
-        // manually set sequence if provided
-        acc.sequence = account_sequence.unwrap_or(acc.sequence);
-
-        let tx_body = tx::Body::new(msgs, memo, *timeout_height);
+        // manually set sequence if provided, otherwise don't go behind txs already sent by this process
+        let tracker_key = (
+            self.inner.network.chain_id().to_string(),
+            signer.to_string(),
+        );
+        acc.sequence = account_sequence.unwrap_or_else(|| {
+            let tracked = sequence_tracker()
+                .lock()
+                .unwrap()
+                .get(&tracker_key)
+                .copied();
+            tracked.map_or(acc.sequence, |seq| seq.max(acc.sequence))
+        });
+
+        let tx_body = self.inner.tx_body(msgs, tx_options).await?;
+
+        let mut retries = 0;
+        loop {
+            match self
+                .sign_and_broadcast_body(gas, tx_options, &acc, &tx_body)
+                .await
+            {
+                Ok(response) => {
+                    sequence_tracker()
+                        .lock()
+                        .unwrap()
+                        .insert(tracker_key, acc.sequence + 1);
+                    return Ok(response);
+                }
+                Err(err) if retries < MAX_SEQUENCE_MISMATCH_RETRIES => {
+                    let expected = match expected_sequence(&format!("{err:#}")) {
+                        Some(expected) => expected,
+                        // the log doesn't carry the expected sequence, ask the chain for it
+                        None if err
+                            .downcast_ref::<TxRejected>()
+                            .is_some_and(TxRejected::is_wrong_sequence) =>
+                        {
+                            self.inner.account(signer.as_ref()).await?.sequence
+                        }
+                        None => return Err(err),
+                    };
+                    if expected == acc.sequence {
+                        return Err(err);
+                    }
+                    retries += 1;
+                    acc.sequence = expected;
+                }
+                Err(err) => return Err(err),
+            }
+        }
+    }
 
-        let fee = self
-            .estimate_fee(gas.clone(), &acc, tx_body.clone())
-            .await?;
+    async fn sign_and_broadcast_body(
+        &self,
+        gas: &Gas,
+        tx_options: &TxOptions,
+        acc: &BaseAccount,
+        tx_body: &tx::Body,
+    ) -> Result<TxCommitResponse> {
+        let fee = self.estimate_fee(gas.clone(), acc, tx_body.clone()).await?;
+        let fee = tx_options.apply_to_fee(fee);
 
         let auth_info =
             SignerInfo::single_direct(Some(self.signing_key.public_key()), acc.sequence)
                 .auth_info(fee.clone());
 
         let sign_doc = SignDoc::new(
-            &tx_body,
+            tx_body,
             &auth_info,
             &self.inner.network.chain_id().parse().unwrap(),
             acc.account_number,
@@ -265,27 +583,159 @@ impl SigningClient {
         .unwrap();
         let tx_raw = sign_doc.sign(&self.signing_key).unwrap();
 
-        let rpc_client = rpc::HttpClient::new(self.inner.network.rpc_endpoint().as_str()).unwrap();
-        dev::poll_for_first_block(&rpc_client).await;
+        self.inner.broadcast(tx_raw).await
+    }
+}
 
-        let tx_commit_response = tx_raw.broadcast_commit(&rpc_client).await.unwrap();
+/// Number of times a tx is re-signed with the sequence expected by the chain
+const MAX_SEQUENCE_MISMATCH_RETRIES: u32 = 3;
 
-        if tx_commit_response.check_tx.code.is_err() {
-            return Err(anyhow!(
-                "check_tx failed: {:?}",
-                tx_commit_response.check_tx
-            ));
-        }
+/// Code of the sdk's `ErrWrongSequence`
+const ERR_WRONG_SEQUENCE: u32 = 32;
+
+/// Tx rejected by the chain at `check_tx` or `deliver_tx`.
+#[derive(Debug)]
+struct TxRejected {
+    stage: &'static str,
+    code: u32,
+    codespace: String,
+    response: String,
+}
+
+impl TxRejected {
+    fn is_wrong_sequence(&self) -> bool {
+        self.codespace == "sdk" && self.code == ERR_WRONG_SEQUENCE
+    }
+}
+
+impl std::fmt::Display for TxRejected {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} failed: {}", self.stage, self.response)
+    }
+}
+
+impl std::error::Error for TxRejected {}
+
+/// Next sequence of each signer (by chain id and address) after the txs broadcasted by this process,
+/// so that chained txs, eg. in `deploy` or tasks, don't depend on nodes having caught up with them.
+fn sequence_tracker() -> &'static Mutex<HashMap<(String, String), u64>> {
+    static SEQUENCE_TRACKER: OnceLock<Mutex<HashMap<(String, String), u64>>> = OnceLock::new();
+    SEQUENCE_TRACKER.get_or_init(Default::default)
+}
 
-        if tx_commit_response.deliver_tx.code.is_err() {
-            return Err(anyhow!(
-                "deliver_tx failed: {:?}",
-                tx_commit_response.deliver_tx
-            ));
+/// Sequence expected by the chain from the sdk's `ErrWrongSequence` error
+/// eg. "account sequence mismatch, expected 5, got 4: incorrect account sequence"
+fn expected_sequence(error: &str) -> Option<u64> {
+    static EXPECTED_SEQUENCE: OnceLock<Regex> = OnceLock::new();
+    EXPECTED_SEQUENCE
+        .get_or_init(|| Regex::new(r"account sequence mismatch, expected (\d+)").unwrap())
+        .captures(error)?[1]
+        .parse()
+        .ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use tendermint::abci::EventAttribute;
+
+    use super::*;
+
+    fn event(kind: &str, attributes: &[(&str, &str)]) -> Event {
+        Event {
+            kind: kind.to_string(),
+            attributes: attributes
+                .iter()
+                .map(|(key, value)| EventAttribute {
+                    key: base64::encode(key),
+                    value: base64::encode(value),
+                    index: true,
+                })
+                .collect(),
         }
+    }
 
-        // dev::poll_for_tx(&rpc_client, tx_commit_response.hash).await;
+    #[test]
+    fn group_msg_events_by_message_action() {
+        let events = vec![
+            event("tx", &[("fee", "1000uosmo")]),
+            event(
+                "message",
+                &[("action", "/cosmwasm.wasm.v1.MsgExecuteContract")],
+            ),
+            event("execute", &[("_contract_address", "osmo1a")]),
+            event("message", &[("module", "wasm")]),
+            event(
+                "message",
+                &[("action", "/cosmwasm.wasm.v1.MsgInstantiateContract")],
+            ),
+            event("instantiate", &[("_contract_address", "osmo1b")]),
+        ];
+
+        let msg_events = group_msg_events(&events);
+
+        assert_eq!(msg_events.len(), 2);
+        assert_eq!(msg_events[0].len(), 3);
+        assert_eq!(
+            attribute_value(&msg_events[1][1], "_contract_address"),
+            Some("osmo1b".to_string())
+        );
+    }
 
-        Ok(tx_commit_response)
+    #[test]
+    fn group_msg_events_by_msg_index() {
+        let events = vec![
+            event("tx", &[("fee", "1000uosmo")]),
+            event(
+                "execute",
+                &[("_contract_address", "osmo1a"), ("msg_index", "0")],
+            ),
+            event(
+                "instantiate",
+                &[("_contract_address", "osmo1b"), ("msg_index", "1")],
+            ),
+            event(
+                "message",
+                &[
+                    ("action", "/cosmwasm.wasm.v1.MsgInstantiateContract"),
+                    ("msg_index", "1"),
+                ],
+            ),
+        ];
+
+        let msg_events = group_msg_events(&events);
+
+        assert_eq!(msg_events.len(), 2);
+        assert_eq!(msg_events[0].len(), 1);
+        assert_eq!(msg_events[1][0].kind, "instantiate");
+    }
+
+    #[test]
+    fn parse_expected_sequence() {
+        assert_eq!(
+            expected_sequence(
+                "check_tx failed: Response { code: Err(32), log: \"account sequence mismatch, expected 5, got 4: incorrect account sequence\" }"
+            ),
+            Some(5)
+        );
+        assert_eq!(expected_sequence("out of gas"), None);
+    }
+
+    #[test]
+    fn wrong_sequence_by_code() {
+        let rejected = |codespace: &str, code| TxRejected {
+            stage: "check_tx",
+            code,
+            codespace: codespace.to_string(),
+            response: String::new(),
+        };
+
+        assert!(rejected("sdk", 32).is_wrong_sequence());
+        assert!(!rejected("sdk", 11).is_wrong_sequence());
+        assert!(!rejected("wasm", 32).is_wrong_sequence());
+
+        let err = anyhow::Error::from(rejected("sdk", 32)).context("Unable to broadcast");
+        assert!(err
+            .downcast_ref::<TxRejected>()
+            .is_some_and(TxRejected::is_wrong_sequence));
     }
 }
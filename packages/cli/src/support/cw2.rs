@@ -0,0 +1,201 @@
+use std::{fs, path::Path};
+
+use anyhow::{Context, Result};
+use serde::Deserialize;
+
+/// Key under which cw2 stores the contract's `ContractVersion`.
+pub const CONTRACT_INFO_KEY: &[u8] = b"contract_info";
+
+/// cw2 contract name and version, eg. `crates.io:counter` at `0.1.0`.
+#[derive(Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct ContractVersion {
+    pub contract: String,
+    pub version: String,
+}
+
+impl ContractVersion {
+    pub fn from_slice(raw: &[u8]) -> Result<Self> {
+        serde_json::from_slice(raw).context("Unable to parse cw2 contract info")
+    }
+
+    /// Crate name of the contract, as cw2 names are conventionally `crates.io:<crate name>`.
+    fn crate_name(&self) -> String {
+        self.contract
+            .trim_start_matches("crates.io:")
+            .replace('-', "_")
+    }
+}
+
+impl std::fmt::Display for ContractVersion {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} {}", self.contract, self.version)
+    }
+}
+
+#[derive(Deserialize)]
+struct Manifest {
+    package: Package,
+}
+
+#[derive(Deserialize)]
+struct Package {
+    name: String,
+    version: toml::Value,
+}
+
+#[derive(Deserialize)]
+struct WorkspaceManifest {
+    workspace: Workspace,
+}
+
+#[derive(Deserialize)]
+struct Workspace {
+    package: WorkspacePackage,
+}
+
+#[derive(Deserialize)]
+struct WorkspacePackage {
+    version: String,
+}
+
+/// Version of the contract's crate in `contracts/<contract_name>`, named as cw2 names it.
+/// Versions inherited from the workspace are read from the root `Cargo.toml`.
+pub fn local_contract_version(root: &Path, contract_name: &str) -> Result<ContractVersion> {
+    let read = |path: &Path| {
+        fs::read_to_string(path).with_context(|| format!("Unable to read `{}`", path.display()))
+    };
+
+    let manifest: Manifest = toml::from_str(&read(
+        &root
+            .join("contracts")
+            .join(contract_name)
+            .join("Cargo.toml"),
+    )?)?;
+    let version = match manifest.package.version {
+        toml::Value::String(version) => version,
+        _ => {
+            let workspace: WorkspaceManifest = toml::from_str(&read(&root.join("Cargo.toml"))?)?;
+            workspace.workspace.package.version
+        }
+    };
+
+    Ok(ContractVersion {
+        contract: format!("crates.io:{}", manifest.package.name),
+        version,
+    })
+}
+
+/// Why migrating the `deployed` contract to the `local` one is unsafe: a different contract,
+/// or an older version of it. `None` when the migration is safe.
+pub fn migration_violation(
+    deployed: &ContractVersion,
+    local: &ContractVersion,
+) -> Result<Option<String>> {
+    if deployed.crate_name() != local.crate_name() {
+        return Ok(Some(format!(
+            "contract `{}` would be migrated to a different contract `{}`",
+            deployed.contract, local.contract
+        )));
+    }
+
+    let parse = |version: &str| {
+        semver::Version::parse(version).with_context(|| format!("Invalid version `{version}`"))
+    };
+    if parse(&local.version)? < parse(&deployed.version)? {
+        return Ok(Some(format!(
+            "`{}` would be downgraded from {} to {}",
+            deployed.contract, deployed.version, local.version
+        )));
+    }
+
+    Ok(None)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn version(contract: &str, version: &str) -> ContractVersion {
+        ContractVersion {
+            contract: contract.to_string(),
+            version: version.to_string(),
+        }
+    }
+
+    #[test]
+    fn parse_stored_contract_version() {
+        assert_eq!(
+            ContractVersion::from_slice(br#"{"contract":"crates.io:counter","version":"0.1.0"}"#)
+                .unwrap(),
+            version("crates.io:counter", "0.1.0")
+        );
+        assert!(ContractVersion::from_slice(b"counter").is_err());
+    }
+
+    #[test]
+    fn refuse_different_contract_or_downgrade() {
+        let deployed = version("crates.io:counter", "0.2.0");
+
+        assert_eq!(
+            migration_violation(&deployed, &version("crates.io:counter", "0.2.1")).unwrap(),
+            None
+        );
+        assert_eq!(
+            migration_violation(&deployed, &version("crates.io:counter", "0.2.0")).unwrap(),
+            None
+        );
+        assert_eq!(
+            migration_violation(
+                &version("crates.io:cw-counter", "0.1.0"),
+                &version("crates.io:cw_counter", "0.1.0")
+            )
+            .unwrap(),
+            None
+        );
+        assert!(
+            migration_violation(&deployed, &version("crates.io:counter", "0.1.9"))
+                .unwrap()
+                .is_some()
+        );
+        assert!(
+            migration_violation(&deployed, &version("crates.io:cw20-base", "1.0.0"))
+                .unwrap()
+                .is_some()
+        );
+    }
+
+    #[test]
+    fn local_version_from_manifest() {
+        let temp = assert_fs::TempDir::new().unwrap();
+        let root = temp.path();
+        let contract = root.join("contracts").join("counter");
+        fs::create_dir_all(&contract).unwrap();
+        fs::write(
+            root.join("Cargo.toml"),
+            "[workspace]\nmembers = [\"contracts/*\"]\n\n[workspace.package]\nversion = \"0.3.0\"\n",
+        )
+        .unwrap();
+
+        fs::write(
+            contract.join("Cargo.toml"),
+            "[package]\nname = \"counter\"\nversion = \"0.2.0\"\n",
+        )
+        .unwrap();
+        assert_eq!(
+            local_contract_version(root, "counter").unwrap(),
+            version("crates.io:counter", "0.2.0")
+        );
+
+        fs::write(
+            contract.join("Cargo.toml"),
+            "[package]\nname = \"counter\"\nversion = { workspace = true }\n",
+        )
+        .unwrap();
+        assert_eq!(
+            local_contract_version(root, "counter").unwrap(),
+            version("crates.io:counter", "0.3.0")
+        );
+
+        temp.close().unwrap();
+    }
+}
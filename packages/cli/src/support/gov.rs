@@ -0,0 +1,220 @@
+use anyhow::{anyhow, Result};
+use cosmos_sdk_proto::cosmos::base::v1beta1::Coin;
+use cosmrs::{AccountId, Any};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+use super::cosmos::Client;
+use super::future::block;
+
+pub const MSG_SUBMIT_PROPOSAL_V1BETA1_TYPE_URL: &str = "/cosmos.gov.v1beta1.MsgSubmitProposal";
+pub const MSG_SUBMIT_PROPOSAL_V1_TYPE_URL: &str = "/cosmos.gov.v1.MsgSubmitProposal";
+pub const MSG_VOTE_V1BETA1_TYPE_URL: &str = "/cosmos.gov.v1beta1.MsgVote";
+pub const MSG_VOTE_V1_TYPE_URL: &str = "/cosmos.gov.v1.MsgVote";
+pub const MSG_VOTE_WEIGHTED_V1BETA1_TYPE_URL: &str = "/cosmos.gov.v1beta1.MsgVoteWeighted";
+pub const MSG_VOTE_WEIGHTED_V1_TYPE_URL: &str = "/cosmos.gov.v1.MsgVoteWeighted";
+pub const MSG_DEPOSIT_V1BETA1_TYPE_URL: &str = "/cosmos.gov.v1beta1.MsgDeposit";
+pub const MSG_DEPOSIT_V1_TYPE_URL: &str = "/cosmos.gov.v1.MsgDeposit";
+
+/// Version of the gov module used for submitting, voting and querying proposals.
+/// `v1beta1` proposals carry a legacy content, `v1` proposals carry messages
+/// executed by the gov module.
+#[derive(clap::ValueEnum, Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum GovVersion {
+    V1beta1,
+    V1,
+}
+
+/// Address of the gov module account, the authority of the messages in `v1` proposals.
+pub fn gov_module_address(account_prefix: &str) -> Result<AccountId> {
+    let hash = Sha256::digest("gov".as_bytes());
+    AccountId::new(account_prefix, &hash[..20]).map_err(|e| anyhow!(e))
+}
+
+/// `version` if given, otherwise the latest version supported by the chain.
+pub fn resolve_gov_version(client: &Client, version: Option<GovVersion>) -> Result<GovVersion> {
+    match version {
+        Some(v) => Ok(v),
+        None => block(client.gov_version()),
+    }
+}
+
+// `cosmos.gov.v1` is not exported by cosmos-sdk-proto, the messages used by beaker are defined here.
+
+/// `cosmos.gov.v1.MsgSubmitProposal` including the fields added in sdk v0.47 and v0.50,
+/// which are not available in the generated protos.
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct MsgSubmitProposal {
+    #[prost(message, repeated, tag = "1")]
+    pub messages: Vec<Any>,
+    #[prost(message, repeated, tag = "2")]
+    pub initial_deposit: Vec<Coin>,
+    #[prost(string, tag = "3")]
+    pub proposer: String,
+    #[prost(string, tag = "4")]
+    pub metadata: String,
+    #[prost(string, tag = "5")]
+    pub title: String,
+    #[prost(string, tag = "6")]
+    pub summary: String,
+    #[prost(bool, tag = "7")]
+    pub expedited: bool,
+}
+
+/// `cosmos.gov.v1.Proposal` including the fields added in sdk v0.47 and v0.50.
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct Proposal {
+    #[prost(uint64, tag = "1")]
+    pub id: u64,
+    #[prost(message, repeated, tag = "2")]
+    pub messages: Vec<Any>,
+    #[prost(int32, tag = "3")]
+    pub status: i32,
+    #[prost(message, optional, tag = "4")]
+    pub final_tally_result: Option<TallyResult>,
+    #[prost(message, optional, tag = "5")]
+    pub submit_time: Option<prost_types::Timestamp>,
+    #[prost(message, optional, tag = "6")]
+    pub deposit_end_time: Option<prost_types::Timestamp>,
+    #[prost(message, repeated, tag = "7")]
+    pub total_deposit: Vec<Coin>,
+    #[prost(message, optional, tag = "8")]
+    pub voting_start_time: Option<prost_types::Timestamp>,
+    #[prost(message, optional, tag = "9")]
+    pub voting_end_time: Option<prost_types::Timestamp>,
+    #[prost(string, tag = "10")]
+    pub metadata: String,
+    #[prost(string, tag = "11")]
+    pub title: String,
+    #[prost(string, tag = "12")]
+    pub summary: String,
+    #[prost(string, tag = "13")]
+    pub proposer: String,
+    #[prost(bool, tag = "14")]
+    pub expedited: bool,
+}
+
+/// `cosmos.gov.v1.TallyResult`
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct TallyResult {
+    #[prost(string, tag = "1")]
+    pub yes_count: String,
+    #[prost(string, tag = "2")]
+    pub abstain_count: String,
+    #[prost(string, tag = "3")]
+    pub no_count: String,
+    #[prost(string, tag = "4")]
+    pub no_with_veto_count: String,
+}
+
+/// `cosmos.gov.v1.QueryProposalRequest`
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct QueryProposalRequest {
+    #[prost(uint64, tag = "1")]
+    pub proposal_id: u64,
+}
+
+/// `cosmos.gov.v1.QueryProposalResponse` carrying the extended `Proposal`.
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct QueryProposalResponse {
+    #[prost(message, optional, tag = "1")]
+    pub proposal: Option<Proposal>,
+}
+
+/// `cosmos.gov.v1.QueryParamsRequest`, `params_type` is one of `voting`, `tallying` or `deposit`.
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct QueryParamsRequest {
+    #[prost(string, tag = "1")]
+    pub params_type: String,
+}
+
+/// `cosmos.gov.v1.MsgExecLegacyContent`, wrapping a `v1beta1` content into a `v1` proposal message.
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct MsgExecLegacyContent {
+    #[prost(message, optional, tag = "1")]
+    pub content: Option<Any>,
+    #[prost(string, tag = "2")]
+    pub authority: String,
+}
+
+/// `cosmos.gov.v1.MsgVote`, `option` is a `v1beta1::VoteOption` which is the same in `v1`.
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct MsgVote {
+    #[prost(uint64, tag = "1")]
+    pub proposal_id: u64,
+    #[prost(string, tag = "2")]
+    pub voter: String,
+    #[prost(int32, tag = "3")]
+    pub option: i32,
+    #[prost(string, tag = "4")]
+    pub metadata: String,
+}
+
+/// `cosmos.gov.v1.WeightedVoteOption`
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct WeightedVoteOption {
+    #[prost(int32, tag = "1")]
+    pub option: i32,
+    #[prost(string, tag = "2")]
+    pub weight: String,
+}
+
+/// `cosmos.gov.v1.MsgVoteWeighted`
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct MsgVoteWeighted {
+    #[prost(uint64, tag = "1")]
+    pub proposal_id: u64,
+    #[prost(string, tag = "2")]
+    pub voter: String,
+    #[prost(message, repeated, tag = "3")]
+    pub options: Vec<WeightedVoteOption>,
+    #[prost(string, tag = "4")]
+    pub metadata: String,
+}
+
+/// `cosmos.gov.v1.MsgDeposit`
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct MsgDeposit {
+    #[prost(uint64, tag = "1")]
+    pub proposal_id: u64,
+    #[prost(string, tag = "2")]
+    pub depositor: String,
+    #[prost(message, repeated, tag = "3")]
+    pub amount: Vec<Coin>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::support::proto::MessageExt;
+    use prost::Message;
+
+    #[test]
+    fn gov_module_address_for_prefix() {
+        assert_eq!(
+            gov_module_address("osmo").unwrap().to_string(),
+            "osmo10d07y265gmmuvt4z0w9aw880jnsr700jjeq4qp"
+        );
+    }
+
+    #[test]
+    fn msg_submit_proposal_encodes_fields_of_newer_sdks() {
+        let msg = MsgSubmitProposal {
+            proposer: "a".to_owned(),
+            title: "t".to_owned(),
+            expedited: true,
+            ..Default::default()
+        };
+
+        // proposer (3), title (5) and expedited (7) with their wire types
+        assert_eq!(
+            msg.to_bytes().unwrap(),
+            vec![0x1a, 1, b'a', 0x2a, 1, b't', 0x38, 1]
+        );
+        assert_eq!(
+            MsgSubmitProposal::decode(msg.to_bytes().unwrap().as_slice()).unwrap(),
+            msg
+        );
+    }
+}
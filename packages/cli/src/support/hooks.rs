@@ -4,6 +4,9 @@ use anyhow::Context as _;
 use console::style;
 use dialoguer::Confirm;
 
+/// Code id of the contract, synced from its latest store code proposal.
+/// The synced code id is only written to the state file when `update_state` is set.
+#[allow(clippy::too_many_arguments)]
 pub fn use_code_id<'a, Ctx: Context<'a, WasmConfig>>(
     ctx: &Ctx,
     network: &str,
@@ -12,6 +15,7 @@ pub fn use_code_id<'a, Ctx: Context<'a, WasmConfig>>(
     contract_name: &str,
     no_proposal_sync: bool,
     yes: bool,
+    update_state: bool,
 ) -> Result<u64, anyhow::Error> {
     let code_id = block(async {
         let wasm_ref = state.get_ref(network, contract_name)?;
@@ -26,7 +30,7 @@ pub fn use_code_id<'a, Ctx: Context<'a, WasmConfig>>(
 
         let proposal_id = wasm_ref
             .proposal()
-            .store_code()
+            .latest_code_upload()
             .with_context(|| style(format!(
                 "Proposal store code not found for contract `{contract_name}` on network `{network}`. \n\n\
                   Use {} option to ignore proposal syncing.\n",
@@ -44,6 +48,8 @@ pub fn use_code_id<'a, Ctx: Context<'a, WasmConfig>>(
                 // code_id from proposal found but no new update
                 if code_id_from_proposal == current_code_id {
                     anyhow::Ok(current_code_id)
+                } else if !update_state {
+                    anyhow::Ok(code_id_from_proposal)
                 } else {
                     println!();
                     println!(
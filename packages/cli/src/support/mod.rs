@@ -1,15 +1,23 @@
+pub mod amino;
+pub mod authz;
+pub mod chain;
 pub mod coin;
 pub mod command;
 pub mod cosmos;
+pub mod cw2;
 pub mod future;
 pub mod gas;
+pub mod gov;
 pub mod hooks;
+pub mod multisig;
 pub mod node;
 pub mod ops_response;
 pub mod permission;
 pub mod proto;
 pub mod signer;
 pub mod state;
+pub mod storage_key;
 pub mod string;
 pub mod template;
+pub mod tx;
 pub mod wasm;
@@ -0,0 +1,308 @@
+use anyhow::{anyhow, bail, Context, Result};
+use clap::Parser;
+use cosmos_sdk_proto::cosmos::crypto::multisig::v1beta1::{CompactBitArray, MultiSignature};
+use cosmos_sdk_proto::cosmos::crypto::multisig::LegacyAminoPubKey;
+use cosmos_sdk_proto::cosmos::crypto::secp256k1::PubKey;
+use cosmos_sdk_proto::cosmos::tx::signing::v1beta1::SignMode;
+use cosmos_sdk_proto::cosmos::tx::v1beta1::{mode_info, ModeInfo, SignerInfo};
+use cosmos_sdk_proto::Any;
+use cosmrs::bip32::secp256k1::ecdsa::{signature::Verifier, Signature, VerifyingKey};
+use cosmrs::AccountId;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+use super::proto::MessageExt;
+use super::tx::MultisigSignature;
+
+#[derive(Debug, Parser, Clone, Deserialize)]
+pub struct MultisigArgs {
+    /// Build the transaction for the multisig account made of `--multisig-pubkeys` instead of the signer.
+    /// Members sign it with `beaker tx sign` and the signatures are combined with `beaker tx multisign`
+    #[clap(
+        long,
+        requires_all = ["multisig_threshold", "multisig_pubkeys", "generate_only"]
+    )]
+    #[serde(default = "default_value::from_multisig")]
+    pub from_multisig: bool,
+
+    /// Number of member signatures required by the multisig account
+    #[clap(long)]
+    pub multisig_threshold: Option<u32>,
+
+    /// Comma separated, base64 encoded secp256k1 public keys of the multisig members, in the multisig's order
+    #[clap(long, value_delimiter = ',')]
+    #[serde(default = "default_value::multisig_pubkeys")]
+    pub multisig_pubkeys: Vec<String>,
+}
+
+mod default_value {
+    pub(crate) fn from_multisig() -> bool {
+        false
+    }
+
+    pub(crate) fn multisig_pubkeys() -> Vec<String> {
+        vec![]
+    }
+}
+
+impl MultisigArgs {
+    pub fn multisig(&self) -> Result<Option<Multisig>> {
+        if !self.from_multisig {
+            return Ok(None);
+        }
+
+        let threshold = self
+            .multisig_threshold
+            .with_context(|| "`--multisig-threshold` is required with `--from-multisig`")?;
+        Multisig::new(threshold, self.multisig_pubkeys.clone()).map(Some)
+    }
+}
+
+/// Legacy amino multisig account, threshold out of the members' secp256k1 public keys.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct Multisig {
+    pub threshold: u32,
+    /// Base64 encoded compressed secp256k1 public keys
+    pub public_keys: Vec<String>,
+}
+
+impl Multisig {
+    pub fn new(threshold: u32, public_keys: Vec<String>) -> Result<Self> {
+        if threshold == 0 || threshold as usize > public_keys.len() {
+            bail!(
+                "Multisig threshold must be between 1 and the number of public keys ({}), got {threshold}",
+                public_keys.len()
+            );
+        }
+
+        let multisig = Multisig {
+            threshold,
+            public_keys,
+        };
+        multisig.public_key_bytes()?;
+
+        Ok(multisig)
+    }
+
+    fn public_key_bytes(&self) -> Result<Vec<Vec<u8>>> {
+        self.public_keys
+            .iter()
+            .map(|pk| {
+                let bytes = base64::decode(pk)?;
+                if bytes.len() != 33 || VerifyingKey::from_sec1_bytes(&bytes).is_err() {
+                    bail!("`{pk}` is not a valid compressed secp256k1 public key");
+                }
+                Ok(bytes)
+            })
+            .collect()
+    }
+
+    /// Address of the multisig, the first 20 bytes of sha256 of its amino encoded public key.
+    pub fn account_id(&self, account_prefix: &str) -> Result<AccountId> {
+        let mut amino = vec![0x22, 0xc1, 0xf7, 0xe2, 0x08];
+        encode_uvarint(self.threshold as u64, &mut amino);
+        for pk in self.public_key_bytes()? {
+            let mut amino_pk = vec![0xeb, 0x5a, 0xe9, 0x87, 0x21];
+            amino_pk.extend(pk);
+
+            amino.push(0x12);
+            encode_uvarint(amino_pk.len() as u64, &mut amino);
+            amino.extend(amino_pk);
+        }
+
+        AccountId::new(account_prefix, &Sha256::digest(&amino)[..20]).map_err(|e| anyhow!(e))
+    }
+
+    /// Position of the public key in the multisig, if it is a member.
+    pub fn member_index(&self, public_key: &[u8]) -> Result<Option<usize>> {
+        Ok(self
+            .public_key_bytes()?
+            .iter()
+            .position(|pk| pk.as_slice() == public_key))
+    }
+
+    /// Signer info and signature of the multisig, combined from the members' signatures
+    /// over `sign_bytes`. Requires at least `threshold` valid signatures of distinct members.
+    pub fn combine(
+        &self,
+        signatures: &[MultisigSignature],
+        sign_bytes: &[u8],
+        sequence: u64,
+    ) -> Result<(SignerInfo, Vec<u8>)> {
+        let public_keys = self.public_key_bytes()?;
+        let mut member_signatures: Vec<Option<Vec<u8>>> = vec![None; public_keys.len()];
+
+        for s in signatures {
+            let public_key = base64::decode(&s.public_key)?;
+            let index = self
+                .member_index(&public_key)?
+                .with_context(|| format!("`{}` is not a member of the multisig", s.signer))?;
+
+            let signature = base64::decode(&s.signature)?;
+            VerifyingKey::from_sec1_bytes(&public_key)
+                .map_err(|e| anyhow!(e))?
+                .verify(
+                    sign_bytes,
+                    &Signature::try_from(signature.as_slice()).map_err(|e| anyhow!(e))?,
+                )
+                .map_err(|_| anyhow!("Invalid signature from `{}`", s.signer))?;
+
+            member_signatures[index] = Some(signature);
+        }
+
+        let signed_count = member_signatures.iter().flatten().count();
+        if signed_count < self.threshold as usize {
+            bail!(
+                "Multisig requires {} signatures, only {signed_count} provided",
+                self.threshold
+            );
+        }
+
+        let mut bitarray = CompactBitArray {
+            extra_bits_stored: (public_keys.len() % 8) as u32,
+            elems: vec![0; public_keys.len().div_ceil(8)],
+        };
+        for (i, signature) in member_signatures.iter().enumerate() {
+            if signature.is_some() {
+                bitarray.elems[i / 8] |= 1 << (7 - i % 8);
+            }
+        }
+
+        let legacy_amino_pubkey = LegacyAminoPubKey {
+            threshold: self.threshold,
+            public_keys: public_keys
+                .into_iter()
+                .map(|key| {
+                    Ok(Any {
+                        type_url: "/cosmos.crypto.secp256k1.PubKey".to_owned(),
+                        value: PubKey { key }.to_bytes()?,
+                    })
+                })
+                .collect::<Result<Vec<_>>>()?,
+        };
+
+        let signer_info = SignerInfo {
+            public_key: Some(Any {
+                type_url: "/cosmos.crypto.multisig.LegacyAminoPubKey".to_owned(),
+                value: legacy_amino_pubkey.to_bytes()?,
+            }),
+            mode_info: Some(ModeInfo {
+                sum: Some(mode_info::Sum::Multi(mode_info::Multi {
+                    bitarray: Some(bitarray),
+                    mode_infos: vec![
+                        ModeInfo {
+                            sum: Some(mode_info::Sum::Single(mode_info::Single {
+                                mode: SignMode::LegacyAminoJson.into(),
+                            })),
+                        };
+                        signed_count
+                    ],
+                })),
+            }),
+            sequence,
+        };
+
+        let multi_signature = MultiSignature {
+            signatures: member_signatures.into_iter().flatten().collect(),
+        };
+
+        Ok((signer_info, multi_signature.to_bytes()?))
+    }
+}
+
+fn encode_uvarint(mut value: u64, buf: &mut Vec<u8>) {
+    while value >= 0x80 {
+        buf.push((value as u8 & 0x7f) | 0x80);
+        value >>= 7;
+    }
+    buf.push(value as u8);
+}
+
+#[cfg(test)]
+mod tests {
+    use cosmrs::crypto::secp256k1::SigningKey;
+
+    use super::*;
+
+    fn member(seed: u8) -> SigningKey {
+        SigningKey::from_slice(&[seed; 32]).unwrap()
+    }
+
+    fn multisig(threshold: u32) -> Multisig {
+        Multisig::new(
+            threshold,
+            (1..=3)
+                .map(|seed| base64::encode(member(seed).public_key().to_bytes()))
+                .collect(),
+        )
+        .unwrap()
+    }
+
+    fn signature(seed: u8, sign_bytes: &[u8]) -> MultisigSignature {
+        let key = member(seed);
+        MultisigSignature {
+            signer: key.public_key().account_id("osmo").unwrap().to_string(),
+            public_key: base64::encode(key.public_key().to_bytes()),
+            signature: base64::encode(key.sign(sign_bytes).unwrap().to_bytes()),
+        }
+    }
+
+    #[test]
+    fn threshold_must_be_within_members() {
+        assert!(Multisig::new(0, multisig(1).public_keys).is_err());
+        assert!(Multisig::new(4, multisig(1).public_keys).is_err());
+    }
+
+    #[test]
+    fn combine_marks_signed_members() {
+        let sign_bytes = b"sign bytes";
+        let (signer_info, _) = multisig(2)
+            .combine(
+                &[signature(3, sign_bytes), signature(1, sign_bytes)],
+                sign_bytes,
+                4,
+            )
+            .unwrap();
+
+        let Some(mode_info::Sum::Multi(multi)) = signer_info.mode_info.unwrap().sum else {
+            panic!("expected multi mode info");
+        };
+        assert_eq!(signer_info.sequence, 4);
+        assert_eq!(multi.mode_infos.len(), 2);
+        assert_eq!(
+            multi.bitarray,
+            Some(CompactBitArray {
+                extra_bits_stored: 3,
+                elems: vec![0b1010_0000],
+            })
+        );
+    }
+
+    #[test]
+    fn combine_requires_threshold_of_valid_signatures() {
+        let sign_bytes = b"sign bytes";
+        let multisig = multisig(2);
+
+        assert!(multisig
+            .combine(
+                &[signature(1, sign_bytes), signature(1, sign_bytes)],
+                sign_bytes,
+                0
+            )
+            .is_err());
+        assert!(multisig
+            .combine(
+                &[signature(1, sign_bytes), signature(2, b"other bytes")],
+                sign_bytes,
+                0
+            )
+            .is_err());
+        assert!(multisig
+            .combine(
+                &[signature(1, sign_bytes), signature(4, sign_bytes)],
+                sign_bytes,
+                0
+            )
+            .is_err());
+    }
+}
@@ -1,7 +1,14 @@
 use std::str::FromStr;
 
-use anyhow::anyhow;
-use cosmrs::{cosmwasm::AccessConfig, AccountId};
+use anyhow::{anyhow, bail, Context};
+use cosmos_sdk_proto::cosmwasm::wasm::v1 as wasm;
+use cosmrs::{
+    cosmwasm::{AccessConfig, AccessType},
+    AccountId,
+};
+
+use super::signer::SignerArgs;
+use crate::framework::config::GlobalConfig;
 
 pub fn compute_admin(
     admin: Option<&String>,
@@ -16,26 +23,166 @@ pub fn compute_admin(
     })
 }
 
+/// Instantiate permission from `--permit-instantiate-only`, which is either `nobody` or
+/// a comma separated list of `signer`, predefined account names and addresses.
+/// A single address is permitted with `OnlyAddress`, several with `AnyOfAddresses`.
 pub fn compute_instantiate_permission(
     permit_instantiate_only: &Option<String>,
     signer_account_id: AccountId,
+    global_config: &GlobalConfig,
 ) -> Result<Option<AccessConfig>, anyhow::Error> {
-    let instantiate_permission = permit_instantiate_only
-        .as_ref()
-        .map(|permitted_account| {
-            let address = if permitted_account == "signer" {
-                signer_account_id
-            } else {
-                permitted_account
-                    .parse()
-                    .map_err(|e: cosmrs::ErrorReport| anyhow::anyhow!(e))?
-            };
-
-            anyhow::Ok(AccessConfig {
-                permission: cosmrs::cosmwasm::AccessType::OnlyAddress,
-                addresses: vec![address],
+    let Some(permitted) = permit_instantiate_only else {
+        return Ok(None);
+    };
+
+    if permitted.trim() == "nobody" {
+        return Ok(Some(AccessConfig {
+            permission: AccessType::Nobody,
+            addresses: vec![],
+        }));
+    }
+
+    let mut addresses = vec![];
+    for permitted_account in permitted.split(',').map(str::trim) {
+        let address = match permitted_account {
+            "signer" => signer_account_id.clone(),
+            "nobody" => bail!("`nobody` can not be combined with other addresses"),
+            "" => bail!("Empty address in `{permitted}`"),
+            name if global_config.accounts().contains_key(name) => SignerArgs {
+                signer_account: Some(name.to_string()),
+                ..Default::default()
+            }
+            .account_id(global_config)?,
+            address => address
+                .parse()
+                .map_err(|e: cosmrs::ErrorReport| anyhow!(e))
+                .with_context(|| format!("Invalid address or account name: {address}"))?,
+        };
+        if !addresses.contains(&address) {
+            addresses.push(address);
+        }
+    }
+
+    Ok(Some(AccessConfig {
+        permission: if addresses.len() == 1 {
+            AccessType::OnlyAddress
+        } else {
+            AccessType::AnyOfAddresses
+        },
+        addresses,
+    }))
+}
+
+/// Whether code stored with the `stored` permission grants the `requested` one.
+/// No requested permission stands for no restriction.
+pub fn is_same_permission(
+    requested: &Option<AccessConfig>,
+    stored: Option<&wasm::AccessConfig>,
+) -> bool {
+    let Some(stored) = stored else {
+        return false;
+    };
+
+    match requested {
+        None => stored.permission == AccessType::Everybody as i32,
+        Some(requested) => {
+            let mut stored_addresses = stored.addresses.clone();
+            if !stored.address.is_empty() && !stored_addresses.contains(&stored.address) {
+                stored_addresses.push(stored.address.clone());
+            }
+            stored_addresses.sort();
+
+            let mut requested_addresses = requested
+                .addresses
+                .iter()
+                .map(|a| a.to_string())
+                .collect::<Vec<_>>();
+            requested_addresses.sort();
+
+            stored.permission == requested.permission as i32
+                && stored_addresses == requested_addresses
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ADDRESS: &str = "osmo1cyyzpxplxdzkeea7kwsydadg87357qnahakaks";
+
+    #[test]
+    fn same_permission_as_stored_code() {
+        let everybody = wasm::AccessConfig {
+            permission: AccessType::Everybody as i32,
+            ..Default::default()
+        };
+        let only_address = wasm::AccessConfig {
+            permission: AccessType::OnlyAddress as i32,
+            address: ADDRESS.to_string(),
+            addresses: vec![],
+        };
+        let requested = compute_instantiate_permission(
+            &Some(ADDRESS.to_string()),
+            ADDRESS.parse().unwrap(),
+            &GlobalConfig::default(),
+        )
+        .unwrap();
+
+        assert!(is_same_permission(&None, Some(&everybody)));
+        assert!(!is_same_permission(&None, Some(&only_address)));
+        assert!(is_same_permission(&requested, Some(&only_address)));
+        assert!(!is_same_permission(&requested, Some(&everybody)));
+        assert!(!is_same_permission(&None, None));
+    }
+
+    #[test]
+    fn instantiate_permission_from_permitted_accounts() {
+        let global_config = GlobalConfig::default();
+        let signer: AccountId = ADDRESS.parse().unwrap();
+        let permission = |permitted: &str| {
+            compute_instantiate_permission(
+                &Some(permitted.to_string()),
+                signer.clone(),
+                &global_config,
+            )
+        };
+
+        assert_eq!(
+            compute_instantiate_permission(&None, signer.clone(), &global_config).unwrap(),
+            None
+        );
+        assert_eq!(
+            permission("nobody").unwrap(),
+            Some(AccessConfig {
+                permission: AccessType::Nobody,
+                addresses: vec![],
+            })
+        );
+        assert_eq!(
+            permission("signer").unwrap(),
+            Some(AccessConfig {
+                permission: AccessType::OnlyAddress,
+                addresses: vec![signer.clone()],
             })
-        })
-        .transpose()?;
-    Ok(instantiate_permission)
+        );
+
+        let test2 = SignerArgs {
+            signer_account: Some("test2".to_string()),
+            ..Default::default()
+        }
+        .account_id(&global_config)
+        .unwrap();
+        assert_eq!(
+            permission(&format!("signer, test2, {ADDRESS}")).unwrap(),
+            Some(AccessConfig {
+                permission: AccessType::AnyOfAddresses,
+                addresses: vec![signer.clone(), test2],
+            })
+        );
+
+        assert!(permission("signer,nobody").is_err());
+        assert!(permission("signer,").is_err());
+        assert!(permission("unknown").is_err());
+    }
 }
@@ -1,6 +1,6 @@
 use anyhow::bail;
 use clap::Parser;
-use cosmrs::{bip32, crypto::secp256k1::SigningKey};
+use cosmrs::{bip32, crypto::secp256k1::SigningKey, AccountId};
 use keyring::Entry;
 use serde::Deserialize;
 
@@ -12,7 +12,7 @@ use crate::{framework::config::Account, modules::key::config::SERVICE};
 
 const SIGNER_GROUP: &str = "signer";
 
-#[derive(Debug, Parser, Clone, Deserialize)]
+#[derive(Debug, Parser, Clone, Deserialize, Default)]
 #[clap(group = clap::ArgGroup::new(SIGNER_GROUP).multiple(false))]
 pub struct SignerArgs {
     /// Specifies predefined account as a tx signer
@@ -30,6 +30,10 @@ pub struct SignerArgs {
     /// Specifies private_key as a tx signer (base64 encoded string)
     #[clap(long, group = SIGNER_GROUP)]
     pub signer_private_key: Option<String>,
+
+    /// Specifies only the address of the tx signer, no key is loaded. Can only be used with `--generate-only`
+    #[clap(long, group = SIGNER_GROUP)]
+    pub signer_address: Option<String>,
 }
 
 impl SignerArgs {
@@ -42,17 +46,13 @@ impl SignerArgs {
             signer_keyring,
             signer_mnemonic,
             signer_private_key,
+            signer_address,
         } = self;
         let derivation_path = global_config.derivation_path();
         let signer_priv = if let Some(signer_account) = signer_account {
             match global_config.accounts().get(signer_account) {
                 None => bail!("signer account: `{signer_account}` is not defined"),
-                Some(Account::FromMnemonic { mnemonic }) => {
-                    SigningKey::from_mnemonic(mnemonic.as_str(), derivation_path)
-                }
-                Some(Account::FromPrivateKey { private_key }) => {
-                    Ok(SigningKey::from_slice(&base64::decode(private_key)?).unwrap())
-                }
+                Some(account) => SigningKey::from_account(account, derivation_path),
             }
         } else if let Some(signer_keyring) = signer_keyring {
             let mnemonic = Entry::new(SERVICE, signer_keyring).get_password()?;
@@ -61,11 +61,30 @@ impl SignerArgs {
             SigningKey::from_mnemonic(signer_mnemonic, derivation_path)
         } else if let Some(signer_private_key) = signer_private_key {
             Ok(SigningKey::from_slice(&base64::decode(signer_private_key)?).unwrap())
+        } else if signer_address.is_some() {
+            bail!("`--signer-address` does not carry a private key, it can only be used with `--generate-only`")
         } else {
             bail!("Unable to retrive signer private key")
         }?;
         Ok(signer_priv)
     }
+
+    /// Address of the tx signer, derived from its key unless given by `--signer-address`.
+    pub fn account_id(
+        &self,
+        global_config: &crate::framework::config::GlobalConfig,
+    ) -> Result<AccountId, anyhow::Error> {
+        if let Some(signer_address) = &self.signer_address {
+            return signer_address
+                .parse()
+                .map_err(|e: cosmrs::ErrorReport| anyhow::anyhow!(e));
+        }
+
+        self.private_key(global_config)?
+            .public_key()
+            .account_id(global_config.account_prefix())
+            .map_err(|e| anyhow::anyhow!(e))
+    }
 }
 
 pub trait SigningKeyExt {
@@ -75,6 +94,17 @@ pub trait SigningKeyExt {
         let signer_priv: SigningKey = xprv.into();
         Ok(signer_priv)
     }
+
+    fn from_account(account: &Account, derivation_path: &str) -> Result<SigningKey, anyhow::Error> {
+        match account {
+            Account::FromMnemonic { mnemonic } => {
+                SigningKey::from_mnemonic(mnemonic.as_str(), derivation_path)
+            }
+            Account::FromPrivateKey { private_key } => {
+                Ok(SigningKey::from_slice(&base64::decode(private_key)?).unwrap())
+            }
+        }
+    }
 }
 
 impl SigningKeyExt for SigningKey {}
@@ -1,35 +1,200 @@
-use std::{fs, path::PathBuf};
+use std::{
+    fs::{self, File, OpenOptions},
+    path::{Path, PathBuf},
+};
 
-use anyhow::{Context as _, Result};
+use anyhow::{bail, Context as _, Result};
 use config::Map;
+use console::style;
+use cosmrs::AccountId;
+use derive_new::new;
+use fs2::FileExt;
 use getset::Getters;
 use serde::{Deserialize, Serialize};
+use serde_json::{Map as JsonMap, Value};
 
+use super::cosmos::{Client, TxCommitResponse};
+use super::storage_key::encode_hex;
 use crate::framework::config::{Network, NetworkVariant};
 
 pub const STATE_DIR: &str = ".beaker";
 pub const STATE_FILE_LOCAL: &str = "state.local.json";
 pub const STATE_FILE_SHARED: &str = "state.json";
+const STATE_LOCK_FILE: &str = ".lock";
+
+/// Schema version of the state files, files written before it was recorded are at version 1.
+/// Only layout changes that older beakers can not read need a new version, along with its entry
+/// in `MIGRATIONS`. Adding optional fields does not.
+pub const STATE_VERSION: u64 = MIGRATIONS.len() as u64 + 1;
+/// Prefix of the top level entry marking the schema version of a state file. The entry is an
+/// empty object, which beakers predating the marker read as a network without contracts.
+const VERSION_KEY_PREFIX: &str = "$schema_v";
 
 #[derive(Serialize, Deserialize, PartialEq, Eq, Debug, Clone, Default, Getters)]
 #[get = "pub"]
 pub struct Proposal {
     store_code: Option<u64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    instantiate: Option<u64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    migrate: Option<u64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    update_admin: Option<u64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    clear_admin: Option<u64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pin_codes: Option<u64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    unpin_codes: Option<u64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    store_and_instantiate: Option<u64>,
+}
+
+/// Kind of the wasm proposals tracked for a contract.
+#[derive(Serialize, Deserialize, PartialEq, Eq, Debug, Clone, Copy)]
+#[serde(rename_all = "snake_case")]
+pub enum ProposalKind {
+    StoreCode,
+    Instantiate,
+    Migrate,
+    UpdateAdmin,
+    ClearAdmin,
+    PinCodes,
+    UnpinCodes,
+    StoreAndInstantiate,
+}
+
+impl Proposal {
+    pub fn get(&self, kind: &ProposalKind) -> Option<u64> {
+        match kind {
+            ProposalKind::StoreCode => self.store_code,
+            ProposalKind::Instantiate => self.instantiate,
+            ProposalKind::Migrate => self.migrate,
+            ProposalKind::UpdateAdmin => self.update_admin,
+            ProposalKind::ClearAdmin => self.clear_admin,
+            ProposalKind::PinCodes => self.pin_codes,
+            ProposalKind::UnpinCodes => self.unpin_codes,
+            ProposalKind::StoreAndInstantiate => self.store_and_instantiate,
+        }
+    }
+
+    fn set(&mut self, kind: &ProposalKind, id: u64) {
+        let field = match kind {
+            ProposalKind::StoreCode => &mut self.store_code,
+            ProposalKind::Instantiate => &mut self.instantiate,
+            ProposalKind::Migrate => &mut self.migrate,
+            ProposalKind::UpdateAdmin => &mut self.update_admin,
+            ProposalKind::ClearAdmin => &mut self.clear_admin,
+            ProposalKind::PinCodes => &mut self.pin_codes,
+            ProposalKind::UnpinCodes => &mut self.unpin_codes,
+            ProposalKind::StoreAndInstantiate => &mut self.store_and_instantiate,
+        };
+        *field = Some(id);
+    }
+
+    /// Latest proposal that stores the contract's code, either through store code
+    /// or store and instantiate.
+    pub fn latest_code_upload(&self) -> Option<u64> {
+        self.store_code.max(self.store_and_instantiate)
+    }
 }
 
 #[derive(Serialize, Deserialize, PartialEq, Eq, Debug, Clone, Default, Getters)]
 #[get = "pub"]
 pub struct WasmRef {
     code_id: Option<u64>,
+    /// Checksum of the wasm stored as `code_id`, when stored by beaker
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    checksum: Option<String>,
     addresses: Map<String, String>, // TODO: considering removing this since it could be retrive from chain
     proposal: Proposal,
+    /// Deployments of each label, oldest first
+    #[serde(default, skip_serializing_if = "Map::is_empty")]
+    history: Map<String, Vec<Deployment>>,
+}
+
+/// Kind of the deployments recorded in a label's history.
+#[derive(Serialize, Deserialize, PartialEq, Eq, Debug, Clone, Copy)]
+#[serde(rename_all = "snake_case")]
+pub enum DeploymentKind {
+    Instantiate,
+    Migrate,
+    /// Migration back to a code id the label ran before
+    Rollback,
+}
+
+/// Code a labeled contract was instantiated or migrated to, along with the msg and the tx it was sent in.
+#[derive(Serialize, Deserialize, PartialEq, Eq, Debug, Clone, Getters, new)]
+#[get = "pub"]
+pub struct Deployment {
+    kind: DeploymentKind,
+    code_id: u64,
+    msg: String,
+    /// File the msg was read from, relative to the workspace root. `None` for raw msgs
+    #[new(default)]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    msg_file: Option<String>,
+    #[new(default)]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    checksum: Option<String>,
+    #[new(default)]
+    #[serde(default)]
+    tx_hash: String,
+    #[new(default)]
+    #[serde(default)]
+    height: u64,
+    #[new(default)]
+    #[serde(default)]
+    timestamp: String,
+    #[new(default)]
+    #[serde(default)]
+    signer: String,
+    #[new(default)]
+    #[serde(default)]
+    beaker_version: String,
+}
+
+impl Deployment {
+    pub fn with_msg_file(self, msg_file: Option<String>) -> Self {
+        Self { msg_file, ..self }
+    }
+
+    /// The deployment as committed by the tx `response` of `signer`, stamped with the time of its block,
+    /// the running beaker version and the checksum of its code, when `client` finds it.
+    pub async fn committed(
+        self,
+        client: &Client,
+        response: &TxCommitResponse,
+        signer: &AccountId,
+    ) -> Self {
+        let checksum = client
+            .code_info(&self.code_id)
+            .await
+            .ok()
+            .map(|code_info| encode_hex(&code_info.data_hash));
+        let timestamp = client
+            .block_time(response.height.value())
+            .await
+            .map(|time| time.to_rfc3339())
+            .unwrap_or_default();
+
+        Self {
+            checksum,
+            tx_hash: response.hash.to_string(),
+            height: response.height.value(),
+            timestamp,
+            signer: signer.to_string(),
+            beaker_version: env!("CARGO_PKG_VERSION").to_string(),
+            ..self
+        }
+    }
 }
 
 #[derive(Serialize, Deserialize, PartialEq, Eq, Debug, Clone, Default)]
 pub struct State(Map<String, Map<String, WasmRef>>);
 
 macro_rules! impl_update {
-    (fn $fn:ident(.., $val:ident) ~ { $key:ident : $expr:expr }) => {
+    (fn $fn:ident(.., $val:ident) ~ { $($key:ident : $expr:expr),+ }) => {
         pub fn $fn(&self, network: &str, contract_name: &str, $val: &u64) -> Self {
             let State(m) = self;
             let mut m = m.clone();
@@ -40,12 +205,12 @@ macro_rules! impl_update {
                         .entry(contract_name.to_string())
                         .and_modify(|wasm_ref| {
                             *wasm_ref = WasmRef {
-                                $key: $expr,
+                                $($key: $expr,)+
                                 ..wasm_ref.clone()
                             };
                         })
                         .or_insert_with(|| WasmRef {
-                            $key: $expr,
+                            $($key: $expr,)+
                             ..Default::default()
                         });
                 })
@@ -53,7 +218,7 @@ macro_rules! impl_update {
                     Map::from([(
                         contract_name.to_string(),
                         WasmRef {
-                            $key: $expr,
+                            $($key: $expr,)+
                             ..Default::default()
                         },
                     )])
@@ -75,43 +240,73 @@ impl State {
         })
     }
 
+    /// Load the state file, upgrading it to the current schema in memory if an older beaker wrote it.
+    /// The file itself is only rewritten by `update_state_file`.
     pub fn load(path: &PathBuf) -> Result<Self> {
-        let path_str = path.to_string_lossy();
-        let content = fs::read_to_string(path)
-            .with_context(|| format!("Unable to read from `{path_str}`"))?;
-        serde_json::from_str(&content)
-            .with_context(|| format!("Unable to serialize state file `{path_str}`"))
-    }
-
-    pub fn save(&self, path: &PathBuf) -> Result<()> {
-        let path_str = path.to_string_lossy();
-        let content =
-            serde_json::to_string_pretty(self).with_context(|| "Unable to serialize to json")?;
-        fs::write(path, content).with_context(|| format!("Unabel to write to `{path_str}`"))
+        let (_, state) = read_versioned(path)?;
+        Ok(state)
     }
 
     pub fn load_by_network(network: Network, root: PathBuf) -> Result<Self> {
         Self::load(&root.join(STATE_DIR).join(match network.network_variant() {
-            NetworkVariant::Local => STATE_FILE_LOCAL,
+            NetworkVariant::Local | NetworkVariant::Simulated => STATE_FILE_LOCAL,
             NetworkVariant::Shared => STATE_FILE_SHARED,
         }))
     }
 
+    /// Apply `f` to the state file while holding the lock on the state directory, so that
+    /// concurrent beaker processes do not lose each other's updates.
+    /// Fails rather than starting over if the existing file can not be loaded.
     pub fn update_state_file(
         network_variant: &NetworkVariant,
         root: PathBuf,
-        f: &(dyn Fn(&State) -> State),
+        f: &dyn Fn(&State) -> State,
     ) -> Result<State> {
         let state_dir = &root.join(STATE_DIR);
         let state_file = &state_dir.join(match network_variant {
-            NetworkVariant::Local => STATE_FILE_LOCAL,
+            NetworkVariant::Local | NetworkVariant::Simulated => STATE_FILE_LOCAL,
             NetworkVariant::Shared => STATE_FILE_SHARED,
         });
         fs::create_dir_all(state_dir)?;
-        let s = State::load(state_file).unwrap_or_default();
+        let _lock = lock_state_dir(state_dir)?;
+
+        let s = if state_file.exists() {
+            let (version, s) = read_versioned(state_file).with_context(|| {
+                format!(
+                    "Refusing to overwrite `{}`, fix or remove it first",
+                    state_file.display()
+                )
+            })?;
+            if version > STATE_VERSION {
+                bail!(
+                    "State file `{}` has schema version {version}, which is newer than version {STATE_VERSION} supported by beaker {}. Please upgrade beaker to update it",
+                    state_file.display(),
+                    env!("CARGO_PKG_VERSION")
+                );
+            }
+            if version < STATE_VERSION {
+                let backup = backup_path(state_file, version);
+                fs::copy(state_file, &backup).with_context(|| {
+                    format!(
+                        "Unable to back up `{}` to `{}`",
+                        state_file.display(),
+                        backup.display()
+                    )
+                })?;
+                println!(
+                    "{} upgraded state file `{}` from version {version} to {STATE_VERSION}, the original is kept in `{}`",
+                    style("NOTE:").bold(),
+                    state_file.display(),
+                    backup.display()
+                );
+            }
+            s
+        } else {
+            State::default()
+        };
 
         let s = f(&s);
-        s.save(state_file)?;
+        write_versioned(state_file, &s)?;
 
         Ok(s)
     }
@@ -140,8 +335,181 @@ impl State {
         State(m)
     }
 
-    impl_update! { fn update_code_id(.., code_id) ~ { code_id: Some(*code_id) } }
-    impl_update! { fn update_proposal_store_code_id(.., id) ~ { proposal: Proposal { store_code: Some(*id) }}}
+    // a code id set without its wasm, eg. from a proposal, leaves the checksum unknown
+    impl_update! { fn update_code_id(.., code_id) ~ { code_id: Some(*code_id), checksum: None } }
+
+    /// Set the code id along with the checksum of the wasm stored as that code.
+    pub fn update_code(
+        &self,
+        network: &str,
+        contract_name: &str,
+        code_id: &u64,
+        checksum: &str,
+    ) -> Self {
+        let State(mut m) = self.update_code_id(network, contract_name, code_id);
+        if let Some(wasm_ref) = m
+            .get_mut(network)
+            .and_then(|contracts| contracts.get_mut(contract_name))
+        {
+            wasm_ref.checksum = Some(checksum.to_string());
+        }
+
+        State(m)
+    }
+
+    /// Append `deployment` to the history of the labeled contract.
+    pub fn record_deployment(
+        &self,
+        network: &str,
+        contract_name: &str,
+        label: &str,
+        deployment: &Deployment,
+    ) -> Self {
+        let State(m) = self;
+        let mut m = m.clone();
+
+        m.entry(network.to_string())
+            .or_default()
+            .entry(contract_name.to_string())
+            .or_default()
+            .history
+            .entry(label.to_string())
+            .or_default()
+            .push(deployment.clone());
+
+        State(m)
+    }
+
+    pub fn update_proposal_id(
+        &self,
+        network: &str,
+        contract_name: &str,
+        kind: &ProposalKind,
+        id: &u64,
+    ) -> Self {
+        let State(m) = self;
+        let mut m = m.clone();
+
+        m.entry(network.to_string())
+            .or_default()
+            .entry(contract_name.to_string())
+            .or_default()
+            .proposal
+            .set(kind, *id);
+
+        State(m)
+    }
+
+    pub fn update_proposal_store_code_id(
+        &self,
+        network: &str,
+        contract_name: &str,
+        id: &u64,
+    ) -> Self {
+        self.update_proposal_id(network, contract_name, &ProposalKind::StoreCode, id)
+    }
+}
+
+type Migration = fn(&mut JsonMap<String, Value>) -> Result<()>;
+
+/// Upgrades of the state file, the one at index `i` upgrades version `i + 1` to `i + 2`.
+const MIGRATIONS: &[Migration] = &[];
+
+/// Read the state file along with the schema version it was written with, upgraded in memory.
+fn read_versioned(path: &PathBuf) -> Result<(u64, State)> {
+    let path_str = path.to_string_lossy();
+    let content =
+        fs::read_to_string(path).with_context(|| format!("Unable to read from `{path_str}`"))?;
+    let mut m: JsonMap<String, Value> = serde_json::from_str(&content)
+        .with_context(|| format!("Unable to serialize state file `{path_str}`"))?;
+
+    let version = take_schema_version(&mut m)?;
+    migrate(&mut m, version, MIGRATIONS)?;
+
+    let state = serde_json::from_value(Value::Object(m))
+        .with_context(|| format!("Unable to serialize state file `{path_str}`"))?;
+    Ok((version, state))
+}
+
+/// Remove the version markers from `m`, returning the version they mark.
+fn take_schema_version(m: &mut JsonMap<String, Value>) -> Result<u64> {
+    let markers = m
+        .keys()
+        .filter(|k| k.starts_with(VERSION_KEY_PREFIX))
+        .cloned()
+        .collect::<Vec<_>>();
+
+    let mut version = 1;
+    for marker in markers {
+        m.remove(&marker);
+        let v = marker[VERSION_KEY_PREFIX.len()..]
+            .parse::<u64>()
+            .with_context(|| format!("Invalid state file version marker `{marker}`"))?;
+        version = version.max(v);
+    }
+    Ok(version)
+}
+
+fn migrate(m: &mut JsonMap<String, Value>, from: u64, migrations: &[Migration]) -> Result<()> {
+    for (i, migration) in migrations
+        .iter()
+        .enumerate()
+        .skip(from.saturating_sub(1) as usize)
+    {
+        migration(m).with_context(|| {
+            format!(
+                "Unable to migrate state file from version {} to {}",
+                i + 1,
+                i + 2
+            )
+        })?;
+    }
+    Ok(())
+}
+
+fn backup_path(path: &Path, version: u64) -> PathBuf {
+    let mut file_name = path.file_name().unwrap_or_default().to_os_string();
+    file_name.push(format!(".v{version}.bak"));
+    path.with_file_name(file_name)
+}
+
+/// Write the state with the current schema version marker, only to be called by
+/// `State::update_state_file` while holding the lock.
+fn write_versioned(path: &PathBuf, state: &State) -> Result<()> {
+    let path_str = path.to_string_lossy();
+    let mut content = JsonMap::from_iter([(
+        format!("{VERSION_KEY_PREFIX}{STATE_VERSION}"),
+        Value::Object(JsonMap::new()),
+    )]);
+    if let Value::Object(networks) =
+        serde_json::to_value(state).with_context(|| "Unable to serialize to json")?
+    {
+        content.extend(networks);
+    }
+
+    let content =
+        serde_json::to_string_pretty(&content).with_context(|| "Unable to serialize to json")?;
+
+    // write next to the file then rename over it, so readers never see a partially written state
+    let mut tmp_name = path.file_name().unwrap_or_default().to_os_string();
+    tmp_name.push(format!(".{}.tmp", std::process::id()));
+    let tmp_path = path.with_file_name(tmp_name);
+    fs::write(&tmp_path, content).with_context(|| format!("Unabel to write to `{path_str}`"))?;
+    fs::rename(&tmp_path, path).with_context(|| format!("Unabel to write to `{path_str}`"))
+}
+
+/// Exclusive advisory lock on the state directory, held until the returned file is dropped.
+fn lock_state_dir(state_dir: &Path) -> Result<File> {
+    let lock_path = state_dir.join(STATE_LOCK_FILE);
+    let lock = OpenOptions::new()
+        .create(true)
+        .write(true)
+        .truncate(false)
+        .open(&lock_path)
+        .with_context(|| format!("Unable to open `{}`", lock_path.display()))?;
+    lock.lock_exclusive()
+        .with_context(|| format!("Unable to lock `{}`", state_dir.display()))?;
+    Ok(lock)
 }
 
 #[cfg(test)]
@@ -269,12 +637,166 @@ mod tests {
             ]),
         )]));
 
-        state.save(&path).unwrap();
+        write_versioned(&path, &state).unwrap();
         let loaded_state = State::load(&path).unwrap();
 
         assert_eq!(state, loaded_state);
     }
 
+    #[test]
+    fn load_leaves_unversioned_file_untouched() {
+        let temp = assert_fs::TempDir::new().unwrap();
+        let content = r#"{ "localosmosis": { "counter": { "code_id": 1, "addresses": {}, "proposal": {} } } }"#;
+        let path = setup(&temp, content);
+
+        let state = State::load(&path).unwrap();
+
+        assert_eq!(
+            state.get_ref("localosmosis", "counter").unwrap().code_id(),
+            &Some(1)
+        );
+        assert_eq!(fs::read_to_string(&path).unwrap(), content);
+        assert_eq!(fs::read_dir(temp.path()).unwrap().count(), 1);
+    }
+
+    #[test]
+    fn load_network_named_version() {
+        let temp = assert_fs::TempDir::new().unwrap();
+        let path = setup(
+            &temp,
+            r#"{ "$schema_v1": {}, "version": { "counter": { "code_id": 1, "addresses": {}, "proposal": {} } } }"#,
+        );
+
+        assert_eq!(
+            State::load(&path)
+                .unwrap()
+                .get_ref("version", "counter")
+                .unwrap()
+                .code_id(),
+            &Some(1)
+        );
+    }
+
+    #[test]
+    fn written_file_is_readable_by_beaker_without_versions() {
+        #[derive(Deserialize)]
+        struct UnversionedWasmRef {
+            #[allow(dead_code)]
+            code_id: Option<u64>,
+            #[allow(dead_code)]
+            addresses: Map<String, String>,
+            #[allow(dead_code)]
+            proposal: Proposal,
+        }
+
+        let temp = assert_fs::TempDir::new().unwrap();
+        let path = state_file(&temp);
+        let state = State::default().update_code_id("localosmosis", "counter", &1);
+
+        write_versioned(&path, &state).unwrap();
+
+        let content = fs::read_to_string(&path).unwrap();
+        assert!(content.contains(&format!(r#""{VERSION_KEY_PREFIX}{STATE_VERSION}": {{}}"#)));
+        let unversioned: Map<String, Map<String, UnversionedWasmRef>> =
+            serde_json::from_str(&content).unwrap();
+        assert!(unversioned["localosmosis"].contains_key("counter"));
+    }
+
+    #[test]
+    fn migrate_from_file_version() {
+        let migrations: &[Migration] = &[
+            |m| {
+                m.insert("v2".to_string(), Value::Object(JsonMap::new()));
+                Ok(())
+            },
+            |m| {
+                m.insert("v3".to_string(), Value::Object(JsonMap::new()));
+                Ok(())
+            },
+        ];
+
+        let mut m = JsonMap::new();
+        migrate(&mut m, 1, migrations).unwrap();
+        assert!(m.contains_key("v2") && m.contains_key("v3"));
+
+        let mut m = JsonMap::new();
+        migrate(&mut m, 2, migrations).unwrap();
+        assert!(!m.contains_key("v2") && m.contains_key("v3"));
+
+        let mut m = JsonMap::new();
+        migrate(&mut m, 3, migrations).unwrap();
+        assert!(m.is_empty());
+    }
+
+    #[test]
+    fn update_state_file_refuses_file_of_newer_version() {
+        let temp = assert_fs::TempDir::new().unwrap();
+        let state_dir = temp.path().join(STATE_DIR);
+        fs::create_dir_all(&state_dir).unwrap();
+        let content = r#"{ "$schema_v999": {}, "localosmosis": {} }"#;
+        fs::write(state_dir.join(STATE_FILE_LOCAL), content).unwrap();
+
+        assert!(State::load(&state_dir.join(STATE_FILE_LOCAL)).is_ok());
+        assert!(State::update_state_file(
+            &NetworkVariant::Local,
+            temp.to_path_buf(),
+            &|s: &State| { s.clone() }
+        )
+        .is_err());
+        assert_eq!(
+            fs::read_to_string(state_dir.join(STATE_FILE_LOCAL)).unwrap(),
+            content
+        );
+    }
+
+    #[test]
+    fn update_state_file_keeps_concurrent_updates() {
+        let temp = assert_fs::TempDir::new().unwrap();
+
+        let handles = (0..8u64)
+            .map(|i| {
+                let root = temp.to_path_buf();
+                std::thread::spawn(move || {
+                    State::update_state_file(&NetworkVariant::Local, root, &|s: &State| {
+                        s.update_code_id("localosmosis", &format!("contract_{i}"), &i)
+                    })
+                    .unwrap();
+                })
+            })
+            .collect::<Vec<_>>();
+        handles.into_iter().for_each(|h| h.join().unwrap());
+
+        let state = State::load(&temp.path().join(STATE_DIR).join(STATE_FILE_LOCAL)).unwrap();
+        for i in 0..8u64 {
+            assert_eq!(
+                state
+                    .get_ref("localosmosis", &format!("contract_{i}"))
+                    .unwrap()
+                    .code_id(),
+                &Some(i)
+            );
+        }
+    }
+
+    #[test]
+    fn update_state_file_fails_on_unparsable_file() {
+        let temp = assert_fs::TempDir::new().unwrap();
+        let state_dir = temp.path().join(STATE_DIR);
+        fs::create_dir_all(&state_dir).unwrap();
+        fs::write(state_dir.join(STATE_FILE_LOCAL), "{ not json").unwrap();
+
+        assert!(State::update_state_file(
+            &NetworkVariant::Local,
+            temp.to_path_buf(),
+            &|s: &State| s.update_code_id("localosmosis", "counter", &1)
+        )
+        .is_err());
+        assert_eq!(
+            fs::read_to_string(state_dir.join(STATE_FILE_LOCAL)).unwrap(),
+            "{ not json"
+        );
+    }
+
     #[test]
     fn update_code_id_test() {
         let empty_state = State(Map::new());
@@ -347,6 +869,92 @@ mod tests {
         assert_eq!(updated_state, state);
     }
 
+    #[test]
+    fn update_code_records_checksum_until_code_id_changes() {
+        let state = State(Map::new()).update_code("localosmosis", "counter", &1, "abc");
+        let wasm_ref = state.get_ref("localosmosis", "counter").unwrap();
+        assert_eq!(wasm_ref.code_id(), &Some(1));
+        assert_eq!(wasm_ref.checksum(), &Some("abc".to_string()));
+
+        let state = state.update_code_id("localosmosis", "counter", &2);
+        let wasm_ref = state.get_ref("localosmosis", "counter").unwrap();
+        assert_eq!(wasm_ref.code_id(), &Some(2));
+        assert_eq!(wasm_ref.checksum(), &None);
+    }
+
+    #[test]
+    fn record_deployment_appends_to_label_history() {
+        let state = State(Map::new())
+            .update_code_id("localosmosis", "counter", &1)
+            .record_deployment(
+                "localosmosis",
+                "counter",
+                "default",
+                &Deployment::new(DeploymentKind::Instantiate, 1, "{}".to_string()),
+            )
+            .update_code_id("localosmosis", "counter", &2)
+            .record_deployment(
+                "localosmosis",
+                "counter",
+                "default",
+                &Deployment::new(DeploymentKind::Migrate, 2, "{}".to_string()),
+            );
+
+        let history = state
+            .get_ref("localosmosis", "counter")
+            .unwrap()
+            .history()
+            .get("default")
+            .cloned()
+            .unwrap();
+        assert_eq!(
+            history.iter().map(|d| *d.code_id()).collect::<Vec<_>>(),
+            vec![1, 2]
+        );
+        assert_eq!(history[1].kind(), &DeploymentKind::Migrate);
+    }
+
+    #[test]
+    fn load_history_recorded_without_tx_details() {
+        let temp = assert_fs::TempDir::new().unwrap();
+        let path = setup(
+            &temp,
+            r#"
+        {
+            "localosmosis": {
+                "counter": {
+                    "code_id": 2,
+                    "addresses": {},
+                    "proposal": {},
+                    "history": {
+                        "default": [{ "kind": "instantiate", "code_id": 2, "msg": "{}" }]
+                    }
+                }
+            }
+        }
+        "#,
+        );
+
+        let state = State::load(&path).unwrap();
+        let history = state
+            .get_ref("localosmosis", "counter")
+            .unwrap()
+            .history()
+            .get("default")
+            .cloned()
+            .unwrap();
+        assert_eq!(
+            history,
+            vec![Deployment::new(
+                DeploymentKind::Instantiate,
+                2,
+                "{}".to_string()
+            )]
+        );
+        assert_eq!(history[0].msg_file(), &None);
+        assert_eq!(history[0].tx_hash(), "");
+    }
+
     #[test]
     fn update_proposal_id_test() {
         let empty_state = State(Map::new());
@@ -360,6 +968,7 @@ mod tests {
                 WasmRef {
                     proposal: Proposal {
                         store_code: Some(1),
+                        ..Default::default()
                     },
                     ..Default::default()
                 },
@@ -377,6 +986,7 @@ mod tests {
                 WasmRef {
                     proposal: Proposal {
                         store_code: Some(99),
+                        ..Default::default()
                     },
                     ..Default::default()
                 },
@@ -395,6 +1005,7 @@ mod tests {
                 WasmRef {
                     proposal: Proposal {
                         store_code: Some(112),
+                        ..Default::default()
                     },
                     ..Default::default()
                 },
@@ -414,6 +1025,7 @@ mod tests {
                     WasmRef {
                         proposal: Proposal {
                             store_code: Some(112),
+                            ..Default::default()
                         },
                         ..Default::default()
                     },
@@ -423,6 +1035,7 @@ mod tests {
                     WasmRef {
                         proposal: Proposal {
                             store_code: Some(666),
+                            ..Default::default()
                         },
                         ..Default::default()
                     },
@@ -433,6 +1046,29 @@ mod tests {
         assert_eq!(updated_state, state);
     }
 
+    #[test]
+    fn update_proposal_id_keeps_other_kinds() {
+        let updated_state = State(Map::new())
+            .update_proposal_id("localosmosis", "counter", &ProposalKind::StoreCode, &1)
+            .update_proposal_id("localosmosis", "counter", &ProposalKind::Migrate, &2)
+            .update_proposal_id(
+                "localosmosis",
+                "counter",
+                &ProposalKind::StoreAndInstantiate,
+                &3,
+            );
+
+        let proposal = updated_state
+            .get_ref("localosmosis", "counter")
+            .unwrap()
+            .proposal;
+
+        assert_eq!(proposal.get(&ProposalKind::StoreCode), Some(1));
+        assert_eq!(proposal.get(&ProposalKind::Migrate), Some(2));
+        assert_eq!(proposal.get(&ProposalKind::Instantiate), None);
+        assert_eq!(proposal.latest_code_upload(), Some(3));
+    }
+
     #[test]
     fn update_address_test() {
         // No code id, no update, since contract_name `counter` doesn't exist
@@ -461,7 +1097,9 @@ mod tests {
                 "counter".to_string(),
                 WasmRef {
                     code_id: Some(1),
-                    proposal: Proposal { store_code: None },
+                    checksum: None,
+                    history: Map::new(),
+                    proposal: Proposal::default(),
                     addresses: Map::from([(
                         "default".to_string(),
                         "osmo1252netaxc2c0n4g4zm428d75gkl0dplrksd32g35yfylldu66nzqjtjn85"
@@ -485,6 +1123,8 @@ mod tests {
                 "counter".to_string(),
                 WasmRef {
                     code_id: Some(1),
+                    checksum: None,
+                    history: Map::new(),
                     addresses: Map::from([
                         (
                             "default".to_string(),
@@ -497,7 +1137,7 @@ mod tests {
                                 .to_string(),
                         ),
                     ]),
-                    proposal: Proposal { store_code: None },
+                    proposal: Proposal::default(),
                 },
             )]),
         )]));
@@ -517,7 +1157,9 @@ mod tests {
                 "counter".to_string(),
                 WasmRef {
                     code_id: Some(1),
-                    proposal: Proposal { store_code: None },
+                    checksum: None,
+                    history: Map::new(),
+                    proposal: Proposal::default(),
                     addresses: Map::from([
                         (
                             "default".to_string(),
@@ -0,0 +1,197 @@
+use std::str::FromStr;
+
+use anyhow::{anyhow, bail, Context, Result};
+
+/// Part of a cw-storage-plus key, parsed from `<type>:<value>`, eg. `addr:osmo1...` or `u64:1`.
+/// Untyped values are string keys.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct KeyPart(Vec<u8>);
+
+impl KeyPart {
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.0
+    }
+}
+
+impl FromStr for KeyPart {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (ty, value) = s.split_once(':').unwrap_or(("string", s));
+
+        // signed ints have their sign bit flipped to keep the order of their bytes
+        let bytes = match ty {
+            "string" | "str" | "addr" => value.as_bytes().to_vec(),
+            "u8" => value.parse::<u8>()?.to_be_bytes().to_vec(),
+            "u16" => value.parse::<u16>()?.to_be_bytes().to_vec(),
+            "u32" => value.parse::<u32>()?.to_be_bytes().to_vec(),
+            "u64" => value.parse::<u64>()?.to_be_bytes().to_vec(),
+            "u128" => value.parse::<u128>()?.to_be_bytes().to_vec(),
+            "i8" => (value.parse::<i8>()? as u8 ^ (1 << 7))
+                .to_be_bytes()
+                .to_vec(),
+            "i16" => (value.parse::<i16>()? as u16 ^ (1 << 15))
+                .to_be_bytes()
+                .to_vec(),
+            "i32" => (value.parse::<i32>()? as u32 ^ (1 << 31))
+                .to_be_bytes()
+                .to_vec(),
+            "i64" => (value.parse::<i64>()? as u64 ^ (1 << 63))
+                .to_be_bytes()
+                .to_vec(),
+            "i128" => (value.parse::<i128>()? as u128 ^ (1 << 127))
+                .to_be_bytes()
+                .to_vec(),
+            "hex" => decode_hex(value)?,
+            "base64" => base64::decode(value)?,
+            ty => bail!("Unsupported key type `{ty}` in `{s}`"),
+        };
+
+        Ok(KeyPart(bytes))
+    }
+}
+
+/// Storage key of an `Item` under `namespace`, or of a `Map` entry when `key` is not empty.
+/// All but the last part of the path are prefixed by their length, as in cw-storage-plus.
+pub fn encode_key(namespace: &str, key: &[KeyPart]) -> Result<Vec<u8>> {
+    let Some((last, prefixes)) = key.split_last() else {
+        return Ok(namespace.as_bytes().to_vec());
+    };
+
+    let mut encoded = vec![];
+    for prefix in [namespace.as_bytes()]
+        .into_iter()
+        .chain(prefixes.iter().map(KeyPart::as_bytes))
+    {
+        let len = u16::try_from(prefix.len()).map_err(|_| anyhow!("Key part is too long"))?;
+        encoded.extend_from_slice(&len.to_be_bytes());
+        encoded.extend_from_slice(prefix);
+    }
+    encoded.extend_from_slice(last.as_bytes());
+
+    Ok(encoded)
+}
+
+/// Namespace and key parts of a storage key. Length prefixed parts are taken for as long as
+/// they fit, so a key whose raw last part happens to look prefixed is split further.
+/// Keys without a valid prefixed namespace are read as an `Item`.
+pub fn decode_key(raw: &[u8]) -> (String, Vec<Vec<u8>>) {
+    let mut parts = vec![];
+    let mut rest = raw;
+    while let [hi, lo, tail @ ..] = rest {
+        let len = u16::from_be_bytes([*hi, *lo]) as usize;
+        if len == 0 || len > tail.len() {
+            break;
+        }
+        parts.push(tail[..len].to_vec());
+        rest = &tail[len..];
+    }
+
+    match parts.first().map(|ns| String::from_utf8(ns.clone())) {
+        Some(Ok(namespace)) => {
+            if !rest.is_empty() {
+                parts.push(rest.to_vec());
+            }
+            (namespace, parts.split_off(1))
+        }
+        _ => (String::from_utf8_lossy(raw).to_string(), vec![]),
+    }
+}
+
+/// Key part as a string when printable, otherwise as `0x` prefixed hex.
+pub fn display_key_part(part: &[u8]) -> String {
+    match std::str::from_utf8(part) {
+        Ok(s) if !s.is_empty() && !s.chars().any(char::is_control) => s.to_string(),
+        _ => format!("0x{}", encode_hex(part)),
+    }
+}
+
+pub fn encode_hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{b:02x}")).collect()
+}
+
+pub fn decode_hex(s: &str) -> Result<Vec<u8>> {
+    let s = s.trim_start_matches("0x");
+    if !s.bytes().all(|b| b.is_ascii_hexdigit()) {
+        bail!("Invalid hex `{s}`: non hex digit");
+    }
+    if !s.len().is_multiple_of(2) {
+        bail!("Invalid hex `{s}`: odd length");
+    }
+    (0..s.len())
+        .step_by(2)
+        .map(|i| u8::from_str_radix(&s[i..i + 2], 16).with_context(|| format!("Invalid hex `{s}`")))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parts(parts: &[&str]) -> Vec<KeyPart> {
+        parts.iter().map(|p| p.parse().unwrap()).collect()
+    }
+
+    #[test]
+    fn parse_typed_key_parts() {
+        assert_eq!(parts(&["foo"]), parts(&["string:foo"]));
+        assert_eq!(
+            "u64:1".parse::<KeyPart>().unwrap().as_bytes(),
+            &[0, 0, 0, 0, 0, 0, 0, 1]
+        );
+        assert_eq!(
+            "i32:-1".parse::<KeyPart>().unwrap().as_bytes(),
+            &[0x7f, 0xff, 0xff, 0xff]
+        );
+        assert_eq!(
+            "i32:1".parse::<KeyPart>().unwrap().as_bytes(),
+            &[0x80, 0, 0, 1]
+        );
+        assert_eq!(
+            "hex:0x00ff".parse::<KeyPart>().unwrap().as_bytes(),
+            &[0, 0xff]
+        );
+        assert!("f32:1.0".parse::<KeyPart>().is_err());
+    }
+
+    #[test]
+    fn encode_item_and_map_keys() {
+        assert_eq!(encode_key("config", &[]).unwrap(), b"config".to_vec());
+        assert_eq!(
+            encode_key("balance", &parts(&["addr:osmo1abc"])).unwrap(),
+            [b"\x00\x07balance".as_slice(), b"osmo1abc"].concat()
+        );
+        assert_eq!(
+            encode_key("allowance", &parts(&["addr:owner", "u8:1"])).unwrap(),
+            [b"\x00\x09allowance".as_slice(), b"\x00\x05owner", &[1]].concat()
+        );
+    }
+
+    #[test]
+    fn decode_encoded_keys() {
+        let key = encode_key("allowance", &parts(&["addr:owner", "u64:258"])).unwrap();
+        assert_eq!(
+            decode_key(&key),
+            (
+                "allowance".to_string(),
+                vec![b"owner".to_vec(), 258u64.to_be_bytes().to_vec()]
+            )
+        );
+        assert_eq!(decode_key(b"config"), ("config".to_string(), vec![]));
+    }
+
+    #[test]
+    fn display_key_parts() {
+        assert_eq!(display_key_part(b"osmo1abc"), "osmo1abc");
+        assert_eq!(display_key_part(&[0, 1]), "0x0001");
+    }
+
+    #[test]
+    fn decode_hex_rejects_non_hex_digits() {
+        assert_eq!(decode_hex("0x00ff").unwrap(), vec![0, 255]);
+        assert!(decode_hex("0").is_err());
+        assert!(decode_hex("+1").is_err());
+        assert!(decode_hex("é0").is_err());
+        assert!(decode_hex("0é").is_err());
+    }
+}
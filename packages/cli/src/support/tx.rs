@@ -0,0 +1,275 @@
+use std::fs;
+use std::path::Path;
+
+use anyhow::{anyhow, bail, Context, Result};
+use cosmos_sdk_proto::cosmos::tx::v1beta1::{AuthInfo as ProtoAuthInfo, TxRaw};
+use cosmos_sdk_proto::traits::Message;
+use cosmrs::crypto::secp256k1::SigningKey;
+use cosmrs::tx::{self, SignDoc, SignerInfo};
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+
+use super::amino;
+use super::multisig::Multisig;
+use super::proto::MessageExt;
+
+/// Transaction written by `--generate-only`, to be signed by `beaker tx sign`.
+/// Holds everything needed for signing so that it can be done without network access.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct UnsignedTx {
+    pub chain_id: String,
+    pub account_number: u64,
+    pub sequence: u64,
+    pub signer: String,
+    /// Base64 encoded protobuf `TxBody`
+    pub body: String,
+    /// Base64 encoded protobuf `AuthInfo`, carrying the fee but no signer info
+    pub auth_info: String,
+    /// Set when `signer` is a multisig account, members then sign in amino json sign mode
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub multisig: Option<Multisig>,
+}
+
+impl UnsignedTx {
+    pub fn read(path: &Path) -> Result<Self> {
+        read_json(path)
+    }
+
+    pub fn write(&self, path: &Path) -> Result<()> {
+        write_json(path, self)
+    }
+
+    pub fn sign(&self, signing_key: &SigningKey, account_prefix: &str) -> Result<SignedTx> {
+        if self.multisig.is_some() {
+            bail!("Transaction is for a multisig account, it must be signed by its members");
+        }
+
+        let signer = signing_key
+            .public_key()
+            .account_id(account_prefix)
+            .map_err(|e| anyhow!(e))?;
+
+        if signer.to_string() != self.signer {
+            bail!(
+                "Signer `{signer}` does not match the signer of the transaction `{}`",
+                self.signer
+            );
+        }
+
+        let auth_info_bytes = base64::decode(&self.auth_info)?;
+        let mut auth_info = tx::AuthInfo::try_from(ProtoAuthInfo::decode(&auth_info_bytes[..])?)
+            .map_err(|e| anyhow!(e))?;
+        auth_info.signer_infos = vec![SignerInfo::single_direct(
+            Some(signing_key.public_key()),
+            self.sequence,
+        )];
+
+        let sign_doc = SignDoc {
+            body_bytes: base64::decode(&self.body)?,
+            auth_info_bytes: auth_info.into_bytes().map_err(|e| anyhow!(e))?,
+            chain_id: self.chain_id.clone(),
+            account_number: self.account_number,
+        };
+        let tx_raw = sign_doc.sign(signing_key).map_err(|e| anyhow!(e))?;
+
+        Ok(SignedTx {
+            chain_id: self.chain_id.clone(),
+            signer: self.signer.clone(),
+            tx: base64::encode(tx_raw.to_bytes().map_err(|e| anyhow!(e))?),
+        })
+    }
+
+    /// Sign as one of the members of the multisig account.
+    pub fn sign_multisig(
+        &self,
+        signing_key: &SigningKey,
+        account_prefix: &str,
+    ) -> Result<MultisigSignature> {
+        let multisig = self
+            .multisig
+            .as_ref()
+            .with_context(|| "Transaction is not for a multisig account")?;
+
+        let public_key = signing_key.public_key().to_bytes();
+        let signer = signing_key
+            .public_key()
+            .account_id(account_prefix)
+            .map_err(|e| anyhow!(e))?;
+
+        if multisig.member_index(&public_key)?.is_none() {
+            bail!(
+                "`{signer}` is not a member of the multisig `{}`",
+                self.signer
+            );
+        }
+
+        let signature = signing_key
+            .sign(&self.amino_sign_bytes()?)
+            .map_err(|e| anyhow!(e))?;
+
+        Ok(MultisigSignature {
+            signer: signer.to_string(),
+            public_key: base64::encode(public_key),
+            signature: base64::encode(signature.to_bytes()),
+        })
+    }
+
+    /// Combine the members' signatures into a signed transaction of the multisig account.
+    pub fn combine(&self, signatures: &[MultisigSignature]) -> Result<SignedTx> {
+        let multisig = self
+            .multisig
+            .as_ref()
+            .with_context(|| "Transaction is not for a multisig account")?;
+
+        let (signer_info, signature) =
+            multisig.combine(signatures, &self.amino_sign_bytes()?, self.sequence)?;
+
+        let mut auth_info = ProtoAuthInfo::decode(&base64::decode(&self.auth_info)?[..])?;
+        auth_info.signer_infos = vec![signer_info];
+
+        let tx_raw = TxRaw {
+            body_bytes: base64::decode(&self.body)?,
+            auth_info_bytes: auth_info.to_bytes()?,
+            signatures: vec![signature],
+        };
+
+        Ok(SignedTx {
+            chain_id: self.chain_id.clone(),
+            signer: self.signer.clone(),
+            tx: base64::encode(tx_raw.to_bytes()?),
+        })
+    }
+
+    fn amino_sign_bytes(&self) -> Result<Vec<u8>> {
+        amino::sign_bytes(
+            &self.chain_id,
+            self.account_number,
+            self.sequence,
+            &base64::decode(&self.body)?,
+            &base64::decode(&self.auth_info)?,
+        )
+    }
+}
+
+/// Signature of a single multisig member written by `beaker tx sign`,
+/// to be combined by `beaker tx multisign`.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct MultisigSignature {
+    pub signer: String,
+    /// Base64 encoded compressed secp256k1 public key of the member
+    pub public_key: String,
+    /// Base64 encoded signature over the amino json sign bytes
+    pub signature: String,
+}
+
+impl MultisigSignature {
+    pub fn read(path: &Path) -> Result<Self> {
+        read_json(path)
+    }
+
+    pub fn write(&self, path: &Path) -> Result<()> {
+        write_json(path, self)
+    }
+}
+
+/// Transaction written by `beaker tx sign`, ready for `beaker tx broadcast`.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct SignedTx {
+    pub chain_id: String,
+    pub signer: String,
+    /// Base64 encoded protobuf `TxRaw`
+    pub tx: String,
+}
+
+impl SignedTx {
+    pub fn read(path: &Path) -> Result<Self> {
+        read_json(path)
+    }
+
+    pub fn write(&self, path: &Path) -> Result<()> {
+        write_json(path, self)
+    }
+
+    pub fn to_raw(&self) -> Result<tx::Raw> {
+        tx::Raw::from_bytes(&base64::decode(&self.tx)?).map_err(|e| anyhow!(e))
+    }
+}
+
+fn read_json<T: DeserializeOwned>(path: &Path) -> Result<T> {
+    let content = fs::read_to_string(path)
+        .with_context(|| format!("Unable to read `{}`", path.to_string_lossy()))?;
+    serde_json::from_str(&content)
+        .with_context(|| format!("Unable to parse `{}`", path.to_string_lossy()))
+}
+
+fn write_json<T: Serialize>(path: &Path, value: &T) -> Result<()> {
+    fs::write(path, serde_json::to_string_pretty(value)?)
+        .with_context(|| format!("Unable to write `{}`", path.to_string_lossy()))
+}
+
+#[cfg(test)]
+mod tests {
+    use cosmrs::tx::{Body, Fee};
+    use cosmrs::{Any, Coin, Tx};
+
+    use super::*;
+
+    fn unsigned_tx(signer: &str) -> UnsignedTx {
+        let fee = Fee::from_amount_and_gas(
+            Coin {
+                denom: "uosmo".parse().unwrap(),
+                amount: 1000u64.into(),
+            },
+            100_000u64,
+        );
+        let auth_info = tx::AuthInfo {
+            signer_infos: vec![],
+            fee,
+        };
+
+        UnsignedTx {
+            chain_id: "osmosis-1".to_string(),
+            account_number: 7,
+            sequence: 3,
+            signer: signer.to_string(),
+            // a body with a memo, as an empty one is encoded to no bytes at all
+            body: base64::encode(
+                Body::new(Vec::<Any>::new(), "memo", 0u32)
+                    .into_bytes()
+                    .unwrap(),
+            ),
+            auth_info: base64::encode(auth_info.into_bytes().unwrap()),
+            multisig: None,
+        }
+    }
+
+    #[test]
+    fn sign_unsigned_tx() {
+        let signing_key = SigningKey::from_slice(&[1u8; 32]).unwrap();
+        let signer = signing_key.public_key().account_id("osmo").unwrap();
+
+        let signed_tx = unsigned_tx(signer.as_ref())
+            .sign(&signing_key, "osmo")
+            .unwrap();
+        let tx = Tx::from_bytes(&base64::decode(&signed_tx.tx).unwrap()).unwrap();
+
+        assert_eq!(signed_tx.chain_id, "osmosis-1");
+        assert_eq!(tx.auth_info.signer_infos.len(), 1);
+        assert_eq!(tx.auth_info.signer_infos[0].sequence, 3);
+        assert_eq!(tx.signatures.len(), 1);
+    }
+
+    #[test]
+    fn sign_unsigned_tx_with_other_signer() {
+        let signing_key = SigningKey::from_slice(&[1u8; 32]).unwrap();
+        let other_signer = SigningKey::from_slice(&[2u8; 32])
+            .unwrap()
+            .public_key()
+            .account_id("osmo")
+            .unwrap();
+
+        assert!(unsigned_tx(other_signer.as_ref())
+            .sign(&signing_key, "osmo")
+            .is_err());
+    }
+}
@@ -5,6 +5,8 @@ use std::{
 };
 
 use anyhow::Context;
+use cosmrs::AccountId;
+use sha2::{Digest, Sha256};
 
 use tendermint::abci::Event;
 use tendermint_rpc::{endpoint::block_results, Client, HttpClient, Order};
@@ -36,6 +38,38 @@ pub fn read_wasm(
     Ok(wasm)
 }
 
+/// Hex encoded sha256 of the wasm, as the checksum of its stored code.
+pub fn wasm_checksum(wasm: &[u8]) -> String {
+    format!("{:x}", Sha256::digest(wasm))
+}
+
+/// Address of a contract instantiated with `MsgInstantiateContract2`, derived as wasmd does
+/// from the code checksum, the creator, the salt and, with `fix_msg`, the instantiate msg.
+pub fn predict_address(
+    checksum: &[u8],
+    creator: &AccountId,
+    salt: &[u8],
+    msg: Option<&[u8]>,
+) -> Result<AccountId, anyhow::Error> {
+    let address =
+        instantiate2_address(checksum, &creator.to_bytes(), salt, msg.unwrap_or_default());
+    AccountId::new(creator.prefix(), &address).map_err(|e| anyhow::anyhow!(e))
+}
+
+fn instantiate2_address(checksum: &[u8], creator: &[u8], salt: &[u8], msg: &[u8]) -> Vec<u8> {
+    let mut key = b"wasm\0".to_vec();
+    for part in [checksum, creator, salt, msg] {
+        key.extend_from_slice(&(part.len() as u64).to_be_bytes());
+        key.extend_from_slice(part);
+    }
+
+    // module account address of `wasm` for `key`, as in ADR-028
+    let mut hasher = Sha256::new();
+    hasher.update(Sha256::digest(b"module"));
+    hasher.update(&key);
+    hasher.finalize().to_vec()
+}
+
 pub async fn get_code_id(rpc_endpoint: &str, proposal_id: &u64) -> Result<String, anyhow::Error> {
     let client = HttpClient::new(rpc_endpoint)?;
     let blocks_response = client
@@ -105,6 +139,20 @@ fn extract_code_id_for_proposal(
     code_id.ok_or_else(|| anyhow::anyhow!(format!("code_id for proposal_id {prop_id} not found")))
 }
 
+pub const MSG_UPDATE_INSTANTIATE_CONFIG_TYPE_URL: &str =
+    "/cosmwasm.wasm.v1.MsgUpdateInstantiateConfig";
+
+/// `cosmwasm.wasm.v1.MsgUpdateInstantiateConfig` from wasmd v0.30, not available in the generated protos.
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct MsgUpdateInstantiateConfig {
+    #[prost(string, tag = "1")]
+    pub sender: String,
+    #[prost(uint64, tag = "2")]
+    pub code_id: u64,
+    #[prost(message, optional, tag = "3")]
+    pub new_instantiate_permission: Option<cosmos_sdk_proto::cosmwasm::wasm::v1::AccessConfig>,
+}
+
 #[cfg(test)]
 mod tests {
 
@@ -181,4 +229,42 @@ mod tests {
         let code_id = extract_code_id_for_proposal(&9, end_block_events).unwrap();
         assert_eq!(code_id, "999");
     }
+
+    #[test]
+    fn instantiate2_address_matches_cosmwasm_std() {
+        let checksum = Sha256::digest(b"\0asm").to_vec();
+        let creator = [0x99; 20];
+
+        for salt in [b"a".as_slice(), b"beaker"] {
+            assert_eq!(
+                instantiate2_address(&checksum, &creator, salt, b""),
+                cosmwasm_std::instantiate2_address(&checksum, &creator.to_vec().into(), salt)
+                    .unwrap()
+                    .to_vec()
+            );
+        }
+        assert_ne!(
+            instantiate2_address(&checksum, &creator, b"a", b"{}"),
+            instantiate2_address(&checksum, &creator, b"a", b"")
+        );
+    }
+
+    #[test]
+    fn predicted_address_keeps_creator_prefix() {
+        let creator: AccountId = "osmo1cyyzpxplxdzkeea7kwsydadg87357qnahakaks"
+            .parse()
+            .unwrap();
+        let address = predict_address(&[0; 32], &creator, b"salt", None).unwrap();
+
+        assert_eq!(address.prefix(), "osmo");
+        assert_eq!(address.to_bytes().len(), 32);
+    }
+
+    #[test]
+    fn wasm_checksum_is_hex_sha256() {
+        assert_eq!(
+            wasm_checksum(b""),
+            "e3b0c44298fc1c149afbf4c8996fb92427ae41e4649b934ca495991b7852b855"
+        );
+    }
 }